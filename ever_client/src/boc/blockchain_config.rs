@@ -12,10 +12,14 @@
 */
 
 use crate::boc::Error;
+use crate::boc::BocCacheType;
 use crate::client::ClientContext;
 use crate::error::ClientResult;
-use ever_block::Serializable;
-use super::internal::deserialize_object_from_boc;
+use ever_block::{
+    BlockLimits, ConfigParam18, ConfigParam8, ConfigParamEnum, GasLimitsPrices, GlobalVersion,
+    MsgForwardPrices, ParamLimits, Serializable, StoragePrices,
+};
+use super::internal::{deserialize_object_from_boc, serialize_object_to_boc};
 
 #[derive(Serialize, Deserialize, Clone, ApiType, Default)]
 pub struct ParamsOfGetBlockchainConfig {
@@ -84,3 +88,450 @@ pub(crate) fn extract_config_from_zerostate(zerostate: &ever_block::ShardStateUn
 
     Ok(master.config().clone())
 }
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default, Debug, PartialEq)]
+pub struct GlobalVersionConfig {
+    /// Global version of the network.
+    pub version: u32,
+    /// Bit mask of the enabled global capabilities.
+    pub capabilities: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default, Debug, PartialEq)]
+pub struct StoragePricesConfig {
+    /// Unix time the prices are effective since.
+    pub utime_since: u32,
+    /// Price of a bit per second in 2^-16 nanotokens for the workchains.
+    pub bit_price_ps: u64,
+    /// Price of a cell per second in 2^-16 nanotokens for the workchains.
+    pub cell_price_ps: u64,
+    /// Price of a bit per second in 2^-16 nanotokens for the masterchain.
+    pub mc_bit_price_ps: u64,
+    /// Price of a cell per second in 2^-16 nanotokens for the masterchain.
+    pub mc_cell_price_ps: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default, Debug, PartialEq)]
+pub struct GasLimitsPricesConfig {
+    /// Price of gas unit in 2^-16 nanotokens.
+    pub gas_price: u64,
+    /// Maximum gas of a transaction.
+    pub gas_limit: u64,
+    /// Maximum gas of a transaction on the special accounts.
+    pub special_gas_limit: u64,
+    /// Gas credit of the external inbound messages.
+    pub gas_credit: u64,
+    /// Maximum gas of a block.
+    pub block_gas_limit: u64,
+    /// Storage due in nanotokens when the account is frozen.
+    pub freeze_due_limit: u64,
+    /// Storage due in nanotokens when the account is deleted.
+    pub delete_due_limit: u64,
+    /// Amount of gas charged with the flat price.
+    pub flat_gas_limit: u64,
+    /// Flat price of the first `flat_gas_limit` gas units in nanotokens.
+    pub flat_gas_price: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default, Debug, PartialEq)]
+pub struct ParamLimitsConfig {
+    /// Block is considered underloaded below this value.
+    pub underload: u32,
+    /// Block is finalized by the collator as soon as this value is reached.
+    pub soft_limit: u32,
+    /// Block can not exceed this value.
+    ///
+    /// Limits must satisfy `underload <= soft_limit <= hard_limit`.
+    pub hard_limit: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default, Debug, PartialEq)]
+pub struct BlockLimitsConfig {
+    /// Block size limits in bytes.
+    pub bytes: ParamLimitsConfig,
+    /// Block gas limits.
+    pub gas: ParamLimitsConfig,
+    /// Block logical time delta limits.
+    pub lt_delta: ParamLimitsConfig,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default, Debug, PartialEq)]
+pub struct MsgForwardPricesConfig {
+    /// Base price of the message forwarding in nanotokens.
+    pub lump_price: u64,
+    /// Price of a bit in 2^-16 nanotokens.
+    pub bit_price: u64,
+    /// Price of a cell in 2^-16 nanotokens.
+    pub cell_price: u64,
+    /// IHR price factor in 2^-16 units.
+    pub ihr_price_factor: u32,
+    /// Part of the forwarding fee taken by the current validators in 2^-16 units.
+    pub first_frac: u16,
+    /// Part of the forwarding fee taken by the next validators in 2^-16 units.
+    pub next_frac: u16,
+}
+
+/// Blockchain config params used by the transaction executor.
+///
+/// Missing params are not present in the config (on decoding)
+/// or are left unchanged (on encoding).
+#[derive(Serialize, Deserialize, Clone, ApiType, Default, Debug, PartialEq)]
+pub struct BlockchainConfigParams {
+    /// Global version and capabilities (param 8).
+    pub global_version: Option<GlobalVersionConfig>,
+    /// Storage prices (param 18) ordered by `utime_since`.
+    pub storage_prices: Option<Vec<StoragePricesConfig>>,
+    /// Masterchain gas limits and prices (param 20).
+    pub masterchain_gas_prices: Option<GasLimitsPricesConfig>,
+    /// Workchain gas limits and prices (param 21).
+    pub gas_prices: Option<GasLimitsPricesConfig>,
+    /// Masterchain block limits (param 22).
+    pub masterchain_block_limits: Option<BlockLimitsConfig>,
+    /// Workchain block limits (param 23).
+    pub block_limits: Option<BlockLimitsConfig>,
+    /// Masterchain message forward prices (param 24).
+    pub masterchain_msg_forward_prices: Option<MsgForwardPricesConfig>,
+    /// Workchain message forward prices (param 25).
+    pub msg_forward_prices: Option<MsgForwardPricesConfig>,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfDecodeBlockchainConfig {
+    /// Blockchain config BOC encoded as base64 or BOC handle
+    pub config_boc: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ResultOfDecodeBlockchainConfig {
+    /// Decoded config params
+    pub params: BlockchainConfigParams,
+}
+
+/// Decodes the blockchain config params used by the transaction executor
+/// (gas, storage and message forward prices, block limits, global version and capabilities).
+#[api_function]
+pub fn decode_blockchain_config(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfDecodeBlockchainConfig,
+) -> ClientResult<ResultOfDecodeBlockchainConfig> {
+    let config = deserialize_object_from_boc::<ever_block::ConfigParams>(
+        &context,
+        &params.config_boc,
+        "blockchain config",
+    )?
+    .object;
+
+    let mut result = BlockchainConfigParams::default();
+    for index in [8, 18, 20, 21, 22, 23, 24, 25] {
+        let param = config.config(index).map_err(|err| {
+            Error::invalid_boc(format!("can not read config param {}: {}", index, err))
+        })?;
+        match param {
+            Some(ConfigParamEnum::ConfigParam8(param)) => {
+                result.global_version = Some(GlobalVersionConfig {
+                    version: param.global_version.version,
+                    capabilities: param.global_version.capabilities,
+                })
+            }
+            Some(ConfigParamEnum::ConfigParam18(param)) => {
+                result.storage_prices = Some(decode_storage_prices(&param)?)
+            }
+            Some(ConfigParamEnum::ConfigParam20(param)) => {
+                result.masterchain_gas_prices = Some(decode_gas_prices(&param))
+            }
+            Some(ConfigParamEnum::ConfigParam21(param)) => {
+                result.gas_prices = Some(decode_gas_prices(&param))
+            }
+            Some(ConfigParamEnum::ConfigParam22(param)) => {
+                result.masterchain_block_limits = Some(decode_block_limits(&param))
+            }
+            Some(ConfigParamEnum::ConfigParam23(param)) => {
+                result.block_limits = Some(decode_block_limits(&param))
+            }
+            Some(ConfigParamEnum::ConfigParam24(param)) => {
+                result.masterchain_msg_forward_prices = Some(decode_fwd_prices(&param))
+            }
+            Some(ConfigParamEnum::ConfigParam25(param)) => {
+                result.msg_forward_prices = Some(decode_fwd_prices(&param))
+            }
+            _ => {}
+        }
+    }
+
+    Ok(ResultOfDecodeBlockchainConfig { params: result })
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfEncodeBlockchainConfig {
+    /// Config params to set
+    pub params: BlockchainConfigParams,
+    /// Base blockchain config BOC encoded as base64 or BOC handle.
+    ///
+    /// Params which are not specified in `params` are taken from the base config.
+    /// Default is the config built into the library.
+    pub config_boc: Option<String>,
+    /// Cache type to put the result. The BOC itself returned if no cache type provided
+    pub boc_cache: Option<BocCacheType>,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ResultOfEncodeBlockchainConfig {
+    /// Blockchain config BOC encoded as base64 or BOC handle
+    pub config_boc: String,
+}
+
+/// Encodes the blockchain config with the specified params.
+///
+/// Result can be used as `ExecutionOptions.blockchain_config` to emulate
+/// transactions with the modified prices and limits.
+#[api_function]
+pub fn encode_blockchain_config(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfEncodeBlockchainConfig,
+) -> ClientResult<ResultOfEncodeBlockchainConfig> {
+    let mut config = match &params.config_boc {
+        Some(boc) => {
+            deserialize_object_from_boc::<ever_block::ConfigParams>(
+                &context,
+                boc,
+                "blockchain config",
+            )?
+            .object
+        }
+        None => crate::net::network_params::offline_config()
+            .0
+            .raw_config()
+            .clone(),
+    };
+
+    let mut set = |param: ConfigParamEnum| {
+        config
+            .set_config(param)
+            .map_err(|err| Error::serialization_error(err, "config param"))
+    };
+    let values = params.params;
+    if let Some(value) = values.global_version {
+        set(ConfigParamEnum::ConfigParam8(ConfigParam8 {
+            global_version: GlobalVersion {
+                version: value.version,
+                capabilities: value.capabilities,
+            },
+        }))?;
+    }
+    if let Some(value) = values.storage_prices {
+        set(ConfigParamEnum::ConfigParam18(encode_storage_prices(
+            value,
+        )?))?;
+    }
+    if let Some(value) = values.masterchain_gas_prices {
+        set(ConfigParamEnum::ConfigParam20(encode_gas_prices(value)))?;
+    }
+    if let Some(value) = values.gas_prices {
+        set(ConfigParamEnum::ConfigParam21(encode_gas_prices(value)))?;
+    }
+    if let Some(value) = values.masterchain_block_limits {
+        set(ConfigParamEnum::ConfigParam22(encode_block_limits(
+            value,
+            "masterchain_block_limits",
+        )?))?;
+    }
+    if let Some(value) = values.block_limits {
+        set(ConfigParamEnum::ConfigParam23(encode_block_limits(
+            value,
+            "block_limits",
+        )?))?;
+    }
+    if let Some(value) = values.masterchain_msg_forward_prices {
+        set(ConfigParamEnum::ConfigParam24(encode_fwd_prices(value)))?;
+    }
+    if let Some(value) = values.msg_forward_prices {
+        set(ConfigParamEnum::ConfigParam25(encode_fwd_prices(value)))?;
+    }
+
+    Ok(ResultOfEncodeBlockchainConfig {
+        config_boc: serialize_object_to_boc(
+            &context,
+            &config,
+            "blockchain config",
+            params.boc_cache,
+        )?,
+    })
+}
+
+pub(crate) fn decode_storage_prices(param: &ConfigParam18) -> ClientResult<Vec<StoragePricesConfig>> {
+    let len = param
+        .len()
+        .map_err(|err| Error::invalid_boc(format!("can not read storage prices: {}", err)))?;
+    let mut result = Vec::with_capacity(len);
+    for index in 0..len {
+        let prices = param
+            .get(index as u32)
+            .map_err(|err| Error::invalid_boc(format!("can not read storage prices: {}", err)))?;
+        result.push(StoragePricesConfig {
+            utime_since: prices.utime_since,
+            bit_price_ps: prices.bit_price_ps,
+            cell_price_ps: prices.cell_price_ps,
+            mc_bit_price_ps: prices.mc_bit_price_ps,
+            mc_cell_price_ps: prices.mc_cell_price_ps,
+        });
+    }
+    Ok(result)
+}
+
+/// Storage prices epoch with the storage fee rate of an account.
+pub(crate) struct Epoch {
+    pub since: u32,
+    pub until: Option<u32>,
+    // fee per second in 2^-16 nanotokens
+    pub rate: u128,
+}
+
+impl Epoch {
+    pub(crate) fn overlap(&self, from: u32, to: u32) -> u32 {
+        let start = std::cmp::max(from, self.since);
+        let end = std::cmp::min(to, self.until.unwrap_or(u32::MAX));
+        end.saturating_sub(start)
+    }
+}
+
+/// Splits the storage prices into the epochs with the storage fee rates of
+/// the account which uses `bits` and `cells`.
+pub(crate) fn storage_epochs(
+    prices: &[StoragePricesConfig],
+    bits: u128,
+    cells: u128,
+    is_masterchain: bool,
+) -> Vec<Epoch> {
+    prices
+        .iter()
+        .enumerate()
+        .map(|(i, epoch)| {
+            let (bit_price, cell_price) = if is_masterchain {
+                (epoch.mc_bit_price_ps, epoch.mc_cell_price_ps)
+            } else {
+                (epoch.bit_price_ps, epoch.cell_price_ps)
+            };
+            Epoch {
+                since: epoch.utime_since,
+                until: prices.get(i + 1).map(|next| next.utime_since),
+                rate: bits * bit_price as u128 + cells * cell_price as u128,
+            }
+        })
+        .collect()
+}
+
+/// Converts the fee in 2^-16 nanotokens into nanotokens rounding up.
+pub(crate) fn to_nanotokens(fee: u128) -> u128 {
+    (fee + 0xffff) >> 16
+}
+
+/// Storage fee in 2^-16 nanotokens accrued between `from` and `to`.
+pub(crate) fn accrued_fee(epochs: &[Epoch], from: u32, to: u32) -> u128 {
+    epochs
+        .iter()
+        .map(|epoch| epoch.rate * epoch.overlap(from, to) as u128)
+        .sum()
+}
+
+fn encode_storage_prices(mut value: Vec<StoragePricesConfig>) -> ClientResult<ConfigParam18> {
+    if value.is_empty() {
+        return Err(Error::invalid_config_param(
+            "storage_prices",
+            "at least one storage prices entry is required",
+        ));
+    }
+    value.sort_by_key(|prices| prices.utime_since);
+    let mut param = ConfigParam18::default();
+    for prices in value {
+        param
+            .insert(&StoragePrices {
+                utime_since: prices.utime_since,
+                bit_price_ps: prices.bit_price_ps,
+                cell_price_ps: prices.cell_price_ps,
+                mc_bit_price_ps: prices.mc_bit_price_ps,
+                mc_cell_price_ps: prices.mc_cell_price_ps,
+            })
+            .map_err(|err| Error::serialization_error(err, "storage prices"))?;
+    }
+    Ok(param)
+}
+
+pub(crate) fn decode_gas_prices(param: &GasLimitsPrices) -> GasLimitsPricesConfig {
+    GasLimitsPricesConfig {
+        gas_price: param.gas_price,
+        gas_limit: param.gas_limit,
+        special_gas_limit: param.special_gas_limit,
+        gas_credit: param.gas_credit,
+        block_gas_limit: param.block_gas_limit,
+        freeze_due_limit: param.freeze_due_limit,
+        delete_due_limit: param.delete_due_limit,
+        flat_gas_limit: param.flat_gas_limit,
+        flat_gas_price: param.flat_gas_price,
+    }
+}
+
+fn encode_gas_prices(value: GasLimitsPricesConfig) -> GasLimitsPrices {
+    GasLimitsPrices {
+        gas_price: value.gas_price,
+        gas_limit: value.gas_limit,
+        special_gas_limit: value.special_gas_limit,
+        gas_credit: value.gas_credit,
+        block_gas_limit: value.block_gas_limit,
+        freeze_due_limit: value.freeze_due_limit,
+        delete_due_limit: value.delete_due_limit,
+        flat_gas_limit: value.flat_gas_limit,
+        flat_gas_price: value.flat_gas_price,
+        ..Default::default()
+    }
+}
+
+fn decode_param_limits(param: &ParamLimits) -> ParamLimitsConfig {
+    ParamLimitsConfig {
+        underload: param.underload(),
+        soft_limit: param.soft_limit(),
+        hard_limit: param.hard_limit(),
+    }
+}
+
+fn encode_param_limits(value: ParamLimitsConfig, name: &str) -> ClientResult<ParamLimits> {
+    ParamLimits::with_limits(value.underload, value.soft_limit, value.hard_limit)
+        .map_err(|err| Error::invalid_config_param(name, err))
+}
+
+fn decode_block_limits(param: &BlockLimits) -> BlockLimitsConfig {
+    BlockLimitsConfig {
+        bytes: decode_param_limits(param.bytes()),
+        gas: decode_param_limits(param.gas()),
+        lt_delta: decode_param_limits(param.lt_delta()),
+    }
+}
+
+fn encode_block_limits(value: BlockLimitsConfig, name: &str) -> ClientResult<BlockLimits> {
+    Ok(BlockLimits::with_limits(
+        encode_param_limits(value.bytes, &format!("{}.bytes", name))?,
+        encode_param_limits(value.gas, &format!("{}.gas", name))?,
+        encode_param_limits(value.lt_delta, &format!("{}.lt_delta", name))?,
+    ))
+}
+
+pub(crate) fn decode_fwd_prices(param: &MsgForwardPrices) -> MsgForwardPricesConfig {
+    MsgForwardPricesConfig {
+        lump_price: param.lump_price,
+        bit_price: param.bit_price,
+        cell_price: param.cell_price,
+        ihr_price_factor: param.ihr_price_factor,
+        first_frac: param.first_frac,
+        next_frac: param.next_frac,
+    }
+}
+
+fn encode_fwd_prices(value: MsgForwardPricesConfig) -> MsgForwardPrices {
+    MsgForwardPrices {
+        lump_price: value.lump_price,
+        bit_price: value.bit_price,
+        cell_price: value.cell_price,
+        ihr_price_factor: value.ihr_price_factor,
+        first_frac: value.first_frac,
+        next_frac: value.next_frac,
+    }
+}
@@ -0,0 +1,1341 @@
+/*
+ * Copyright 2018-2021 EverX Labs Ltd.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ *
+ */
+
+use crate::abi::Abi;
+use crate::boc::internal::deserialize_cell_from_boc;
+use crate::client::ClientContext;
+use crate::encoding::slice_from_cell;
+use crate::error::ClientResult;
+use ever_block::{BuilderData, Cell, HashmapE, HashmapType, SliceData};
+use num_bigint::BigInt;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt::Write;
+use std::sync::Arc;
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfDisassembleCode {
+    /// Contract code BOC encoded as base64 or code BOC handle.
+    ///
+    /// Use `boc.get_code_from_tvc` to extract the code from the TVC image.
+    pub code: String,
+
+    /// Contract ABI used to resolve the function names.
+    pub abi: Option<Abi>,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default, Debug, PartialEq)]
+pub struct DisassembledFunction {
+    /// Function id (key of the selector dictionary).
+    pub id: i64,
+
+    /// Function name.
+    ///
+    /// Resolved with the ABI if provided. Standard TVM selector entries
+    /// (e.g. `recv_internal`, `recv_external`) are named without ABI.
+    pub name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default, Debug)]
+pub struct ResultOfDisassembleCode {
+    /// TVM assembly text.
+    pub assembly: String,
+
+    /// Functions found in the dictionary-based function selectors.
+    pub functions: Vec<DisassembledFunction>,
+}
+
+/// Disassembles contract code into TVM assembly.
+///
+/// Each code cell starts with the `;; cell <hash>` comment. Nested continuations
+/// are printed inside `{ }` blocks. Entries of the dictionary-based function selectors
+/// (`DICTPUSHCONST`) are printed with the function ids and, if ABI is provided,
+/// with the function names. `PUSHINT` constants equal to the ABI function ids are
+/// annotated with the function names too.
+///
+/// Instructions which can not be decoded are printed as `.blob` with the rest of the cell data.
+#[api_function]
+pub fn disassemble_code(
+    context: Arc<ClientContext>,
+    params: ParamsOfDisassembleCode,
+) -> ClientResult<ResultOfDisassembleCode> {
+    let (_, code) = deserialize_cell_from_boc(&context, &params.code, "code")?;
+    let mut names = HashMap::new();
+    if let Some(abi) = &params.abi {
+        for (name, function) in abi.abi()?.functions() {
+            names.insert(function.get_input_id(), name.clone());
+        }
+    }
+
+    let mut disassembler = Disassembler {
+        names,
+        assembly: String::new(),
+        functions: Vec::new(),
+    };
+    disassembler.print_cell(code, 0)?;
+    Ok(ResultOfDisassembleCode {
+        assembly: disassembler.assembly,
+        functions: disassembler.functions,
+    })
+}
+
+/// Names of the standard TVM selector entries.
+fn standard_function_name(id: i64) -> Option<&'static str> {
+    match id {
+        0 => Some("recv_internal"),
+        -1 => Some("recv_external"),
+        -2 => Some("run_ticktock"),
+        -3 => Some("split_prepare"),
+        -4 => Some("split_install"),
+        _ => None,
+    }
+}
+
+enum Block {
+    /// Continuation code. Hash is specified for the continuations stored in the separate cells.
+    Code(SliceData, Option<String>),
+    /// Dictionary of continuations with the specified key length.
+    Dict(usize, Cell),
+}
+
+struct Instruction {
+    text: String,
+    blocks: Vec<Block>,
+}
+
+impl Instruction {
+    fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            blocks: Vec::new(),
+        }
+    }
+
+    fn with_block(mut self, block: Block) -> Self {
+        self.blocks.push(block);
+        self
+    }
+}
+
+struct Disassembler {
+    names: HashMap<u32, String>,
+    assembly: String,
+    functions: Vec<DisassembledFunction>,
+}
+
+impl Disassembler {
+    fn line(&mut self, indent: usize, text: &str) {
+        let _ = writeln!(self.assembly, "{:width$}{}", "", text, width = indent * 2);
+    }
+
+    fn print_cell(&mut self, cell: Cell, indent: usize) -> ClientResult<()> {
+        let hash = cell.repr_hash().as_hex_string();
+        self.print_code(slice_from_cell(cell)?, Some(hash), indent)
+    }
+
+    fn print_code(
+        &mut self,
+        mut code: SliceData,
+        hash: Option<String>,
+        indent: usize,
+    ) -> ClientResult<()> {
+        if let Some(hash) = hash {
+            self.line(indent, &format!(";; cell {}", hash));
+        }
+        loop {
+            if code.remaining_bits() == 0 {
+                if code.remaining_references() == 0 {
+                    return Ok(());
+                }
+                // TVM implicitly jumps to the first reference when the cell data is over
+                let next = code.checked_drain_reference().map_err(|err| {
+                    crate::boc::Error::invalid_boc(format!("can not read code cell: {}", err))
+                })?;
+                for _ in 0..code.remaining_references() {
+                    if let Ok(cell) = code.checked_drain_reference() {
+                        self.line(
+                            indent,
+                            &format!(";; unreachable cell {}", cell.repr_hash().as_hex_string()),
+                        );
+                    }
+                }
+                self.line(indent, ";; implicit jump");
+                let hash = next.repr_hash().as_hex_string();
+                self.line(indent, &format!(";; cell {}", hash));
+                code = slice_from_cell(next)?;
+                continue;
+            }
+            let rest = code.clone();
+            match self.decode(&mut code) {
+                Some(instruction) => self.print_instruction(instruction, indent)?,
+                None => {
+                    let mut rest = rest;
+                    let bits = rest.remaining_bits();
+                    let data = rest.get_next_bits(bits).unwrap_or_default();
+                    self.line(indent, &format!(".blob {}", bits_to_hex(&data, bits)));
+                    while let Ok(cell) = rest.checked_drain_reference() {
+                        self.line(
+                            indent,
+                            &format!(";; cell {}", cell.repr_hash().as_hex_string()),
+                        );
+                    }
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    fn print_instruction(&mut self, instruction: Instruction, indent: usize) -> ClientResult<()> {
+        if instruction.blocks.is_empty() {
+            self.line(indent, &instruction.text);
+            return Ok(());
+        }
+        self.line(indent, &format!("{} {{", instruction.text));
+        let count = instruction.blocks.len();
+        for (i, block) in instruction.blocks.into_iter().enumerate() {
+            match block {
+                Block::Code(code, hash) => self.print_code(code, hash, indent + 1)?,
+                Block::Dict(key_bits, root) => self.print_dict(key_bits, root, indent + 1)?,
+            }
+            self.line(indent, if i + 1 < count { "} {" } else { "}" });
+        }
+        Ok(())
+    }
+
+    fn print_dict(&mut self, key_bits: usize, root: Cell, indent: usize) -> ClientResult<()> {
+        let mut entries = Vec::new();
+        HashmapE::with_hashmap(key_bits, Some(root))
+            .iterate_slices(|key, value| {
+                entries.push((key, value));
+                Ok(true)
+            })
+            .map_err(|err| {
+                crate::boc::Error::invalid_boc(format!("can not read selector dictionary: {}", err))
+            })?;
+        for (mut key, value) in entries {
+            let header = if key_bits <= 64 {
+                let unsigned = key.get_next_int(key_bits).unwrap_or_default();
+                let id = to_signed(unsigned, key_bits);
+                let name = self
+                    .names
+                    .get(&(unsigned as u32))
+                    .filter(|_| key_bits == 32)
+                    .cloned()
+                    .or_else(|| standard_function_name(id).map(|name| name.to_string()));
+                let header = match &name {
+                    Some(name) => format!(";; function {} (0x{:x}) {}", id, unsigned, name),
+                    None => format!(";; function {} (0x{:x})", id, unsigned),
+                };
+                self.functions.push(DisassembledFunction { id, name });
+                header
+            } else {
+                let data = key.get_next_bits(key_bits).unwrap_or_default();
+                format!(";; key {}", bits_to_hex(&data, key_bits))
+            };
+            self.line(indent, &header);
+            self.print_code(value, None, indent + 1)?;
+        }
+        Ok(())
+    }
+
+    fn int_comment(&self, value: &BigInt) -> String {
+        let name = u32::try_from(value).ok().and_then(|id| self.names.get(&id));
+        match name {
+            Some(name) => format!(" ;; {}", name),
+            None => String::new(),
+        }
+    }
+
+    fn decode(&self, code: &mut SliceData) -> Option<Instruction> {
+        let op = next(code, 8)?;
+        Some(match op {
+            0x00 => Instruction::new("NOP"),
+            0x01 => Instruction::new("SWAP"),
+            0x02..=0x0f => Instruction::new(format!("XCHG s{}", op)),
+            0x10 => {
+                let (i, j) = (next(code, 4)?, next(code, 4)?);
+                Instruction::new(format!("XCHG s{}, s{}", i, j))
+            }
+            0x11 => Instruction::new(format!("XCHG s0, s{}", next(code, 8)?)),
+            0x12..=0x1f => Instruction::new(format!("XCHG s1, s{}", op & 0xf)),
+            0x20 => Instruction::new("DUP"),
+            0x21 => Instruction::new("OVER"),
+            0x22..=0x2f => Instruction::new(format!("PUSH s{}", op & 0xf)),
+            0x30 => Instruction::new("DROP"),
+            0x31 => Instruction::new("NIP"),
+            0x32..=0x3f => Instruction::new(format!("POP s{}", op & 0xf)),
+            0x40..=0x4f => {
+                let (j, k) = (next(code, 4)?, next(code, 4)?);
+                Instruction::new(format!("XCHG3 s{}, s{}, s{}", op & 0xf, j, k))
+            }
+            0x50..=0x53 => {
+                let (i, j) = (next(code, 4)? as i64, next(code, 4)? as i64);
+                match op {
+                    0x50 => Instruction::new(format!("XCHG2 s{}, s{}", i, j)),
+                    0x51 => Instruction::new(format!("XCPU s{}, s{}", i, j)),
+                    0x52 => Instruction::new(format!("PUXC s{}, s{}", i, j - 1)),
+                    _ => Instruction::new(format!("PUSH2 s{}, s{}", i, j)),
+                }
+            }
+            0x54 => {
+                let sub = next(code, 4)?;
+                let (i, j, k) = (
+                    next(code, 4)? as i64,
+                    next(code, 4)? as i64,
+                    next(code, 4)? as i64,
+                );
+                let (name, j, k) = match sub {
+                    0 => ("XCHG3", j, k),
+                    1 => ("XC2PU", j, k),
+                    2 => ("XCPUXC", j, k - 1),
+                    3 => ("XCPU2", j, k),
+                    4 => ("PUXC2", j - 1, k - 1),
+                    5 => ("PUXCPU", j - 1, k - 1),
+                    6 => ("PU2XC", j - 1, k - 2),
+                    7 => ("PUSH3", j, k),
+                    _ => return None,
+                };
+                Instruction::new(format!("{} s{}, s{}, s{}", name, i, j, k))
+            }
+            0x55 => {
+                let (i, j) = (next(code, 4)?, next(code, 4)?);
+                Instruction::new(format!("BLKSWAP {}, {}", i + 1, j + 1))
+            }
+            0x56 => Instruction::new(format!("PUSH s{}", next(code, 8)?)),
+            0x57 => Instruction::new(format!("POP s{}", next(code, 8)?)),
+            0x5e => {
+                let (i, j) = (next(code, 4)?, next(code, 4)?);
+                Instruction::new(format!("REVERSE {}, {}", i + 2, j))
+            }
+            0x5f => {
+                let (i, j) = (next(code, 4)?, next(code, 4)?);
+                if i == 0 {
+                    Instruction::new(format!("BLKDROP {}", j))
+                } else {
+                    Instruction::new(format!("BLKPUSH {}, {}", i, j))
+                }
+            }
+            0x6c => {
+                let (i, j) = (next(code, 4)?, next(code, 4)?);
+                if i == 0 {
+                    return None;
+                }
+                Instruction::new(format!("BLKDROP2 {}, {}", i, j))
+            }
+            0x6f => {
+                let (sub, n) = (next(code, 4)?, next(code, 4)?);
+                match sub {
+                    0..=7 => {
+                        let name = [
+                            "TUPLE",
+                            "INDEX",
+                            "UNTUPLE",
+                            "UNPACKFIRST",
+                            "EXPLODE",
+                            "SETINDEX",
+                            "INDEXQ",
+                            "SETINDEXQ",
+                        ][sub as usize];
+                        Instruction::new(format!("{} {}", name, n))
+                    }
+                    8 => Instruction::new(lookup(TUPLE_OPS, n)?),
+                    0xa => Instruction::new(lookup(NULL_OPS, n)?),
+                    0xb => Instruction::new(format!("INDEX2 {}, {}", n >> 2, n & 3)),
+                    0xc..=0xf => {
+                        Instruction::new(format!("INDEX3 {}, {}, {}", sub & 3, n >> 2, n & 3))
+                    }
+                    _ => return None,
+                }
+            }
+            0x70..=0x7f => {
+                let value = if op >= 0x7b {
+                    op as i64 - 0x80
+                } else {
+                    op as i64 - 0x70
+                };
+                self.push_int(BigInt::from(value))
+            }
+            0x80 => self.push_int(BigInt::from(to_signed(next(code, 8)?, 8))),
+            0x81 => self.push_int(BigInt::from(to_signed(next(code, 16)?, 16))),
+            0x82 => {
+                let len = next(code, 5)? as usize * 8 + 19;
+                let data = code.get_next_bits(len).ok()?;
+                let value = BigInt::from_signed_bytes_be(&data) >> (data.len() * 8 - len);
+                self.push_int(value)
+            }
+            0x83 => match next(code, 8)? {
+                0xff => Instruction::new("PUSHNAN"),
+                x => Instruction::new(format!("PUSHPOW2 {}", x + 1)),
+            },
+            0x84 => Instruction::new(format!("PUSHPOW2DEC {}", next(code, 8)? + 1)),
+            0x85 => Instruction::new(format!("PUSHNEGPOW2 {}", next(code, 8)? + 1)),
+            0x88 => data_ref_instruction("PUSHREF", code, 1)?,
+            0x89 => data_ref_instruction("PUSHREFSLICE", code, 1)?,
+            0x8a => code_ref_instruction("PUSHREFCONT", code)?,
+            0x8b => {
+                let len = next(code, 4)? as usize * 8 + 4;
+                push_slice(code, len, 0)?
+            }
+            0x8c => {
+                let refs = next(code, 2)? as usize + 1;
+                let len = next(code, 5)? as usize * 8 + 1;
+                push_slice(code, len, refs)?
+            }
+            0x8d => {
+                let refs = next(code, 3)? as usize;
+                let len = next(code, 7)? as usize * 8 + 6;
+                push_slice(code, len, refs)?
+            }
+            0x8e | 0x8f => {
+                let refs = ((op & 1) << 1 | next(code, 1)?) as usize;
+                let len = next(code, 7)? as usize * 8;
+                let data = code.get_next_slice(len).ok()?;
+                let mut builder = BuilderData::from_slice(&data);
+                for _ in 0..refs {
+                    builder
+                        .checked_append_reference(code.checked_drain_reference().ok()?)
+                        .ok()?;
+                }
+                let cont = SliceData::load_builder(builder).ok()?;
+                Instruction::new("PUSHCONT").with_block(Block::Code(cont, None))
+            }
+            0x90..=0x9f => {
+                let cont = code.get_next_slice((op & 0xf) as usize * 8).ok()?;
+                Instruction::new("PUSHCONT").with_block(Block::Code(cont, None))
+            }
+            0xa6 => Instruction::new(format!("ADDCONST {}", to_signed(next(code, 8)?, 8))),
+            0xa7 => Instruction::new(format!("MULCONST {}", to_signed(next(code, 8)?, 8))),
+            0xa9 => Instruction::new(lookup(DIV_OPS, next(code, 8)?)?),
+            0xaa => Instruction::new(format!("LSHIFT {}", next(code, 8)? + 1)),
+            0xab => Instruction::new(format!("RSHIFT {}", next(code, 8)? + 1)),
+            0xb4 => Instruction::new(format!("FITS {}", next(code, 8)? + 1)),
+            0xb5 => Instruction::new(format!("UFITS {}", next(code, 8)? + 1)),
+            0xb6 => Instruction::new(lookup(B6_OPS, next(code, 8)?)?),
+            0xb7 => {
+                // quiet arithmetic prefix
+                let inner = peek(code, 8)?;
+                if !(0xa0..=0xc5).contains(&inner) || inner == 0xb7 {
+                    return None;
+                }
+                let instruction = self.decode(code)?;
+                Instruction::new(format!("Q{}", instruction.text))
+            }
+            0xc0..=0xc3 => {
+                let name = ["EQINT", "LESSINT", "GTINT", "NEQINT"][(op - 0xc0) as usize];
+                Instruction::new(format!("{} {}", name, to_signed(next(code, 8)?, 8)))
+            }
+            0xc7 => Instruction::new(lookup(C7_OPS, next(code, 8)?)?),
+            0xca => Instruction::new(format!("STI {}", next(code, 8)? + 1)),
+            0xcb => Instruction::new(format!("STU {}", next(code, 8)? + 1)),
+            0xcf => {
+                if next(code, 1)? == 1 {
+                    let refs = next(code, 2)? as usize;
+                    let len = next(code, 3)? as usize * 8 + 2;
+                    let data = code.get_next_bits(len).ok()?;
+                    let mut text = format!("STSLICECONST {}", tagged_bits_to_hex(&data, len));
+                    push_ref_hashes(&mut text, code, refs)?;
+                    Instruction::new(text)
+                } else {
+                    match next(code, 7)? {
+                        sub @ 0x08..=0x0f => {
+                            let name = [
+                                "STI", "STU", "STIR", "STUR", "STIQ", "STUQ", "STIRQ", "STURQ",
+                            ][(sub - 0x08) as usize];
+                            Instruction::new(format!("{} {}", name, next(code, 8)? + 1))
+                        }
+                        0x20 => data_ref_instruction("STREFCONST", code, 1)?,
+                        0x21 => data_ref_instruction("STREF2CONST", code, 2)?,
+                        0x38 => Instruction::new(format!("BCHKBITS {}", next(code, 8)? + 1)),
+                        0x3c => Instruction::new(format!("BCHKBITSQ {}", next(code, 8)? + 1)),
+                        sub => Instruction::new(lookup(CF_OPS, sub)?),
+                    }
+                }
+            }
+            0xd2 => Instruction::new(format!("LDI {}", next(code, 8)? + 1)),
+            0xd3 => Instruction::new(format!("LDU {}", next(code, 8)? + 1)),
+            0xd6 => Instruction::new(format!("LDSLICE {}", next(code, 8)? + 1)),
+            0xd7 => match next(code, 8)? {
+                sub @ 0x08..=0x0f => {
+                    let name = [
+                        "LDI", "LDU", "PLDI", "PLDU", "LDIQ", "LDUQ", "PLDIQ", "PLDUQ",
+                    ][(sub - 0x08) as usize];
+                    Instruction::new(format!("{} {}", name, next(code, 8)? + 1))
+                }
+                sub @ 0x10..=0x17 => Instruction::new(format!("PLDUZ {}", ((sub & 7) + 1) * 32)),
+                sub @ 0x1c..=0x1f => {
+                    let name =
+                        ["LDSLICE", "PLDSLICE", "LDSLICEQ", "PLDSLICEQ"][(sub - 0x1c) as usize];
+                    Instruction::new(format!("{} {}", name, next(code, 8)? + 1))
+                }
+                sub @ 0x28..=0x2f => {
+                    let name = if sub < 0x2c { "SDBEGINS" } else { "SDBEGINSQ" };
+                    let len = ((sub & 3) << 5 | next(code, 5)?) as usize * 8 + 3;
+                    let data = code.get_next_bits(len).ok()?;
+                    Instruction::new(format!("{} {}", name, tagged_bits_to_hex(&data, len)))
+                }
+                sub @ 0x4c..=0x4f => Instruction::new(format!("PLDREFIDX {}", sub & 3)),
+                sub => Instruction::new(lookup(D7_OPS, sub)?),
+            },
+            0xda => {
+                let (p, r) = (next(code, 4)?, next(code, 4)?);
+                Instruction::new(format!("CALLXARGS {}, {}", p, r))
+            }
+            0xdb => match next(code, 8)? {
+                sub @ 0x00..=0x0f => Instruction::new(format!("CALLXARGS {}, -1", sub)),
+                sub @ 0x10..=0x1f => Instruction::new(format!("JMPXARGS {}", sub & 0xf)),
+                sub @ 0x20..=0x2f => Instruction::new(format!("RETARGS {}", sub & 0xf)),
+                0x36 => {
+                    let (p, r) = (next(code, 4)?, next(code, 4)?);
+                    Instruction::new(format!("CALLCCARGS {}, {}", p, to_signed(r, 4)))
+                }
+                0x3c => code_ref_instruction("CALLREF", code)?,
+                0x3d => code_ref_instruction("JMPREF", code)?,
+                0x3e => code_ref_instruction("JMPREFDATA", code)?,
+                sub => Instruction::new(lookup(DB_OPS, sub)?),
+            },
+            0xe3 => match next(code, 8)? {
+                0x00 => code_ref_instruction("IFREF", code)?,
+                0x01 => code_ref_instruction("IFNOTREF", code)?,
+                0x02 => code_ref_instruction("IFJMPREF", code)?,
+                0x03 => code_ref_instruction("IFNOTJMPREF", code)?,
+                0x0d => code_ref_instruction("IFREFELSE", code)?,
+                0x0e => code_ref_instruction("IFELSEREF", code)?,
+                0x0f => {
+                    let instruction = code_ref_instruction("IFREFELSEREF", code)?;
+                    let cell = code.checked_drain_reference().ok()?;
+                    let hash = cell.repr_hash().as_hex_string();
+                    let cont = SliceData::load_cell(cell).ok()?;
+                    instruction.with_block(Block::Code(cont, Some(hash)))
+                }
+                sub @ 0x80..=0x9f => Instruction::new(format!("IFBITJMP {}", sub & 0x1f)),
+                sub @ 0xa0..=0xbf => Instruction::new(format!("IFNBITJMP {}", sub & 0x1f)),
+                sub @ 0xc0..=0xdf => {
+                    code_ref_instruction(&format!("IFBITJMPREF {}", sub & 0x1f), code)?
+                }
+                sub @ 0xe0..=0xff => {
+                    code_ref_instruction(&format!("IFNBITJMPREF {}", sub & 0x1f), code)?
+                }
+                sub => Instruction::new(lookup(E3_OPS, sub)?),
+            },
+            0xec => {
+                let (r, n) = (next(code, 4)?, next(code, 4)?);
+                Instruction::new(format!("SETCONTARGS {}, {}", r, to_signed(n, 4)))
+            }
+            0xed => {
+                let (sub, i) = (next(code, 4)?, next(code, 4)?);
+                match sub {
+                    0x0 => Instruction::new(format!("RETURNARGS {}", i)),
+                    0x4..=0xc => {
+                        let name = [
+                            "PUSHCTR",
+                            "POPCTR",
+                            "SETCONTCTR",
+                            "SETRETCTR",
+                            "SETALTCTR",
+                            "POPSAVE",
+                            "SAVECTR",
+                            "SAVEALTCTR",
+                            "SAVEBOTHCTR",
+                        ][(sub - 0x4) as usize];
+                        Instruction::new(format!("{} c{}", name, i))
+                    }
+                    _ => Instruction::new(lookup(ED_OPS, sub << 4 | i)?),
+                }
+            }
+            0xee => {
+                let (r, n) = (next(code, 4)?, next(code, 4)?);
+                Instruction::new(format!("BLESSARGS {}, {}", r, to_signed(n, 4)))
+            }
+            0xf0 => Instruction::new(format!("CALLDICT {}", next(code, 8)?)),
+            0xf1 => {
+                let name = match next(code, 2)? {
+                    0 => "CALLDICT",
+                    1 => "JMPDICT",
+                    2 => "PREPAREDICT",
+                    _ => return None,
+                };
+                Instruction::new(format!("{} {}", name, next(code, 14)?))
+            }
+            0xf2 => match next(code, 2)? {
+                kind @ 0..=2 => {
+                    let name = ["THROW", "THROWIF", "THROWIFNOT"][kind as usize];
+                    Instruction::new(format!("{} {}", name, next(code, 6)?))
+                }
+                _ => match next(code, 2)? {
+                    3 => Instruction::new(lookup(THROW_ANY_OPS, next(code, 4)?)?),
+                    kind => {
+                        let names = if next(code, 1)? == 1 {
+                            ["THROWARG", "THROWARGIF", "THROWARGIFNOT"]
+                        } else {
+                            ["THROW", "THROWIF", "THROWIFNOT"]
+                        };
+                        Instruction::new(format!("{} {}", names[kind as usize], next(code, 11)?))
+                    }
+                },
+            },
+            0xf3 => {
+                let (p, r) = (next(code, 4)?, next(code, 4)?);
+                Instruction::new(format!("TRYARGS {}, {}", p, r))
+            }
+            0xf4 => match next(code, 8)? {
+                sub @ 0xa4..=0xa7 => {
+                    let key_bits = ((sub & 3) << 8 | next(code, 8)?) as usize;
+                    let root = code.checked_drain_reference().ok()?;
+                    Instruction::new(format!("DICTPUSHCONST {}", key_bits))
+                        .with_block(Block::Dict(key_bits, root))
+                }
+                sub => Instruction::new(lookup(DICT_OPS, sub)?),
+            },
+            0xf8 => match next(code, 8)? {
+                sub @ 0x20..=0x2f => match lookup(PARAM_OPS, sub) {
+                    Some(name) => Instruction::new(name),
+                    None => Instruction::new(format!("GETPARAM {}", sub & 0xf)),
+                },
+                0x40 => Instruction::new("GETGLOBVAR"),
+                sub @ 0x41..=0x5f => Instruction::new(format!("GETGLOB {}", sub & 0x1f)),
+                0x60 => Instruction::new("SETGLOBVAR"),
+                sub @ 0x61..=0x7f => Instruction::new(format!("SETGLOB {}", sub & 0x1f)),
+                sub => Instruction::new(lookup(F8_OPS, sub)?),
+            },
+            0xf9 => Instruction::new(lookup(F9_OPS, next(code, 8)?)?),
+            0xfa => Instruction::new(lookup(FA_OPS, next(code, 8)?)?),
+            0xfb => Instruction::new(lookup(FB_OPS, next(code, 8)?)?),
+            0xfe => match next(code, 8)? {
+                sub @ 0xf0..=0xff => {
+                    let len = ((sub & 0xf) as usize + 1) * 8;
+                    let data = code.get_next_bits(len).ok()?;
+                    Instruction::new(format!("DEBUGSTR {}", bits_to_hex(&data, len)))
+                }
+                sub => Instruction::new(format!("DEBUG {}", sub)),
+            },
+            0xff => match next(code, 8)? {
+                0x00 => Instruction::new("SETCP0"),
+                0xf0 => Instruction::new("SETCPX"),
+                cp @ 0xf1..=0xff => Instruction::new(format!("SETCP {}", cp as i64 - 0x100)),
+                cp => Instruction::new(format!("SETCP {}", cp)),
+            },
+            op => Instruction::new(lookup(SIMPLE_OPS, op)?),
+        })
+    }
+
+    fn push_int(&self, value: BigInt) -> Instruction {
+        let comment = self.int_comment(&value);
+        Instruction::new(format!("PUSHINT {}{}", value, comment))
+    }
+}
+
+#[cfg(test)]
+impl Disassembler {
+    /// Rebuilds the code from the raw encodings of the decoded instructions and checks
+    /// the nested continuations and selector entries the same way.
+    fn reassemble(&self, mut code: SliceData) -> Result<BuilderData, String> {
+        let mut builder = BuilderData::new();
+        while code.remaining_bits() > 0 {
+            let mut rest = code.clone();
+            let instruction = self
+                .decode(&mut code)
+                .ok_or_else(|| format!("can not decode {}", rest))?;
+            let bits = rest.remaining_bits() - code.remaining_bits();
+            let refs = rest.remaining_references() - code.remaining_references();
+            let data = rest.get_next_bits(bits).map_err(|err| err.to_string())?;
+            builder
+                .append_raw(&data, bits)
+                .map_err(|err| err.to_string())?;
+            for _ in 0..refs {
+                let cell = rest
+                    .checked_drain_reference()
+                    .map_err(|err| err.to_string())?;
+                builder
+                    .checked_append_reference(cell)
+                    .map_err(|err| err.to_string())?;
+            }
+            for block in instruction.blocks {
+                match block {
+                    Block::Code(cont, _) => self.check_reassembled(cont, &instruction.text)?,
+                    Block::Dict(key_bits, root) => {
+                        let mut entries = Vec::new();
+                        HashmapE::with_hashmap(key_bits, Some(root))
+                            .iterate_slices(|_, value| {
+                                entries.push(value);
+                                Ok(true)
+                            })
+                            .map_err(|err| err.to_string())?;
+                        for value in entries {
+                            self.check_reassembled(value, &instruction.text)?;
+                        }
+                    }
+                }
+            }
+        }
+        // the rest of the references is reached by the implicit jump
+        if let Ok(next) = code.reference(0) {
+            let next = SliceData::load_cell(next).map_err(|err| err.to_string())?;
+            self.check_reassembled(next, "implicit jump")?;
+        }
+        while let Ok(cell) = code.checked_drain_reference() {
+            builder
+                .checked_append_reference(cell)
+                .map_err(|err| err.to_string())?;
+        }
+        Ok(builder)
+    }
+
+    fn check_reassembled(&self, code: SliceData, context: &str) -> Result<(), String> {
+        let expected = BuilderData::from_slice(&code)
+            .into_cell()
+            .map_err(|err| err.to_string())?;
+        let actual = self
+            .reassemble(code)?
+            .into_cell()
+            .map_err(|err| err.to_string())?;
+        if actual.repr_hash() != expected.repr_hash() {
+            return Err(format!("code of `{}` is reassembled incorrectly", context));
+        }
+        Ok(())
+    }
+}
+
+/// Decodes every instruction of the code and its nested continuations and rebuilds
+/// the code cell from the decoded instruction encodings.
+#[cfg(test)]
+pub(crate) fn reassemble_code(code: Cell) -> Result<Cell, String> {
+    let disassembler = Disassembler {
+        names: HashMap::new(),
+        assembly: String::new(),
+        functions: Vec::new(),
+    };
+    disassembler
+        .reassemble(SliceData::load_cell(code).map_err(|err| err.to_string())?)?
+        .into_cell()
+        .map_err(|err| err.to_string())
+}
+
+fn next(code: &mut SliceData, bits: usize) -> Option<u64> {
+    code.get_next_int(bits).ok()
+}
+
+fn peek(code: &SliceData, bits: usize) -> Option<u64> {
+    code.clone().get_next_int(bits).ok()
+}
+
+fn to_signed(value: u64, bits: usize) -> i64 {
+    if bits < 64 && value >> (bits - 1) & 1 == 1 {
+        value as i64 - (1i64 << bits)
+    } else {
+        value as i64
+    }
+}
+
+fn lookup(table: &[(u64, &'static str)], op: u64) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(code, _)| *code == op)
+        .map(|(_, name)| *name)
+}
+
+fn push_ref_hashes(text: &mut String, code: &mut SliceData, refs: usize) -> Option<()> {
+    for i in 0..refs {
+        let cell = code.checked_drain_reference().ok()?;
+        let separator = if i == 0 { " ;; cell" } else { "," };
+        let _ = write!(text, "{} {}", separator, cell.repr_hash().as_hex_string());
+    }
+    Some(())
+}
+
+fn data_ref_instruction(name: &str, code: &mut SliceData, refs: usize) -> Option<Instruction> {
+    let mut text = name.to_string();
+    push_ref_hashes(&mut text, code, refs)?;
+    Some(Instruction::new(text))
+}
+
+fn code_ref_instruction(name: &str, code: &mut SliceData) -> Option<Instruction> {
+    let cell = code.checked_drain_reference().ok()?;
+    let hash = cell.repr_hash().as_hex_string();
+    let cont = SliceData::load_cell(cell).ok()?;
+    Some(Instruction::new(name).with_block(Block::Code(cont, Some(hash))))
+}
+
+fn push_slice(code: &mut SliceData, len: usize, refs: usize) -> Option<Instruction> {
+    let data = code.get_next_bits(len).ok()?;
+    let mut text = format!("PUSHSLICE {}", tagged_bits_to_hex(&data, len));
+    push_ref_hashes(&mut text, code, refs)?;
+    Some(Instruction::new(text))
+}
+
+fn get_bit(data: &[u8], index: usize) -> bool {
+    data[index / 8] >> (7 - index % 8) & 1 == 1
+}
+
+/// Formats the bit string with the completion tag removed.
+fn tagged_bits_to_hex(data: &[u8], len: usize) -> String {
+    let mut len = len;
+    while len > 0 && !get_bit(data, len - 1) {
+        len -= 1;
+    }
+    bits_to_hex(data, len.saturating_sub(1))
+}
+
+/// Formats the bit string in the `x{...}` notation. Incomplete nibbles
+/// are padded with the completion tag and marked with `_`.
+fn bits_to_hex(data: &[u8], len: usize) -> String {
+    let mut hex = String::new();
+    for nibble in 0..(len + 3) / 4 {
+        let mut value = 0;
+        for bit in nibble * 4..nibble * 4 + 4 {
+            let set = if bit < len {
+                get_bit(data, bit)
+            } else {
+                bit == len
+            };
+            value = value << 1 | set as u8;
+        }
+        let _ = write!(hex, "{:X}", value);
+    }
+    if len % 4 != 0 {
+        hex.push('_');
+    }
+    format!("x{{{}}}", hex)
+}
+
+const SIMPLE_OPS: &[(u64, &str)] = &[
+    (0x58, "ROT"),
+    (0x59, "ROTREV"),
+    (0x5a, "SWAP2"),
+    (0x5b, "DROP2"),
+    (0x5c, "DUP2"),
+    (0x5d, "OVER2"),
+    (0x60, "PICK"),
+    (0x61, "ROLLX"),
+    (0x62, "-ROLLX"),
+    (0x63, "BLKSWX"),
+    (0x64, "REVX"),
+    (0x65, "DROPX"),
+    (0x66, "TUCK"),
+    (0x67, "XCHGX"),
+    (0x68, "DEPTH"),
+    (0x69, "CHKDEPTH"),
+    (0x6a, "ONLYTOPX"),
+    (0x6b, "ONLYX"),
+    (0x6d, "NULL"),
+    (0x6e, "ISNULL"),
+    (0xa0, "ADD"),
+    (0xa1, "SUB"),
+    (0xa2, "SUBR"),
+    (0xa3, "NEGATE"),
+    (0xa4, "INC"),
+    (0xa5, "DEC"),
+    (0xa8, "MUL"),
+    (0xac, "LSHIFT"),
+    (0xad, "RSHIFT"),
+    (0xae, "POW2"),
+    (0xb0, "AND"),
+    (0xb1, "OR"),
+    (0xb2, "XOR"),
+    (0xb3, "NOT"),
+    (0xb8, "SGN"),
+    (0xb9, "LESS"),
+    (0xba, "EQUAL"),
+    (0xbb, "LEQ"),
+    (0xbc, "GREATER"),
+    (0xbd, "NEQ"),
+    (0xbe, "GEQ"),
+    (0xbf, "CMP"),
+    (0xc4, "ISNAN"),
+    (0xc5, "CHKNAN"),
+    (0xc8, "NEWC"),
+    (0xc9, "ENDC"),
+    (0xcc, "STREF"),
+    (0xcd, "STBREFR"),
+    (0xce, "STSLICE"),
+    (0xd0, "CTOS"),
+    (0xd1, "ENDS"),
+    (0xd4, "LDREF"),
+    (0xd5, "LDREFRTOS"),
+    (0xd8, "EXECUTE"),
+    (0xd9, "JMPX"),
+    (0xdc, "IFRET"),
+    (0xdd, "IFNOTRET"),
+    (0xde, "IF"),
+    (0xdf, "IFNOT"),
+    (0xe0, "IFJMP"),
+    (0xe1, "IFNOTJMP"),
+    (0xe2, "IFELSE"),
+    (0xe4, "REPEAT"),
+    (0xe5, "REPEATEND"),
+    (0xe6, "UNTIL"),
+    (0xe7, "UNTILEND"),
+    (0xe8, "WHILE"),
+    (0xe9, "WHILEEND"),
+    (0xea, "AGAIN"),
+    (0xeb, "AGAINEND"),
+];
+
+const TUPLE_OPS: &[(u64, &str)] = &[
+    (0x0, "TUPLEVAR"),
+    (0x1, "INDEXVAR"),
+    (0x2, "UNTUPLEVAR"),
+    (0x3, "UNPACKFIRSTVAR"),
+    (0x4, "EXPLODEVAR"),
+    (0x5, "SETINDEXVAR"),
+    (0x6, "INDEXVARQ"),
+    (0x7, "SETINDEXVARQ"),
+    (0x8, "TLEN"),
+    (0x9, "QTLEN"),
+    (0xa, "ISTUPLE"),
+    (0xb, "LAST"),
+    (0xc, "TPUSH"),
+    (0xd, "TPOP"),
+];
+
+const NULL_OPS: &[(u64, &str)] = &[
+    (0x0, "NULLSWAPIF"),
+    (0x1, "NULLSWAPIFNOT"),
+    (0x2, "NULLROTRIF"),
+    (0x3, "NULLROTRIFNOT"),
+    (0x4, "NULLSWAPIF2"),
+    (0x5, "NULLSWAPIFNOT2"),
+    (0x6, "NULLROTRIF2"),
+    (0x7, "NULLROTRIFNOT2"),
+];
+
+const DIV_OPS: &[(u64, &str)] = &[
+    (0x04, "DIV"),
+    (0x05, "DIVR"),
+    (0x06, "DIVC"),
+    (0x08, "MOD"),
+    (0x09, "MODR"),
+    (0x0a, "MODC"),
+    (0x0c, "DIVMOD"),
+    (0x0d, "DIVMODR"),
+    (0x0e, "DIVMODC"),
+    (0x84, "MULDIV"),
+    (0x85, "MULDIVR"),
+    (0x86, "MULDIVC"),
+    (0x88, "MULMOD"),
+    (0x8c, "MULDIVMOD"),
+    (0x8d, "MULDIVMODR"),
+    (0x8e, "MULDIVMODC"),
+];
+
+const B6_OPS: &[(u64, &str)] = &[
+    (0x00, "FITSX"),
+    (0x01, "UFITSX"),
+    (0x02, "BITSIZE"),
+    (0x03, "UBITSIZE"),
+    (0x08, "MIN"),
+    (0x09, "MAX"),
+    (0x0a, "MINMAX"),
+    (0x0b, "ABS"),
+];
+
+const C7_OPS: &[(u64, &str)] = &[
+    (0x00, "SEMPTY"),
+    (0x01, "SDEMPTY"),
+    (0x02, "SREMPTY"),
+    (0x03, "SDFIRST"),
+    (0x04, "SDLEXCMP"),
+    (0x05, "SDEQ"),
+    (0x08, "SDPFX"),
+    (0x09, "SDPFXREV"),
+    (0x0a, "SDPPFX"),
+    (0x0b, "SDPPFXREV"),
+    (0x0c, "SDSFX"),
+    (0x0d, "SDSFXREV"),
+    (0x0e, "SDPSFX"),
+    (0x0f, "SDPSFXREV"),
+    (0x10, "SDCNTLEAD0"),
+    (0x11, "SDCNTLEAD1"),
+    (0x12, "SDCNTTRAIL0"),
+    (0x13, "SDCNTTRAIL1"),
+];
+
+/// `CF` prefixed instructions without arguments, indexed by the 7 bits after the prefix.
+const CF_OPS: &[(u64, &str)] = &[
+    (0x00, "STIX"),
+    (0x01, "STUX"),
+    (0x02, "STIXR"),
+    (0x03, "STUXR"),
+    (0x04, "STIXQ"),
+    (0x05, "STUXQ"),
+    (0x06, "STIXRQ"),
+    (0x07, "STUXRQ"),
+    (0x10, "STREF"),
+    (0x11, "STBREF"),
+    (0x12, "STSLICE"),
+    (0x13, "STB"),
+    (0x14, "STREFR"),
+    (0x15, "STBREFR"),
+    (0x16, "STSLICER"),
+    (0x17, "STBR"),
+    (0x18, "STREFQ"),
+    (0x19, "STBREFQ"),
+    (0x1a, "STSLICEQ"),
+    (0x1b, "STBQ"),
+    (0x1c, "STREFRQ"),
+    (0x1d, "STBREFRQ"),
+    (0x1e, "STSLICERQ"),
+    (0x1f, "STBRQ"),
+    (0x23, "ENDXC"),
+    (0x28, "STILE4"),
+    (0x29, "STULE4"),
+    (0x2a, "STILE8"),
+    (0x2b, "STULE8"),
+    (0x30, "BDEPTH"),
+    (0x31, "BBITS"),
+    (0x32, "BREFS"),
+    (0x33, "BBITREFS"),
+    (0x35, "BREMBITS"),
+    (0x36, "BREMREFS"),
+    (0x37, "BREMBITREFS"),
+    (0x39, "BCHKBITS"),
+    (0x3a, "BCHKREFS"),
+    (0x3b, "BCHKBITREFS"),
+    (0x3d, "BCHKBITSQ"),
+    (0x3e, "BCHKREFSQ"),
+    (0x3f, "BCHKBITREFSQ"),
+    (0x40, "STZEROES"),
+    (0x41, "STONES"),
+    (0x42, "STSAME"),
+];
+
+const D7_OPS: &[(u64, &str)] = &[
+    (0x00, "LDIX"),
+    (0x01, "LDUX"),
+    (0x02, "PLDIX"),
+    (0x03, "PLDUX"),
+    (0x04, "LDIXQ"),
+    (0x05, "LDUXQ"),
+    (0x06, "PLDIXQ"),
+    (0x07, "PLDUXQ"),
+    (0x18, "LDSLICEX"),
+    (0x19, "PLDSLICEX"),
+    (0x1a, "LDSLICEXQ"),
+    (0x1b, "PLDSLICEXQ"),
+    (0x20, "SDCUTFIRST"),
+    (0x21, "SDSKIPFIRST"),
+    (0x22, "SDCUTLAST"),
+    (0x23, "SDSKIPLAST"),
+    (0x24, "SDSUBSTR"),
+    (0x26, "SDBEGINSX"),
+    (0x27, "SDBEGINSXQ"),
+    (0x30, "SCUTFIRST"),
+    (0x31, "SSKIPFIRST"),
+    (0x32, "SCUTLAST"),
+    (0x33, "SSKIPLAST"),
+    (0x34, "SUBSLICE"),
+    (0x36, "SPLIT"),
+    (0x37, "SPLITQ"),
+    (0x39, "XCTOS"),
+    (0x3a, "XLOAD"),
+    (0x3b, "XLOADQ"),
+    (0x41, "SCHKBITS"),
+    (0x42, "SCHKREFS"),
+    (0x43, "SCHKBITREFS"),
+    (0x45, "SCHKBITSQ"),
+    (0x46, "SCHKREFSQ"),
+    (0x47, "SCHKBITREFSQ"),
+    (0x48, "PLDREFVAR"),
+    (0x49, "SBITS"),
+    (0x4a, "SREFS"),
+    (0x4b, "SBITREFS"),
+    (0x50, "LDILE4"),
+    (0x51, "LDULE4"),
+    (0x52, "LDILE8"),
+    (0x53, "LDULE8"),
+    (0x54, "PLDILE4"),
+    (0x55, "PLDULE4"),
+    (0x56, "PLDILE8"),
+    (0x57, "PLDULE8"),
+    (0x58, "LDILE4Q"),
+    (0x59, "LDULE4Q"),
+    (0x5a, "LDILE8Q"),
+    (0x5b, "LDULE8Q"),
+    (0x5c, "PLDILE4Q"),
+    (0x5d, "PLDULE4Q"),
+    (0x5e, "PLDILE8Q"),
+    (0x5f, "PLDULE8Q"),
+    (0x60, "LDZEROES"),
+    (0x61, "LDONES"),
+    (0x62, "LDSAME"),
+    (0x64, "SDEPTH"),
+    (0x65, "CDEPTH"),
+];
+
+const DB_OPS: &[(u64, &str)] = &[
+    (0x30, "RET"),
+    (0x31, "RETALT"),
+    (0x32, "RETBOOL"),
+    (0x34, "CALLCC"),
+    (0x35, "JMPXDATA"),
+    (0x38, "CALLXVARARGS"),
+    (0x39, "RETVARARGS"),
+    (0x3a, "JMPXVARARGS"),
+    (0x3b, "CALLCCVARARGS"),
+    (0x3f, "RETDATA"),
+];
+
+const E3_OPS: &[(u64, &str)] = &[
+    (0x04, "CONDSEL"),
+    (0x05, "CONDSELCHK"),
+    (0x08, "IFRETALT"),
+    (0x09, "IFNOTRETALT"),
+    (0x14, "REPEATBRK"),
+    (0x15, "REPEATENDBRK"),
+    (0x16, "UNTILBRK"),
+    (0x17, "UNTILENDBRK"),
+    (0x18, "WHILEBRK"),
+    (0x19, "WHILEENDBRK"),
+    (0x1a, "AGAINBRK"),
+    (0x1b, "AGAINENDBRK"),
+];
+
+const ED_OPS: &[(u64, &str)] = &[
+    (0x10, "RETURNVARARGS"),
+    (0x11, "SETCONTVARARGS"),
+    (0x12, "SETNUMVARARGS"),
+    (0x1e, "BLESS"),
+    (0x1f, "BLESSVARARGS"),
+    (0xe0, "PUSHCTRX"),
+    (0xe1, "POPCTRX"),
+    (0xe2, "SETCONTCTRX"),
+    (0xf0, "COMPOS"),
+    (0xf1, "COMPOSALT"),
+    (0xf2, "COMPOSBOTH"),
+    (0xf3, "ATEXIT"),
+    (0xf4, "ATEXITALT"),
+    (0xf5, "SETEXITALT"),
+    (0xf6, "THENRET"),
+    (0xf7, "THENRETALT"),
+    (0xf8, "INVERT"),
+    (0xf9, "BOOLEVAL"),
+    (0xfa, "SAMEALT"),
+    (0xfb, "SAMEALTSAVE"),
+];
+
+const THROW_ANY_OPS: &[(u64, &str)] = &[
+    (0x0, "THROWANY"),
+    (0x1, "THROWARGANY"),
+    (0x2, "THROWANYIF"),
+    (0x3, "THROWARGANYIF"),
+    (0x4, "THROWANYIFNOT"),
+    (0x5, "THROWARGANYIFNOT"),
+    (0xf, "TRY"),
+];
+
+const DICT_OPS: &[(u64, &str)] = &[
+    (0x00, "STDICT"),
+    (0x01, "SKIPDICT"),
+    (0x02, "LDDICTS"),
+    (0x03, "PLDDICTS"),
+    (0x04, "LDDICT"),
+    (0x05, "PLDDICT"),
+    (0x06, "LDDICTQ"),
+    (0x07, "PLDDICTQ"),
+    (0x0a, "DICTGET"),
+    (0x0b, "DICTGETREF"),
+    (0x0c, "DICTIGET"),
+    (0x0d, "DICTIGETREF"),
+    (0x0e, "DICTUGET"),
+    (0x0f, "DICTUGETREF"),
+    (0x12, "DICTSET"),
+    (0x13, "DICTSETREF"),
+    (0x14, "DICTISET"),
+    (0x15, "DICTISETREF"),
+    (0x16, "DICTUSET"),
+    (0x17, "DICTUSETREF"),
+    (0x1a, "DICTSETGET"),
+    (0x1b, "DICTSETGETREF"),
+    (0x1c, "DICTISETGET"),
+    (0x1d, "DICTISETGETREF"),
+    (0x1e, "DICTUSETGET"),
+    (0x1f, "DICTUSETGETREF"),
+    (0x22, "DICTREPLACE"),
+    (0x23, "DICTREPLACEREF"),
+    (0x24, "DICTIREPLACE"),
+    (0x25, "DICTIREPLACEREF"),
+    (0x26, "DICTUREPLACE"),
+    (0x27, "DICTUREPLACEREF"),
+    (0x2a, "DICTREPLACEGET"),
+    (0x2b, "DICTREPLACEGETREF"),
+    (0x2c, "DICTIREPLACEGET"),
+    (0x2d, "DICTIREPLACEGETREF"),
+    (0x2e, "DICTUREPLACEGET"),
+    (0x2f, "DICTUREPLACEGETREF"),
+    (0x32, "DICTADD"),
+    (0x33, "DICTADDREF"),
+    (0x34, "DICTIADD"),
+    (0x35, "DICTIADDREF"),
+    (0x36, "DICTUADD"),
+    (0x37, "DICTUADDREF"),
+    (0x3a, "DICTADDGET"),
+    (0x3b, "DICTADDGETREF"),
+    (0x3c, "DICTIADDGET"),
+    (0x3d, "DICTIADDGETREF"),
+    (0x3e, "DICTUADDGET"),
+    (0x3f, "DICTUADDGETREF"),
+    (0x41, "DICTSETB"),
+    (0x42, "DICTISETB"),
+    (0x43, "DICTUSETB"),
+    (0x45, "DICTSETGETB"),
+    (0x46, "DICTISETGETB"),
+    (0x47, "DICTUSETGETB"),
+    (0x49, "DICTREPLACEB"),
+    (0x4a, "DICTIREPLACEB"),
+    (0x4b, "DICTUREPLACEB"),
+    (0x4d, "DICTREPLACEGETB"),
+    (0x4e, "DICTIREPLACEGETB"),
+    (0x4f, "DICTUREPLACEGETB"),
+    (0x51, "DICTADDB"),
+    (0x52, "DICTIADDB"),
+    (0x53, "DICTUADDB"),
+    (0x55, "DICTADDGETB"),
+    (0x56, "DICTIADDGETB"),
+    (0x57, "DICTUADDGETB"),
+    (0x59, "DICTDEL"),
+    (0x5a, "DICTIDEL"),
+    (0x5b, "DICTUDEL"),
+    (0x62, "DICTDELGET"),
+    (0x63, "DICTDELGETREF"),
+    (0x64, "DICTIDELGET"),
+    (0x65, "DICTIDELGETREF"),
+    (0x66, "DICTUDELGET"),
+    (0x67, "DICTUDELGETREF"),
+    (0x69, "DICTGETOPTREF"),
+    (0x6a, "DICTIGETOPTREF"),
+    (0x6b, "DICTUGETOPTREF"),
+    (0x6d, "DICTSETGETOPTREF"),
+    (0x6e, "DICTISETGETOPTREF"),
+    (0x6f, "DICTUSETGETOPTREF"),
+    (0x70, "PFXDICTSET"),
+    (0x71, "PFXDICTREPLACE"),
+    (0x72, "PFXDICTADD"),
+    (0x73, "PFXDICTDEL"),
+    (0x74, "DICTGETNEXT"),
+    (0x75, "DICTGETNEXTEQ"),
+    (0x76, "DICTGETPREV"),
+    (0x77, "DICTGETPREVEQ"),
+    (0x78, "DICTIGETNEXT"),
+    (0x79, "DICTIGETNEXTEQ"),
+    (0x7a, "DICTIGETPREV"),
+    (0x7b, "DICTIGETPREVEQ"),
+    (0x7c, "DICTUGETNEXT"),
+    (0x7d, "DICTUGETNEXTEQ"),
+    (0x7e, "DICTUGETPREV"),
+    (0x7f, "DICTUGETPREVEQ"),
+    (0x82, "DICTMIN"),
+    (0x83, "DICTMINREF"),
+    (0x84, "DICTIMIN"),
+    (0x85, "DICTIMINREF"),
+    (0x86, "DICTUMIN"),
+    (0x87, "DICTUMINREF"),
+    (0x8a, "DICTMAX"),
+    (0x8b, "DICTMAXREF"),
+    (0x8c, "DICTIMAX"),
+    (0x8d, "DICTIMAXREF"),
+    (0x8e, "DICTUMAX"),
+    (0x8f, "DICTUMAXREF"),
+    (0x92, "DICTREMMIN"),
+    (0x93, "DICTREMMINREF"),
+    (0x94, "DICTIREMMIN"),
+    (0x95, "DICTIREMMINREF"),
+    (0x96, "DICTUREMMIN"),
+    (0x97, "DICTUREMMINREF"),
+    (0x9a, "DICTREMMAX"),
+    (0x9b, "DICTREMMAXREF"),
+    (0x9c, "DICTIREMMAX"),
+    (0x9d, "DICTIREMMAXREF"),
+    (0x9e, "DICTUREMMAX"),
+    (0x9f, "DICTUREMMAXREF"),
+    (0xa0, "DICTIGETJMP"),
+    (0xa1, "DICTUGETJMP"),
+    (0xa2, "DICTIGETEXEC"),
+    (0xa3, "DICTUGETEXEC"),
+    (0xa8, "PFXDICTGETQ"),
+    (0xa9, "PFXDICTGET"),
+    (0xaa, "PFXDICTGETJMP"),
+    (0xab, "PFXDICTGETEXEC"),
+    (0xb1, "SUBDICTGET"),
+    (0xb2, "SUBDICTIGET"),
+    (0xb3, "SUBDICTUGET"),
+    (0xb5, "SUBDICTRPGET"),
+    (0xb6, "SUBDICTIRPGET"),
+    (0xb7, "SUBDICTURPGET"),
+    (0xbc, "DICTIGETJMPZ"),
+    (0xbd, "DICTUGETJMPZ"),
+    (0xbe, "DICTIGETEXECZ"),
+    (0xbf, "DICTUGETEXECZ"),
+];
+
+const PARAM_OPS: &[(u64, &str)] = &[
+    (0x23, "NOW"),
+    (0x24, "BLOCKLT"),
+    (0x25, "LTIME"),
+    (0x26, "RANDSEED"),
+    (0x27, "BALANCE"),
+    (0x28, "MYADDR"),
+    (0x29, "CONFIGROOT"),
+];
+
+const F8_OPS: &[(u64, &str)] = &[
+    (0x00, "ACCEPT"),
+    (0x01, "SETGASLIMIT"),
+    (0x0f, "COMMIT"),
+    (0x10, "RANDU256"),
+    (0x11, "RAND"),
+    (0x14, "SETRAND"),
+    (0x15, "ADDRAND"),
+    (0x30, "CONFIGDICT"),
+    (0x32, "CONFIGPARAM"),
+    (0x33, "CONFIGOPTPARAM"),
+];
+
+const F9_OPS: &[(u64, &str)] = &[
+    (0x00, "HASHCU"),
+    (0x01, "HASHSU"),
+    (0x02, "SHA256U"),
+    (0x10, "CHKSIGNU"),
+    (0x11, "CHKSIGNS"),
+    (0x40, "CDATASIZEQ"),
+    (0x41, "CDATASIZE"),
+    (0x42, "SDATASIZEQ"),
+    (0x43, "SDATASIZE"),
+];
+
+const FA_OPS: &[(u64, &str)] = &[
+    (0x00, "LDGRAMS"),
+    (0x01, "LDVARINT16"),
+    (0x02, "STGRAMS"),
+    (0x03, "STVARINT16"),
+    (0x04, "LDVARUINT32"),
+    (0x05, "LDVARINT32"),
+    (0x06, "STVARUINT32"),
+    (0x07, "STVARINT32"),
+    (0x40, "LDMSGADDR"),
+    (0x41, "LDMSGADDRQ"),
+    (0x42, "PARSEMSGADDR"),
+    (0x43, "PARSEMSGADDRQ"),
+    (0x44, "REWRITESTDADDR"),
+    (0x45, "REWRITESTDADDRQ"),
+    (0x46, "REWRITEVARADDR"),
+    (0x47, "REWRITEVARADDRQ"),
+];
+
+const FB_OPS: &[(u64, &str)] = &[
+    (0x00, "SENDRAWMSG"),
+    (0x02, "RAWRESERVE"),
+    (0x03, "RAWRESERVEX"),
+    (0x04, "SETCODE"),
+    (0x06, "SETLIBCODE"),
+    (0x07, "CHANGELIB"),
+];
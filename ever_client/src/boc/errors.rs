@@ -23,6 +23,7 @@ pub enum ErrorCode {
     InsufficientCacheSize = 205,
     BocRefNotFound = 206,
     InvalidBocRef = 207,
+    InvalidConfigParam = 208,
 }
 pub struct Error;
 
@@ -83,4 +84,13 @@ impl Error {
         error.data["boc_ref"] = boc_ref.into();
         error
     }
+
+    pub fn invalid_config_param<E: Display>(param: &str, err: E) -> ClientError {
+        let mut error = error(
+            ErrorCode::InvalidConfigParam,
+            format!("Invalid blockchain config param `{}`: {}", param, err),
+        );
+        error.data["param"] = param.into();
+        error
+    }
 }
@@ -16,6 +16,7 @@ use serde::{Deserialize, Deserializer};
 pub(crate) mod blockchain_config;
 pub(crate) mod cache;
 pub(crate) mod common;
+pub(crate) mod disassemble;
 pub(crate) mod encode;
 mod errors;
 pub mod internal;
@@ -28,7 +29,11 @@ pub(crate) mod tests;
 pub(crate) mod tvc;
 
 pub use blockchain_config::{
-    get_blockchain_config, ParamsOfGetBlockchainConfig, ResultOfGetBlockchainConfig,
+    decode_blockchain_config, encode_blockchain_config, get_blockchain_config,
+    BlockLimitsConfig, BlockchainConfigParams, GasLimitsPricesConfig, GlobalVersionConfig,
+    MsgForwardPricesConfig, ParamLimitsConfig, ParamsOfDecodeBlockchainConfig,
+    ParamsOfEncodeBlockchainConfig, ParamsOfGetBlockchainConfig, ResultOfDecodeBlockchainConfig,
+    ResultOfEncodeBlockchainConfig, ResultOfGetBlockchainConfig, StoragePricesConfig,
 };
 pub use cache::{
     cache_get, cache_set, cache_unpin, BocCacheType, CachedBoc, ParamsOfBocCacheGet,
@@ -38,6 +43,9 @@ pub use common::{
     get_boc_depth, get_boc_hash, ParamsOfGetBocDepth, ParamsOfGetBocHash, ResultOfGetBocDepth,
     ResultOfGetBocHash,
 };
+pub use disassemble::{
+    disassemble_code, DisassembledFunction, ParamsOfDisassembleCode, ResultOfDisassembleCode,
+};
 pub use encode::{encode_boc, BuilderOp, ParamsOfEncodeBoc, ResultOfEncodeBoc};
 pub use encode_external_in_message::{
     encode_external_in_message, ParamsOfEncodeExternalInMessage, ResultOfEncodeExternalInMessage,
@@ -619,6 +619,97 @@ fn get_blockchain_config() {
     );
 }
 
+#[test]
+fn decode_encode_blockchain_config() {
+    let client = TestClient::new();
+    let config_boc = base64::encode(&include_bytes!("test_data/block_config.boc"));
+
+    let decoded: ResultOfDecodeBlockchainConfig = client
+        .request(
+            "boc.decode_blockchain_config",
+            ParamsOfDecodeBlockchainConfig {
+                config_boc: config_boc.clone(),
+            },
+        )
+        .unwrap();
+    let params = decoded.params;
+    assert!(params.global_version.is_some());
+    assert!(!params.storage_prices.as_ref().unwrap().is_empty());
+    assert!(params.masterchain_gas_prices.is_some());
+    assert!(params.block_limits.is_some());
+    assert!(params.msg_forward_prices.is_some());
+
+    // encoding of the decoded params does not change the params
+    let encoded: ResultOfEncodeBlockchainConfig = client
+        .request(
+            "boc.encode_blockchain_config",
+            ParamsOfEncodeBlockchainConfig {
+                params: params.clone(),
+                config_boc: Some(config_boc.clone()),
+                boc_cache: None,
+            },
+        )
+        .unwrap();
+    let decoded: ResultOfDecodeBlockchainConfig = client
+        .request(
+            "boc.decode_blockchain_config",
+            ParamsOfDecodeBlockchainConfig {
+                config_boc: encoded.config_boc,
+            },
+        )
+        .unwrap();
+    assert_eq!(decoded.params, params);
+
+    let mut gas_prices = params.gas_prices.clone().unwrap();
+    gas_prices.gas_price *= 2;
+    let encoded: ResultOfEncodeBlockchainConfig = client
+        .request(
+            "boc.encode_blockchain_config",
+            ParamsOfEncodeBlockchainConfig {
+                params: BlockchainConfigParams {
+                    gas_prices: Some(gas_prices.clone()),
+                    ..Default::default()
+                },
+                config_boc: Some(config_boc),
+                boc_cache: None,
+            },
+        )
+        .unwrap();
+    let decoded: ResultOfDecodeBlockchainConfig = client
+        .request(
+            "boc.decode_blockchain_config",
+            ParamsOfDecodeBlockchainConfig {
+                config_boc: encoded.config_boc,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        decoded.params,
+        BlockchainConfigParams {
+            gas_prices: Some(gas_prices),
+            ..params.clone()
+        }
+    );
+
+    let mut block_limits = params.block_limits.clone().unwrap();
+    block_limits.bytes.soft_limit = block_limits.bytes.hard_limit + 1;
+    let error = client
+        .request::<_, ResultOfEncodeBlockchainConfig>(
+            "boc.encode_blockchain_config",
+            ParamsOfEncodeBlockchainConfig {
+                params: BlockchainConfigParams {
+                    masterchain_block_limits: Some(block_limits),
+                    ..Default::default()
+                },
+                config_boc: None,
+                boc_cache: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidConfigParam as u32);
+    assert_eq!(error.data["param"], "masterchain_block_limits.bytes");
+}
+
 fn read_salted_boc(name: &str) -> String {
     base64::encode(&std::fs::read("src/boc/test_data/salt/".to_owned() + name).unwrap())
 }
@@ -861,6 +952,91 @@ fn test_get_compiler_version() {
     assert_eq!(result.version.as_deref(), Some("sol 0.51.0"));
 }
 
+#[test]
+fn test_disassemble_code() {
+    let client = TestClient::new();
+
+    let mut callee = BuilderData::new();
+    callee.append_raw(&[0xdb, 0x30], 16).unwrap();
+    let callee = callee.into_cell().unwrap();
+    let mut code = BuilderData::new();
+    code.append_raw(&[0xff, 0x00, 0x71, 0x72, 0xa0, 0xdb, 0x3c, 0xf2, 0x0a], 72)
+        .unwrap();
+    code.checked_append_reference(callee.clone()).unwrap();
+    let code = code.into_cell().unwrap();
+
+    let result: ResultOfDisassembleCode = client
+        .request(
+            "boc.disassemble_code",
+            ParamsOfDisassembleCode {
+                code: serialize_cell_to_base64(&code, "code").unwrap(),
+                abi: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        result.assembly,
+        format!(
+            ";; cell {}\nSETCP0\nPUSHINT 1\nPUSHINT 2\nADD\nCALLREF {{\n  ;; cell {}\n  RET\n}}\nTHROW 10\n",
+            code.repr_hash().as_hex_string(),
+            callee.repr_hash().as_hex_string(),
+        )
+    );
+    assert!(result.functions.is_empty());
+
+    let mut code = BuilderData::new();
+    code.append_raw(&[0xff, 0xf1, 0xff, 0xff, 0xff, 0x0f], 48)
+        .unwrap();
+    let code = code.into_cell().unwrap();
+    let result: ResultOfDisassembleCode = client
+        .request(
+            "boc.disassemble_code",
+            ParamsOfDisassembleCode {
+                code: serialize_cell_to_base64(&code, "code").unwrap(),
+                abi: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        result.assembly,
+        format!(
+            ";; cell {}\nSETCP -15\nSETCP -1\nSETCP 15\n",
+            code.repr_hash().as_hex_string(),
+        )
+    );
+
+    let (abi, tvc) = TestClient::package(crate::tests::HELLO, None);
+    let code = client
+        .request::<_, ResultOfGetCodeFromTvc>(
+            "boc.get_code_from_tvc",
+            ParamsOfGetCodeFromTvc { tvc: tvc.unwrap() },
+        )
+        .unwrap()
+        .code;
+    let result: ResultOfDisassembleCode = client
+        .request(
+            "boc.disassemble_code",
+            ParamsOfDisassembleCode {
+                code: code.clone(),
+                abi: Some(abi.clone()),
+            },
+        )
+        .unwrap();
+    assert!(result.assembly.starts_with(";; cell "));
+    assert!(result.assembly.contains("SETCP0"));
+    assert!(result.assembly.contains("DICTPUSHCONST"));
+    assert!(result.assembly.contains("sayHello"));
+    assert!(!result.assembly.contains(".blob"));
+    for function in abi.abi().unwrap().functions().values() {
+        let id = function.get_input_id();
+        assert!(result.functions.iter().any(|f| f.id as u32 == id));
+    }
+
+    let (_, cell) = internal::deserialize_cell_from_base64(&code, "code").unwrap();
+    let reassembled = disassemble::reassemble_code(cell.clone()).unwrap();
+    assert_eq!(reassembled.repr_hash(), cell.repr_hash());
+}
+
 #[test]
 fn encode_external_in_message() {
     TestClient::init_log();
@@ -36,8 +36,13 @@ use crate::error::ClientResult;
 use crate::json_interface::interop::ResponseType;
 use crate::json_interface::request::Request;
 use crate::net::{NetworkConfig, NetworkContext, ServerLink};
-use crate::processing::SdkServices;
+use crate::processing::{
+    MonitorSubscriptions, Outbox, RempMetrics, SdkServices, SendScheduler,
+};
 use crate::proofs::ProofsConfig;
+use crate::tvm::coverage::CoverageSession;
+use crate::tvm::fork::Fork;
+use crate::tvm::libraries::MasterchainLibraries;
 
 #[derive(Default)]
 pub struct Boxes {
@@ -75,6 +80,15 @@ pub struct ClientContext {
 
     // processing module
     pub(crate) message_monitor: Arc<MessageMonitor<SdkServices>>,
+    pub(crate) outbox: RwLock<Option<Arc<Outbox>>>,
+    pub(crate) monitor_subscriptions: MonitorSubscriptions,
+    pub(crate) send_scheduler: SendScheduler,
+    pub(crate) remp_metrics: RempMetrics,
+
+    // tvm module
+    pub(crate) coverage: Arc<CoverageSession>,
+    pub(crate) forks: LockfreeMap<u32, Mutex<Fork>>,
+    pub(crate) masterchain_libraries: MasterchainLibraries,
 
     // proofs module
     pub(crate) proofs_storage: RwLock<Option<Arc<dyn KeyValueStorage>>>,
@@ -104,7 +118,7 @@ impl ClientContext {
 
     pub fn new(config: ClientConfig) -> ClientResult<ClientContext> {
         update_binding_config(&config.binding);
-        let env = Arc::new(ClientEnv::new()?);
+        let env = Arc::new(ClientEnv::new(&config.network)?);
 
         let server_link =
             if config.network.server_address.is_some() || config.network.endpoints.is_some() {
@@ -128,6 +142,13 @@ impl ClientContext {
         Ok(Self {
             net,
             message_monitor,
+            outbox: Default::default(),
+            monitor_subscriptions: Default::default(),
+            send_scheduler: Default::default(),
+            remp_metrics: Default::default(),
+            coverage: Default::default(),
+            forks: LockfreeMap::new(),
+            masterchain_libraries: Default::default(),
             config,
             env: env.clone(),
             debots: LockfreeMap::new(),
@@ -17,6 +17,7 @@ use crate::client::network_mock::NetworkMock;
 use crate::client::storage::KeyValueStorage;
 use crate::client::LOCAL_STORAGE_DEFAULT_DIR_NAME;
 use crate::error::ClientResult;
+use crate::net::{NetworkConfig, NetworkTlsConfig};
 use futures::{Future, SinkExt, StreamExt};
 use lazy_static::lazy_static;
 use reqwest::{
@@ -28,10 +29,15 @@ use std::convert::TryFrom;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
 use tokio::runtime::Runtime;
 #[cfg(test)]
 use tokio::sync::RwLock;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::handshake::client::Request as WsRequest;
 use tokio_tungstenite::tungstenite::Message as WsMessage;
+use tokio_tungstenite::{Connector, WebSocketStream};
 
 #[cfg(test)]
 #[path = "client_env_tests.rs"]
@@ -49,19 +55,184 @@ fn create_runtime() -> ClientResult<Runtime> {
         .map_err(|err| Error::cannot_create_runtime(err))
 }
 
+const MAX_PROXY_RESPONSE_SIZE: usize = 8192;
+
+enum ProxyKind {
+    Http,
+    Socks5 { remote_dns: bool },
+}
+
+struct ProxyConfig {
+    url: String,
+    kind: ProxyKind,
+    host: String,
+    port: u16,
+    credentials: Option<(String, String)>,
+}
+
+// IPv6 addresses are enclosed in brackets in the `host:port` form
+fn authority(host: &str, port: u16) -> String {
+    if host.contains(':') {
+        format!("[{}]:{}", host, port)
+    } else {
+        format!("{}:{}", host, port)
+    }
+}
+
+impl ProxyConfig {
+    fn parse(url: &str) -> ClientResult<Self> {
+        let parsed = reqwest::Url::parse(url)
+            .map_err(|err| Error::invalid_config(format!("Invalid proxy URL {}: {}", url, err)))?;
+        let kind = match parsed.scheme() {
+            "http" => ProxyKind::Http,
+            "socks5" => ProxyKind::Socks5 { remote_dns: false },
+            "socks5h" => ProxyKind::Socks5 { remote_dns: true },
+            scheme => {
+                return Err(Error::invalid_config(format!(
+                    "Unsupported proxy scheme `{}`. Supported schemes: http, socks5, socks5h",
+                    scheme
+                )))
+            }
+        };
+        let host = parsed
+            .host_str()
+            .ok_or_else(|| Error::invalid_config(format!("Proxy URL has no host: {}", url)))?
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_owned();
+        let port = parsed.port().unwrap_or(match kind {
+            ProxyKind::Http => 80,
+            ProxyKind::Socks5 { .. } => 1080,
+        });
+        let credentials = if parsed.username().is_empty() {
+            None
+        } else {
+            Some((
+                parsed.username().to_owned(),
+                parsed.password().unwrap_or_default().to_owned(),
+            ))
+        };
+
+        Ok(Self {
+            url: url.to_owned(),
+            kind,
+            host,
+            port,
+            credentials,
+        })
+    }
+
+    async fn http_connect(&self, host: &str, port: u16) -> Result<TcpStream, String> {
+        let mut stream = TcpStream::connect((self.host.as_str(), self.port))
+            .await
+            .map_err(|err| format!("Can not connect to proxy {}: {}", self.url, err))?;
+
+        let authority = authority(host, port);
+        let mut request = format!("CONNECT {0} HTTP/1.1\r\nHost: {0}\r\n", authority);
+        if let Some((username, password)) = &self.credentials {
+            request.push_str(&format!(
+                "Proxy-Authorization: Basic {}\r\n",
+                base64::encode(format!("{}:{}", username, password).as_bytes())
+            ));
+        }
+        request.push_str("\r\n");
+        stream
+            .write_all(request.as_bytes())
+            .await
+            .map_err(|err| format!("Can not send request to proxy: {}", err))?;
+
+        // Read the response byte by byte so no data after the headers is consumed
+        let mut response = Vec::new();
+        let mut byte = [0u8; 1];
+        while !response.ends_with(b"\r\n\r\n") {
+            if response.len() > MAX_PROXY_RESPONSE_SIZE {
+                return Err("Proxy response headers are too long".to_owned());
+            }
+            let read = stream
+                .read(&mut byte)
+                .await
+                .map_err(|err| format!("Can not read proxy response: {}", err))?;
+            if read == 0 {
+                return Err("Proxy closed connection".to_owned());
+            }
+            response.push(byte[0]);
+        }
+        let response = String::from_utf8_lossy(&response);
+        let status_line = response.lines().next().unwrap_or_default();
+        if status_line.split_whitespace().nth(1) != Some("200") {
+            return Err(format!("Proxy refused connection: {}", status_line));
+        }
+
+        Ok(stream)
+    }
+
+    async fn socks5_connect(
+        &self,
+        host: &str,
+        port: u16,
+        remote_dns: bool,
+    ) -> Result<tokio_socks::tcp::Socks5Stream<TcpStream>, String> {
+        let target = if remote_dns {
+            tokio_socks::TargetAddr::Domain(host.into(), port)
+        } else {
+            let address = tokio::net::lookup_host((host, port))
+                .await
+                .map_err(|err| format!("Can not resolve {}: {}", host, err))?
+                .next()
+                .ok_or_else(|| format!("Can not resolve {}", host))?;
+            tokio_socks::TargetAddr::Ip(address)
+        };
+        let proxy = (self.host.as_str(), self.port);
+        let result = match &self.credentials {
+            Some((username, password)) => {
+                tokio_socks::tcp::Socks5Stream::connect_with_password(
+                    proxy, target, username, password,
+                )
+                .await
+            }
+            None => tokio_socks::tcp::Socks5Stream::connect(proxy, target).await,
+        };
+        result.map_err(|err| format!("SOCKS5 proxy {} error: {}", self.url, err))
+    }
+}
+
 pub(crate) struct ClientEnv {
     cookies: Arc<dyn reqwest::cookie::CookieStore>,
     http_client: HttpClient,
+    proxy: Option<ProxyConfig>,
+    websocket_connector: Option<Connector>,
     async_runtime_handle: tokio::runtime::Handle,
     #[cfg(test)]
     pub network_mock: RwLock<NetworkMock>,
 }
 
 impl ClientEnv {
-    pub fn new() -> ClientResult<Self> {
+    pub fn new(config: &NetworkConfig) -> ClientResult<Self> {
         let cookies = Arc::new(reqwest::cookie::Jar::default());
-        let client = ClientBuilder::new()
-            .cookie_provider(cookies.clone())
+        let mut builder = ClientBuilder::new().cookie_provider(cookies.clone());
+
+        let proxy = config
+            .proxy
+            .as_ref()
+            .map(|url| ProxyConfig::parse(url))
+            .transpose()?;
+        if let Some(proxy) = &proxy {
+            builder = builder.proxy(
+                reqwest::Proxy::all(&proxy.url)
+                    .map_err(|err| Error::invalid_config(format!("Invalid proxy: {}", err)))?,
+            );
+        }
+
+        let websocket_connector = match &config.tls {
+            Some(tls) => {
+                let (http_builder, connector) = Self::apply_tls_config(builder, tls)?;
+                builder = http_builder;
+                Some(connector)
+            }
+            None => None,
+        };
+
+        let client = builder
             .build()
             .map_err(|err| Error::http_client_create_error(err))?;
 
@@ -77,12 +248,103 @@ impl ClientEnv {
         Ok(Self {
             cookies,
             http_client: client,
+            proxy,
+            websocket_connector,
             async_runtime_handle,
             #[cfg(test)]
             network_mock: RwLock::new(NetworkMock::new()),
         })
     }
 
+    #[cfg(feature = "native_tls_crate")]
+    fn apply_tls_config(
+        mut builder: ClientBuilder,
+        tls: &NetworkTlsConfig,
+    ) -> ClientResult<(ClientBuilder, Connector)> {
+        let tls_error = |err: String| Error::invalid_config(format!("Invalid TLS config: {}", err));
+
+        let mut connector = native_tls_crate::TlsConnector::builder();
+        for pem in tls.ca_certificates.iter().flatten() {
+            builder = builder.add_root_certificate(
+                reqwest::Certificate::from_pem(pem.as_bytes())
+                    .map_err(|err| tls_error(err.to_string()))?,
+            );
+            connector.add_root_certificate(
+                native_tls_crate::Certificate::from_pem(pem.as_bytes())
+                    .map_err(|err| tls_error(err.to_string()))?,
+            );
+        }
+        match (&tls.client_certificate, &tls.client_key) {
+            (Some(certificate), Some(key)) => {
+                builder = builder.identity(
+                    reqwest::Identity::from_pkcs8_pem(certificate.as_bytes(), key.as_bytes())
+                        .map_err(|err| tls_error(err.to_string()))?,
+                );
+                connector.identity(
+                    native_tls_crate::Identity::from_pkcs8(certificate.as_bytes(), key.as_bytes())
+                        .map_err(|err| tls_error(err.to_string()))?,
+                );
+            }
+            (None, None) => {}
+            _ => {
+                return Err(tls_error(
+                    "`client_certificate` and `client_key` must be specified together".to_owned(),
+                ))
+            }
+        }
+        let connector = connector
+            .build()
+            .map_err(|err| tls_error(err.to_string()))?;
+
+        Ok((builder, Connector::NativeTls(connector)))
+    }
+
+    #[cfg(not(feature = "native_tls_crate"))]
+    fn apply_tls_config(
+        _builder: ClientBuilder,
+        _tls: &NetworkTlsConfig,
+    ) -> ClientResult<(ClientBuilder, Connector)> {
+        Err(Error::invalid_config(
+            "Custom TLS config is supported only with `native-tls` feature".to_owned(),
+        ))
+    }
+
+    fn websocket_target(url: &str, request: &WsRequest) -> ClientResult<(String, u16)> {
+        let uri = request.uri();
+        let host = uri
+            .host()
+            .ok_or_else(|| Error::websocket_connect_error(url, "URL has no host"))?;
+        let default_port = if uri.scheme_str() == Some("wss") { 443 } else { 80 };
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        Ok((host.to_owned(), uri.port_u16().unwrap_or(default_port)))
+    }
+
+    fn split_websocket<S>(client: WebSocketStream<S>) -> WebSocket
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let (write, read) = client.split();
+
+        let write = write
+            .sink_map_err(|err| Error::websocket_send_error(err))
+            .with(|text| async move { Ok(WsMessage::text(text)) });
+
+        let read = read.filter_map(|result| async move {
+            match result {
+                Ok(message) => match message {
+                    WsMessage::Text(text) => Some(Ok(text)),
+                    _ => None,
+                },
+                Err(err) => Some(Err(Error::websocket_receive_error(err))),
+            }
+        });
+
+        WebSocket {
+            receiver: Box::pin(read),
+            sender: Box::pin(write),
+        }
+    }
+
     fn string_map_to_header_map(headers: HashMap<String, String>) -> ClientResult<HeaderMap> {
         let mut map = HeaderMap::new();
         for (key, value) in headers {
@@ -149,9 +411,9 @@ impl ClientEnv {
                 return Ok(ws);
             }
         }
-        let mut request =
-            tokio_tungstenite::tungstenite::client::IntoClientRequest::into_client_request(url)
-                .map_err(|err| Error::websocket_connect_error(url, err))?;
+        let mut request = url
+            .into_client_request()
+            .map_err(|err| Error::websocket_connect_error(url, err))?;
 
         if let Some(headers) = headers {
             for (key, value) in headers {
@@ -171,30 +433,45 @@ impl ClientEnv {
             }
         }
 
-        let (client, _) = tokio_tungstenite::connect_async(request)
-            .await
-            .map_err(|err| Error::websocket_connect_error(url, err))?;
-
-        let (write, read) = client.split();
-
-        let write = write
-            .sink_map_err(|err| Error::websocket_send_error(err))
-            .with(|text| async move { Ok(WsMessage::text(text)) });
-
-        let read = read.filter_map(|result| async move {
-            match result {
-                Ok(message) => match message {
-                    WsMessage::Text(text) => Some(Ok(text)),
-                    _ => None,
-                },
-                Err(err) => Some(Err(Error::websocket_receive_error(err))),
+        let connector = self.websocket_connector.clone();
+        let proxy = match &self.proxy {
+            Some(proxy) => proxy,
+            None => {
+                let (client, _) =
+                    tokio_tungstenite::connect_async_tls_with_config(request, None, connector)
+                        .await
+                        .map_err(|err| Error::websocket_connect_error(url, err))?;
+                return Ok(Self::split_websocket(client));
             }
-        });
+        };
 
-        Ok(WebSocket {
-            receiver: Box::pin(read),
-            sender: Box::pin(write),
-        })
+        let (host, port) = Self::websocket_target(url, &request)?;
+        match proxy.kind {
+            ProxyKind::Http => {
+                let stream = proxy
+                    .http_connect(&host, port)
+                    .await
+                    .map_err(|err| Error::websocket_connect_error(url, err))?;
+                let (client, _) = tokio_tungstenite::client_async_tls_with_config(
+                    request, stream, None, connector,
+                )
+                .await
+                .map_err(|err| Error::websocket_connect_error(url, err))?;
+                Ok(Self::split_websocket(client))
+            }
+            ProxyKind::Socks5 { remote_dns } => {
+                let stream = proxy
+                    .socks5_connect(&host, port, remote_dns)
+                    .await
+                    .map_err(|err| Error::websocket_connect_error(url, err))?;
+                let (client, _) = tokio_tungstenite::client_async_tls_with_config(
+                    request, stream, None, connector,
+                )
+                .await
+                .map_err(|err| Error::websocket_connect_error(url, err))?;
+                Ok(Self::split_websocket(client))
+            }
+        }
     }
 
     /// Executes http request
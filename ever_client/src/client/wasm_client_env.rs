@@ -15,6 +15,7 @@ use super::{Error, FetchMethod, FetchResult, WebSocket};
 use crate::client::LOCAL_STORAGE_DEFAULT_DIR_NAME;
 use crate::client::storage::KeyValueStorage;
 use crate::error::ClientResult;
+use crate::net::NetworkConfig;
 use futures::{Future, FutureExt, SinkExt, StreamExt};
 use indexed_db_futures::{IdbDatabase, IdbQuerySource, IdbVersionChangeEvent};
 use indexed_db_futures::request::IdbOpenDbRequestLike;
@@ -121,7 +122,12 @@ impl Drop for Timer {
 pub(crate) struct ClientEnv {}
 
 impl ClientEnv {
-    pub fn new() -> ClientResult<Self> {
+    pub fn new(config: &NetworkConfig) -> ClientResult<Self> {
+        if config.proxy.is_some() || config.tls.is_some() {
+            return Err(Error::invalid_config(
+                "Proxy and TLS settings are not supported in the browser environment".to_owned(),
+            ));
+        }
         Ok(Self {})
     }
 
@@ -823,6 +823,7 @@ impl DEngine {
             ParamsOfProcessMessage {
                 message_encode_params: call_params,
                 send_events: true,
+                ..Default::default()
             },
             callback,
         )
@@ -29,6 +29,10 @@ fn register_client(handlers: &mut RuntimeHandlers) {
     module.register_type::<crate::net::NetworkConfig>();
     module.register_type::<crate::client::BindingConfig>();
     module.register_type::<crate::net::NetworkQueriesProtocol>();
+    module.register_type::<crate::net::RetryPolicy>();
+    module.register_type::<crate::net::RetryPolicies>();
+    module.register_type::<crate::net::NetworkTlsConfig>();
+    module.register_type::<crate::net::MessageSendingStrategy>();
     module.register_type::<crate::crypto::CryptoConfig>();
     module.register_type::<crate::abi::AbiConfig>();
     module.register_type::<crate::boc::BocConfig>();
@@ -423,6 +427,21 @@ fn register_boc(handlers: &mut RuntimeHandlers) {
         crate::boc::get_blockchain_config,
         crate::boc::blockchain_config::get_blockchain_config_api,
     );
+    module.register_type::<crate::boc::GlobalVersionConfig>();
+    module.register_type::<crate::boc::StoragePricesConfig>();
+    module.register_type::<crate::boc::GasLimitsPricesConfig>();
+    module.register_type::<crate::boc::ParamLimitsConfig>();
+    module.register_type::<crate::boc::BlockLimitsConfig>();
+    module.register_type::<crate::boc::MsgForwardPricesConfig>();
+    module.register_type::<crate::boc::BlockchainConfigParams>();
+    module.register_sync_fn(
+        crate::boc::decode_blockchain_config,
+        crate::boc::blockchain_config::decode_blockchain_config_api,
+    );
+    module.register_sync_fn(
+        crate::boc::encode_blockchain_config,
+        crate::boc::blockchain_config::encode_blockchain_config_api,
+    );
     module.register_sync_fn(
         crate::boc::get_boc_hash,
         crate::boc::common::get_boc_hash_api,
@@ -463,6 +482,11 @@ fn register_boc(handlers: &mut RuntimeHandlers) {
         crate::boc::get_compiler_version,
         crate::boc::state_init::get_compiler_version_api,
     );
+    module.register_type::<crate::boc::DisassembledFunction>();
+    module.register_sync_fn(
+        crate::boc::disassemble_code,
+        crate::boc::disassemble::disassemble_code_api,
+    );
     module.register();
 }
 
@@ -482,6 +506,13 @@ fn register_net(handlers: &mut RuntimeHandlers) {
     module.register_type::<crate::net::AggregationFn>();
     module.register_type::<crate::net::TransactionNode>();
     module.register_type::<crate::net::MessageNode>();
+    module.register_type::<crate::net::ContractEvent>();
+    module.register_type::<crate::net::AccountStateChange>();
+    module.register_type::<crate::net::AccountDataChange>();
+    module.register_type::<crate::net::TransactionTreeNode>();
+    module.register_type::<crate::net::AbortedTransactionInfo>();
+    module.register_type::<crate::net::TransactionTreeSummary>();
+    module.register_type::<crate::net::EndpointSendingResult>();
 
     module.register_async_fn(crate::net::query, crate::net::queries::query_api);
     module.register_async_fn(crate::net::batch_query, crate::net::batch::batch_query_api);
@@ -506,6 +537,14 @@ fn register_net(handlers: &mut RuntimeHandlers) {
         super::net::subscribe_collection_api,
     );
     module.register_async_fn_with_callback(super::net::subscribe, super::net::subscribe_api);
+    module.register_async_fn_with_callback(
+        super::net::subscribe_events,
+        super::net::subscribe_events_api,
+    );
+    module.register_async_fn_with_callback(
+        super::net::subscribe_account,
+        super::net::subscribe_account_api,
+    );
     module.register_async_fn_no_args(crate::net::suspend, crate::net::suspend_api);
     module.register_async_fn_no_args(crate::net::resume, crate::net::resume_api);
     module.register_async_fn(
@@ -523,6 +562,10 @@ fn register_net(handlers: &mut RuntimeHandlers) {
         crate::net::transaction_tree::query_transaction_tree,
         crate::net::transaction_tree::query_transaction_tree_api,
     );
+    module.register_sync_fn(
+        crate::net::transaction_tree_render::render_transaction_tree,
+        crate::net::transaction_tree_render::render_transaction_tree_api,
+    );
 
     module.register_async_fn(
         crate::net::iterators::block_iterator::create_block_iterator,
@@ -569,10 +612,20 @@ fn register_processing(handlers: &mut RuntimeHandlers) {
 
     module.register_type::<crate::processing::ProcessingEvent>();
     module.register_type::<crate::processing::ResultOfProcessMessage>();
+    module.register_type::<crate::processing::PreflightOptions>();
+    module.register_type::<crate::processing::SendSchedulingOptions>();
+    module.register_type::<crate::processing::BounceTrackingOptions>();
+    module.register_type::<crate::processing::BouncedMessage>();
+    module.register_type::<crate::processing::RempTimelineEntry>();
+    module.register_type::<crate::processing::RempLatencyBucket>();
+    module.register_type::<crate::processing::RempStageLatency>();
     module.register_type::<crate::processing::DecodedOutput>();
+    module.register_type::<crate::processing::ProcessMessageOutcome>();
 
     module.register_type::<crate::processing::MessageMonitoringTransactionCompute>();
     module.register_type::<crate::processing::MessageMonitoringTransaction>();
+    module.register_type::<crate::processing::MessageMonitoringAbortedTransaction>();
+    module.register_type::<crate::processing::MessageMonitoringTransactionTree>();
     module.register_type::<crate::processing::MessageMonitoringParams>();
     module.register_type::<crate::processing::MessageMonitoringResult>();
     module.register_type::<crate::processing::MonitorFetchWaitMode>();
@@ -596,10 +649,30 @@ fn register_processing(handlers: &mut RuntimeHandlers) {
         crate::processing::cancel_monitor,
         crate::processing::cancel_monitor_api,
     );
+    module.register_async_fn_with_callback(
+        super::processing::subscribe_monitor_results,
+        super::processing::subscribe_monitor_results_api,
+    );
+    module.register_async_fn(
+        crate::processing::unsubscribe_monitor_results,
+        crate::processing::unsubscribe_monitor_results_api,
+    );
     module.register_async_fn(
         crate::processing::send_messages,
         crate::processing::send_messages_api,
     );
+    module.register_async_fn_no_args(
+        crate::processing::restore_outbox,
+        crate::processing::restore_outbox_api,
+    );
+    module.register_sync_fn(
+        crate::processing::get_remp_latency_stats,
+        crate::processing::get_remp_latency_stats_api,
+    );
+    module.register_async_fn(
+        crate::processing::plan_expiration,
+        crate::processing::plan_expiration_api,
+    );
 
     module.register_async_fn_with_callback(
         super::processing::send_message,
@@ -613,6 +686,10 @@ fn register_processing(handlers: &mut RuntimeHandlers) {
         super::processing::process_message,
         super::processing::process_message_api,
     );
+    module.register_async_fn_with_callback(
+        super::processing::process_messages,
+        super::processing::process_messages_api,
+    );
 
     module.register();
 }
@@ -626,14 +703,55 @@ fn register_tvm(handlers: &mut RuntimeHandlers) {
     module.register_error_code::<crate::tvm::ErrorCode>();
 
     module.register_type::<crate::tvm::types::ExecutionOptions>();
+    module.register_type::<crate::tvm::TransactionType>();
     module.register_type::<crate::tvm::AccountForExecutor>();
     module.register_type::<crate::tvm::TransactionFees>();
     module.register_async_fn(
         crate::tvm::run_executor,
         crate::tvm::run_message::run_executor_api,
     );
+    module.register_type::<crate::tvm::RunExecutorOutcome>();
+    module.register_async_fn(
+        crate::tvm::run_executor_batch,
+        crate::tvm::run_message::run_executor_batch_api,
+    );
     module.register_async_fn(crate::tvm::run_tvm, crate::tvm::run_message::run_tvm_api);
     module.register_async_fn(crate::tvm::run_get, crate::tvm::run_get::run_get_api);
+    module.register_type::<crate::tvm::InstructionCoverage>();
+    module.register_type::<crate::tvm::CodeCellCoverage>();
+    module.register_sync_fn(
+        crate::tvm::get_coverage,
+        crate::tvm::coverage::get_coverage_api,
+    );
+    module.register_type::<crate::tvm::SourceMapEntry>();
+    module.register_sync_fn(
+        crate::tvm::export_coverage_lcov,
+        crate::tvm::coverage::export_coverage_lcov_api,
+    );
+    module.register_async_fn(crate::tvm::create_fork, crate::tvm::fork::create_fork_api);
+    module.register_type::<crate::tvm::ForkTransaction>();
+    module.register_async_fn(
+        crate::tvm::fork_run_message,
+        crate::tvm::fork::fork_run_message_api,
+    );
+    module.register_async_fn(
+        crate::tvm::fork_get_account,
+        crate::tvm::fork::fork_get_account_api,
+    );
+    module.register_async_fn(
+        crate::tvm::fork_set_account,
+        crate::tvm::fork::fork_set_account_api,
+    );
+    module.register_sync_fn(crate::tvm::remove_fork, crate::tvm::fork::remove_fork_api);
+    module.register_type::<crate::tvm::StorageFeeExplanation>();
+    module.register_type::<crate::tvm::GasFeeExplanation>();
+    module.register_type::<crate::tvm::MessageFeeExplanation>();
+    module.register_type::<crate::tvm::ActionFeeExplanation>();
+    module.register_type::<crate::tvm::FeeConfigParams>();
+    module.register_async_fn(
+        crate::tvm::explain_fees,
+        crate::tvm::explain_fees::explain_fees_api,
+    );
     module.register();
 }
 
@@ -658,6 +776,11 @@ fn register_utils(handlers: &mut RuntimeHandlers) {
         crate::utils::calc_storage_fee,
         crate::utils::calc_storage_fee::calc_storage_fee_api,
     );
+    module.register_type::<crate::utils::StorageFeeEpoch>();
+    module.register_async_fn(
+        crate::utils::project_storage,
+        crate::utils::project_storage::project_storage_api,
+    );
     #[cfg(feature = "include-zstd")]
     module.register_sync_fn(super::utils::compress_zstd, super::utils::compress_zstd_api);
     #[cfg(feature = "include-zstd")]
@@ -17,6 +17,9 @@ use crate::client::ClientContext;
 use crate::error::ClientResult;
 use crate::net::{ParamsOfSubscribeCollection, ResultOfSubscribeCollection, ResultOfSubscription};
 use crate::net::subscriptions::ParamsOfSubscribe;
+use crate::net::{
+    AccountStateChange, ContractEvent, ParamsOfSubscribeAccount, ParamsOfSubscribeEvents,
+};
 
 /// Creates a collection subscription
 ///
@@ -135,3 +138,70 @@ pub(crate) async fn subscribe(
 
     crate::net::subscribe(context, params, callback).await
 }
+
+/// Subscribes to the events emitted by the specified accounts
+///
+/// Events are outbound external messages of the accounts. Each event is decoded
+/// with the provided ABI and delivered to the callback together with the
+/// message id, the id of the producing transaction, logical time and creation time.
+///
+/// Subscription can be closed with `unsubscribe`.
+///
+/// When `from_lt` is specified, events produced after this logical time
+/// are queried and delivered before the realtime ones.
+///
+/// When the connection with the network is restored the application receives
+/// callback with `responseType` == 101 and `params.code` == 614 (NetworkModuleResumed).
+/// After that the library queries the events produced while the client was
+/// disconnected and delivers them, so no events are lost or repeated.
+#[api_function]
+pub(crate) async fn subscribe_events(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfSubscribeEvents,
+    callback: std::sync::Arc<Request>,
+) -> ClientResult<ResultOfSubscribeCollection> {
+    let callback = move |result: ClientResult<ContractEvent>| {
+        match result {
+            Ok(result) => {
+                callback.response(result, crate::net::SubscriptionResponseType::Ok as u32)
+            }
+            Err(err) => callback.response(err, crate::net::SubscriptionResponseType::Error as u32),
+        }
+        futures::future::ready(())
+    };
+
+    crate::net::subscribe_events(context, params, callback).await
+}
+
+/// Subscribes to the account state changes
+///
+/// The current account state is delivered first. After that the new state
+/// is delivered each time the account `last_trans_lt` changes.
+///
+/// If `abi` is specified, the account data is decoded and the list of changes
+/// against the previously delivered decoded data is reported.
+///
+/// Subscription can be closed with `unsubscribe`.
+///
+/// When the connection with the network is restored the application receives
+/// callback with `responseType` == 101 and `params.code` == 614 (NetworkModuleResumed).
+/// After that the library queries the actual account state and delivers it
+/// if it has been changed while the client was disconnected.
+#[api_function]
+pub(crate) async fn subscribe_account(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfSubscribeAccount,
+    callback: std::sync::Arc<Request>,
+) -> ClientResult<ResultOfSubscribeCollection> {
+    let callback = move |result: ClientResult<AccountStateChange>| {
+        match result {
+            Ok(result) => {
+                callback.response(result, crate::net::SubscriptionResponseType::Ok as u32)
+            }
+            Err(err) => callback.response(err, crate::net::SubscriptionResponseType::Error as u32),
+        }
+        futures::future::ready(())
+    };
+
+    crate::net::subscribe_account(context, params, callback).await
+}
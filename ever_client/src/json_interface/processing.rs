@@ -15,9 +15,12 @@
 use super::request::Request;
 use crate::client::ClientContext;
 use crate::error::ClientResult;
+use crate::net::SubscriptionResponseType;
 use crate::processing::{
-    ParamsOfProcessMessage, ParamsOfSendMessage, ParamsOfWaitForTransaction, ProcessingEvent,
-    ProcessingResponseType, ResultOfProcessMessage, ResultOfSendMessage,
+    MessageMonitoringResult, ParamsOfProcessMessage, ParamsOfProcessMessages, ParamsOfSendMessage,
+    ParamsOfSubscribeMonitorResults, ParamsOfWaitForTransaction, ProcessingEvent,
+    ProcessingResponseType, ResultOfProcessMessage, ResultOfProcessMessages, ResultOfSendMessage,
+    ResultOfSubscribeMonitorResults,
 };
 use std::sync::Arc;
 
@@ -52,6 +55,29 @@ pub(crate) async fn process_message(
     crate::processing::process_message(context, params, callback).await
 }
 
+/// Processes multiple messages.
+///
+/// Encodes the messages with bounded concurrency (and checks them in pre-flight mode
+/// if requested), sends them together and monitors their processing in a single
+/// message monitoring queue. Expired messages are re-encoded and resent according to
+/// the retry configuration parameters, as `process_message` does.
+///
+/// Returns the result or the error of each message in the order of the input messages.
+/// Progress of the whole batch is reported with `BatchProgress` events if `send_events`
+/// is specified.
+#[api_function]
+pub(crate) async fn process_messages(
+    context: Arc<ClientContext>,
+    params: ParamsOfProcessMessages,
+    request: Arc<Request>,
+) -> ClientResult<ResultOfProcessMessages> {
+    let callback = move |event: ProcessingEvent| {
+        request.response(event, ProcessingResponseType::ProcessingEvent as u32);
+        futures::future::ready(())
+    };
+    crate::processing::process_messages(context, params, callback).await
+}
+
 /// Sends message to the network
 ///
 /// Sends message to the network and returns the last generated shard block of the destination account
@@ -109,3 +135,30 @@ pub(crate) async fn wait_for_transaction(
     };
     crate::processing::wait_for_transaction(context, params, callback).await
 }
+
+/// Subscribes to the resolved results of the specified monitoring queue.
+///
+/// Each `MessageMonitoringResult` is delivered to the callback as soon as it is resolved
+/// with `responseType` == 100. Errors are delivered with `responseType` == 101.
+///
+/// Delivered results are removed from the queue's resolved list in the same way
+/// as `fetch_next_monitor_results` does, so the application should not mix
+/// both approaches for the same queue.
+///
+/// Subscription must be closed with `unsubscribe_monitor_results`.
+#[api_function]
+pub(crate) async fn subscribe_monitor_results(
+    context: Arc<ClientContext>,
+    params: ParamsOfSubscribeMonitorResults,
+    callback: Arc<Request>,
+) -> ClientResult<ResultOfSubscribeMonitorResults> {
+    let callback = move |result: ClientResult<MessageMonitoringResult>| {
+        match result {
+            Ok(result) => callback.response(result, SubscriptionResponseType::Ok as u32),
+            Err(err) => callback.response(err, SubscriptionResponseType::Error as u32),
+        }
+        futures::future::ready(())
+    };
+
+    crate::processing::subscribe_monitor_results(context, params, callback).await
+}
@@ -0,0 +1,303 @@
+/*
+* Copyright 2018-2021 EverX Labs Ltd.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific EVERX DEV software governing permissions and
+* limitations under the License.
+*/
+
+use crate::abi::{decode_account_data, Abi, ParamsOfDecodeAccountData};
+use crate::client::ClientContext;
+use crate::encoding::account_decode;
+use crate::error::ClientResult;
+use crate::net::{
+    ErrorCode, ParamsOfQueryCollection, ResultOfSubscribeCollection, ACCOUNTS_COLLECTION,
+};
+use futures::Future;
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+const DEFAULT_ACCOUNT_FIELDS: &str = "acc_type balance(format: DEC) code_hash data_hash";
+// fields required by the subscription are requested under the aliases
+// so they don't conflict with the same fields of the caller's projection
+const LT_FIELD_ALIAS: &str = "subscribed_account_lt";
+const DATA_FIELD_ALIAS: &str = "subscribed_account_data";
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone)]
+pub struct ParamsOfSubscribeAccount {
+    /// Account address.
+    pub address: String,
+    /// Projection (result) string of the account fields.
+    ///
+    /// Default is `acc_type balance(format: DEC) code_hash data_hash`.
+    pub result: Option<String>,
+    /// Contract ABI used to decode the account storage.
+    ///
+    /// If specified, the account data is decoded with `abi.decode_account_data`
+    /// and the changes against the previously decoded data are reported.
+    pub abi: Option<Abi>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Clone, Debug, PartialEq)]
+pub struct AccountDataChange {
+    /// JSON pointer (RFC 6901) of the changed value in the decoded data.
+    pub path: String,
+    /// Previous value. Absent if the value has been added.
+    pub before: Option<Value>,
+    /// New value. Absent if the value has been removed.
+    pub after: Option<Value>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Clone, Debug, Default)]
+pub struct AccountStateChange {
+    /// Account address.
+    pub address: String,
+    /// Logical time of the last account transaction (decimal string).
+    pub last_trans_lt: String,
+    /// Account fields requested in `result`.
+    pub account: Value,
+    /// Account data decoded with the provided ABI.
+    pub decoded_data: Option<Value>,
+    /// Changes of the decoded data against the previously delivered state.
+    ///
+    /// Absent for the first delivered state.
+    pub data_changes: Option<Vec<AccountDataChange>>,
+}
+
+enum AccountSourceItem {
+    Refresh,
+    Account(ClientResult<Value>),
+}
+
+struct AccountProcessor {
+    context: Arc<ClientContext>,
+    address: String,
+    result: String,
+    abi: Option<Abi>,
+    last_trans_lt: Option<u64>,
+    decoded_data: Option<Value>,
+}
+
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+pub(crate) fn diff_values(
+    path: &str,
+    before: &Value,
+    after: &Value,
+    changes: &mut Vec<AccountDataChange>,
+) {
+    if before == after {
+        return;
+    }
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => {
+            for (key, value) in before {
+                let path = format!("{}/{}", path, escape_pointer_token(key));
+                match after.get(key) {
+                    Some(new_value) => diff_values(&path, value, new_value, changes),
+                    None => changes.push(AccountDataChange {
+                        path,
+                        before: Some(value.clone()),
+                        after: None,
+                    }),
+                }
+            }
+            for (key, value) in after {
+                if !before.contains_key(key) {
+                    changes.push(AccountDataChange {
+                        path: format!("{}/{}", path, escape_pointer_token(key)),
+                        before: None,
+                        after: Some(value.clone()),
+                    });
+                }
+            }
+        }
+        _ => changes.push(AccountDataChange {
+            path: path.to_string(),
+            before: Some(before.clone()),
+            after: Some(after.clone()),
+        }),
+    }
+}
+
+impl AccountProcessor {
+    async fn run<F: Future<Output = ()> + Send>(
+        mut self,
+        mut receiver: mpsc::UnboundedReceiver<AccountSourceItem>,
+        callback: impl Fn(ClientResult<AccountStateChange>) -> F + Send + Sync + 'static,
+    ) {
+        while let Some(item) = receiver.recv().await {
+            match item {
+                AccountSourceItem::Refresh => self.refresh(&callback).await,
+                AccountSourceItem::Account(Ok(account)) => {
+                    if !account.is_null() {
+                        self.deliver(account, &callback).await;
+                    }
+                }
+                AccountSourceItem::Account(Err(err)) => {
+                    let resumed = err.code == ErrorCode::NetworkModuleResumed as u32;
+                    callback(Err(err)).await;
+                    // account could be changed while the client was disconnected
+                    if resumed {
+                        self.refresh(&callback).await;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn refresh<F: Future<Output = ()> + Send>(
+        &mut self,
+        callback: &(impl Fn(ClientResult<AccountStateChange>) -> F + Send + Sync + 'static),
+    ) {
+        match self.query_account().await {
+            Ok(Some(account)) => self.deliver(account, callback).await,
+            Ok(None) => {}
+            Err(err) => callback(Err(err)).await,
+        }
+    }
+
+    async fn query_account(&self) -> ClientResult<Option<Value>> {
+        let result = self
+            .context
+            .get_server_link()?
+            .query_collection(
+                ParamsOfQueryCollection {
+                    collection: ACCOUNTS_COLLECTION.to_string(),
+                    filter: Some(json!({ "id": { "eq": self.address } })),
+                    result: self.result.clone(),
+                    limit: Some(1),
+                    ..Default::default()
+                },
+                None,
+            )
+            .await?;
+        Ok(result
+            .as_array()
+            .and_then(|accounts| accounts.first().cloned()))
+    }
+
+    fn decode_data(&self, account: &Value) -> ClientResult<Option<Value>> {
+        let abi = match &self.abi {
+            Some(abi) => abi,
+            None => return Ok(None),
+        };
+        let data = match account[DATA_FIELD_ALIAS].as_str() {
+            Some(data) => data,
+            None => return Ok(None),
+        };
+        let decoded = decode_account_data(
+            self.context.clone(),
+            ParamsOfDecodeAccountData {
+                abi: abi.clone(),
+                data: data.to_string(),
+                allow_partial: false,
+            },
+        )?;
+        Ok(Some(decoded.data))
+    }
+
+    async fn deliver<F: Future<Output = ()> + Send>(
+        &mut self,
+        mut account: Value,
+        callback: &(impl Fn(ClientResult<AccountStateChange>) -> F + Send + Sync + 'static),
+    ) {
+        let last_trans_lt = account[LT_FIELD_ALIAS]
+            .as_str()
+            .and_then(|lt| lt.parse::<u64>().ok())
+            .unwrap_or_default();
+        // the same or an outdated state can be received from both the subscription
+        // and the refreshing query
+        if self
+            .last_trans_lt
+            .map(|last| last_trans_lt <= last)
+            .unwrap_or(false)
+        {
+            return;
+        }
+        self.last_trans_lt = Some(last_trans_lt);
+
+        let decoded_data = match self.decode_data(&account) {
+            Ok(decoded_data) => decoded_data,
+            Err(err) => {
+                callback(Err(err)).await;
+                None
+            }
+        };
+        let data_changes = match (&self.decoded_data, &decoded_data) {
+            (Some(before), Some(after)) => {
+                let mut changes = Vec::new();
+                diff_values("", before, after, &mut changes);
+                Some(changes)
+            }
+            _ => None,
+        };
+        self.decoded_data = decoded_data.clone();
+
+        if let Some(fields) = account.as_object_mut() {
+            fields.remove(LT_FIELD_ALIAS);
+            fields.remove(DATA_FIELD_ALIAS);
+        }
+
+        callback(Ok(AccountStateChange {
+            address: self.address.clone(),
+            last_trans_lt: last_trans_lt.to_string(),
+            account,
+            decoded_data,
+            data_changes,
+        }))
+        .await;
+    }
+}
+
+pub async fn subscribe_account<F: Future<Output = ()> + Send>(
+    context: Arc<ClientContext>,
+    params: ParamsOfSubscribeAccount,
+    callback: impl Fn(ClientResult<AccountStateChange>) -> F + Send + Sync + 'static,
+) -> ClientResult<ResultOfSubscribeCollection> {
+    let address = account_decode(&params.address)?.to_string();
+    let mut result = format!(
+        "{} {}: last_trans_lt(format: DEC)",
+        params.result.as_deref().unwrap_or(DEFAULT_ACCOUNT_FIELDS),
+        LT_FIELD_ALIAS,
+    );
+    if params.abi.is_some() {
+        result.push_str(&format!(" {}: data", DATA_FIELD_ALIAS));
+    }
+
+    let (sender, receiver) = mpsc::unbounded_channel();
+    // current state is delivered first and used as a base for the data changes
+    let _ = sender.send(AccountSourceItem::Refresh);
+    let handle = context
+        .net
+        .subscribe_collection(
+            ACCOUNTS_COLLECTION.to_string(),
+            Some(json!({ "id": { "eq": address } })),
+            result.clone(),
+            move |result| {
+                let _ = sender.send(AccountSourceItem::Account(result.map(|data| data.result)));
+                futures::future::ready(())
+            },
+        )
+        .await?;
+
+    let processor = AccountProcessor {
+        context: context.clone(),
+        address,
+        result,
+        abi: params.abi,
+        last_trans_lt: None,
+        decoded_data: None,
+    };
+    context.env.spawn(processor.run(receiver, callback));
+
+    Ok(ResultOfSubscribeCollection { handle })
+}
@@ -70,6 +70,7 @@ impl Endpoint {
         if let Some(auth) = config.get_auth_header() {
             headers.push(auth);
         }
+        headers.extend(config.get_extra_headers());
         headers
     }
 
@@ -221,6 +222,12 @@ impl Endpoint {
         Ok(())
     }
 
+    /// Difference between the server time and the client time in ms
+    /// detected at the last server info request.
+    pub fn server_time_delta(&self) -> i64 {
+        self.server_time_delta.load(Ordering::Relaxed)
+    }
+
     pub fn latency(&self) -> u64 {
         self.server_latency.load(Ordering::Relaxed)
     }
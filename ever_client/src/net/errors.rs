@@ -23,6 +23,8 @@ pub enum ErrorCode {
     QueryTransactionTreeTimeout = 616,
     GraphqlConnectionError = 617,
     WrongWebscoketProtocolSequence = 618,
+    InvalidEventsFilter = 619,
+    InvalidTransactionTree = 620,
 }
 
 pub struct Error;
@@ -205,4 +207,39 @@ impl Error {
             format!("Wrong webscoket protocol sequence: {}", err),
         )
     }
+
+    pub fn events_addresses_missing() -> ClientError {
+        error(
+            ErrorCode::InvalidEventsFilter,
+            "Invalid events filter: at least one address must be specified".to_owned(),
+        )
+    }
+
+    pub fn invalid_events_from_lt<E: Display>(from_lt: &str, err: E) -> ClientError {
+        let mut error = error(
+            ErrorCode::InvalidEventsFilter,
+            format!("Invalid events filter: invalid `from_lt` value: {}", err),
+        );
+        error.data = json!({ "from_lt": from_lt });
+        error
+    }
+
+    pub fn transaction_tree_root_missing(in_msg: &str) -> ClientError {
+        let mut error = error(
+            ErrorCode::InvalidTransactionTree,
+            "Invalid transaction tree: root message is missing in the provided messages"
+                .to_owned(),
+        );
+        error.data = json!({ "in_msg": in_msg });
+        error
+    }
+
+    pub fn invalid_transaction_tree_tokens<E: Display>(value: &str, err: E) -> ClientError {
+        let mut error = error(
+            ErrorCode::InvalidTransactionTree,
+            format!("Invalid transaction tree: invalid tokens value: {}", err),
+        );
+        error.data = json!({ "value": value });
+        error
+    }
 }
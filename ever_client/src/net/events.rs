@@ -0,0 +1,324 @@
+/*
+* Copyright 2018-2021 EverX Labs Ltd.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific EVERX DEV software governing permissions and
+* limitations under the License.
+*/
+
+use crate::abi::{
+    decode_message_body, Abi, DecodedMessageBody, MessageBodyType, ParamsOfDecodeMessageBody,
+};
+use crate::client::ClientContext;
+use crate::encoding::account_decode;
+use crate::error::ClientResult;
+use crate::net::{
+    Error, ErrorCode, OrderBy, ParamsOfQueryCollection, ResultOfSubscribeCollection, SortDirection,
+    MESSAGES_COLLECTION,
+};
+use futures::Future;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+const EXT_OUT_MESSAGE_TYPE: u8 = 2;
+const EVENT_FIELDS: &str = "id src body created_lt(format: DEC) created_at";
+const CATCH_UP_FIELDS: &str =
+    "id src body created_lt(format: DEC) created_at src_transaction { id }";
+const CATCH_UP_PAGE_SIZE: u32 = 50;
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone)]
+pub struct ParamsOfSubscribeEvents {
+    /// Addresses of the accounts which events must be delivered.
+    pub addresses: Vec<String>,
+    /// Contract ABI used to decode the events.
+    pub abi: Abi,
+    /// Names of the events to deliver.
+    ///
+    /// If not specified, all events described in the ABI are delivered.
+    pub event_names: Option<Vec<String>>,
+    /// Logical time to resume from.
+    ///
+    /// If specified, events with `lt` greater than this value produced before the
+    /// subscription has been started are delivered first. Can be a decimal or
+    /// a `0x` prefixed hex string.
+    pub from_lt: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Clone, Debug)]
+pub struct ContractEvent {
+    /// Id of the event message.
+    pub message_id: String,
+    /// Address of the account that emitted the event.
+    pub address: String,
+    /// Id of the transaction that produced the event.
+    pub transaction_id: Option<String>,
+    /// Logical time of the event message creation (decimal string).
+    pub lt: String,
+    /// Unix time of the event message creation.
+    pub created_at: u32,
+    /// Decoded event.
+    pub event: DecodedMessageBody,
+}
+
+enum EventsSourceItem {
+    CatchUp,
+    Message(ClientResult<Value>),
+}
+
+struct EventsProcessor {
+    context: Arc<ClientContext>,
+    addresses: Vec<String>,
+    abi: Abi,
+    event_names: Option<Vec<String>>,
+    from_lt: Option<u64>,
+    started_at: u32,
+    last_lt: HashMap<String, u64>,
+}
+
+fn parse_lt(lt: &str) -> Result<u64, String> {
+    let result = if let Some(hex) = lt.strip_prefix("0x") {
+        u64::from_str_radix(hex, 16)
+    } else {
+        lt.parse()
+    };
+    result.map_err(|err| format!("invalid lt [{}]: {}", lt, err))
+}
+
+fn get_str<'a>(value: &'a Value, field: &str) -> &'a str {
+    value[field].as_str().unwrap_or_default()
+}
+
+impl EventsProcessor {
+    async fn run<F: Future<Output = ()> + Send>(
+        mut self,
+        mut receiver: mpsc::UnboundedReceiver<EventsSourceItem>,
+        callback: impl Fn(ClientResult<ContractEvent>) -> F + Send + Sync + 'static,
+    ) {
+        while let Some(item) = receiver.recv().await {
+            match item {
+                EventsSourceItem::CatchUp => self.catch_up(&callback).await,
+                EventsSourceItem::Message(Ok(message)) => {
+                    if !message.is_null() {
+                        self.deliver(message, &callback).await;
+                    }
+                }
+                EventsSourceItem::Message(Err(err)) => {
+                    let resumed = err.code == ErrorCode::NetworkModuleResumed as u32;
+                    callback(Err(err)).await;
+                    // events produced while the client was disconnected are queried
+                    // and delivered after the resumed notification
+                    if resumed {
+                        self.catch_up(&callback).await;
+                    }
+                }
+            }
+        }
+    }
+
+    fn catch_up_filter(&self, address: &str) -> Value {
+        let mut filter = json!({
+            "src": { "eq": address },
+            "msg_type": { "eq": EXT_OUT_MESSAGE_TYPE },
+        });
+        match self.last_lt.get(address).copied().or(self.from_lt) {
+            Some(lt) => filter["created_lt"] = json!({ "gt": lt.to_string() }),
+            None => filter["created_at"] = json!({ "ge": self.started_at }),
+        }
+        filter
+    }
+
+    async fn catch_up<F: Future<Output = ()> + Send>(
+        &mut self,
+        callback: &(impl Fn(ClientResult<ContractEvent>) -> F + Send + Sync + 'static),
+    ) {
+        for address in self.addresses.clone() {
+            loop {
+                let messages = match self.query_missed_messages(&address).await {
+                    Ok(messages) => messages,
+                    Err(err) => {
+                        callback(Err(err)).await;
+                        return;
+                    }
+                };
+                let count = messages.len();
+                for message in messages {
+                    self.deliver(message, callback).await;
+                }
+                if count < CATCH_UP_PAGE_SIZE as usize {
+                    break;
+                }
+            }
+        }
+    }
+
+    async fn query_missed_messages(&self, address: &str) -> ClientResult<Vec<Value>> {
+        let result = self
+            .context
+            .get_server_link()?
+            .query_collection(
+                ParamsOfQueryCollection {
+                    collection: MESSAGES_COLLECTION.to_string(),
+                    filter: Some(self.catch_up_filter(address)),
+                    result: CATCH_UP_FIELDS.to_string(),
+                    order: Some(vec![OrderBy {
+                        path: "created_lt".to_string(),
+                        direction: SortDirection::ASC,
+                    }]),
+                    limit: Some(CATCH_UP_PAGE_SIZE),
+                },
+                None,
+            )
+            .await?;
+        Ok(result.as_array().cloned().unwrap_or_default())
+    }
+
+    async fn query_transaction_id(&self, message_id: &str) -> Option<String> {
+        let result = self
+            .context
+            .get_server_link()
+            .ok()?
+            .query_collection(
+                ParamsOfQueryCollection {
+                    collection: MESSAGES_COLLECTION.to_string(),
+                    filter: Some(json!({ "id": { "eq": message_id } })),
+                    result: "src_transaction { id }".to_string(),
+                    limit: Some(1),
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .ok()?;
+        result[0]["src_transaction"]["id"]
+            .as_str()
+            .map(|id| id.to_string())
+    }
+
+    fn decode_event(&self, body: &str) -> Option<DecodedMessageBody> {
+        let decoded = decode_message_body(
+            self.context.clone(),
+            ParamsOfDecodeMessageBody {
+                abi: self.abi.clone(),
+                body: body.to_string(),
+                is_internal: false,
+                ..Default::default()
+            },
+        )
+        .ok()?;
+        if decoded.body_type != MessageBodyType::Event {
+            return None;
+        }
+        if let Some(names) = &self.event_names {
+            if !names.contains(&decoded.name) {
+                return None;
+            }
+        }
+        Some(decoded)
+    }
+
+    async fn deliver<F: Future<Output = ()> + Send>(
+        &mut self,
+        message: Value,
+        callback: &(impl Fn(ClientResult<ContractEvent>) -> F + Send + Sync + 'static),
+    ) {
+        let address = get_str(&message, "src").to_string();
+        let lt = match parse_lt(get_str(&message, "created_lt")) {
+            Ok(lt) => lt,
+            Err(err) => {
+                callback(Err(Error::invalid_server_response(err))).await;
+                return;
+            }
+        };
+        // messages already delivered by the subscription or by the previous catch up
+        // are skipped
+        if self
+            .last_lt
+            .get(&address)
+            .map(|last| lt <= *last)
+            .unwrap_or(false)
+        {
+            return;
+        }
+        self.last_lt.insert(address.clone(), lt);
+
+        let event = match self.decode_event(get_str(&message, "body")) {
+            Some(event) => event,
+            None => return,
+        };
+        let message_id = get_str(&message, "id").to_string();
+        let transaction_id = match message["src_transaction"]["id"].as_str() {
+            Some(id) => Some(id.to_string()),
+            None => self.query_transaction_id(&message_id).await,
+        };
+        callback(Ok(ContractEvent {
+            message_id,
+            address,
+            transaction_id,
+            lt: lt.to_string(),
+            created_at: message["created_at"].as_u64().unwrap_or_default() as u32,
+            event,
+        }))
+        .await;
+    }
+}
+
+pub async fn subscribe_events<F: Future<Output = ()> + Send>(
+    context: Arc<ClientContext>,
+    params: ParamsOfSubscribeEvents,
+    callback: impl Fn(ClientResult<ContractEvent>) -> F + Send + Sync + 'static,
+) -> ClientResult<ResultOfSubscribeCollection> {
+    if params.addresses.is_empty() {
+        return Err(Error::events_addresses_missing());
+    }
+    let addresses = params
+        .addresses
+        .iter()
+        .map(|address| Ok(account_decode(address)?.to_string()))
+        .collect::<ClientResult<Vec<String>>>()?;
+    let from_lt = params
+        .from_lt
+        .as_deref()
+        .map(|from_lt| parse_lt(from_lt).map_err(|err| Error::invalid_events_from_lt(from_lt, err)))
+        .transpose()?;
+
+    let (sender, receiver) = mpsc::unbounded_channel();
+    if from_lt.is_some() {
+        let _ = sender.send(EventsSourceItem::CatchUp);
+    }
+    let filter = json!({
+        "src": { "in": addresses },
+        "msg_type": { "eq": EXT_OUT_MESSAGE_TYPE },
+    });
+    let handle = context
+        .net
+        .subscribe_collection(
+            MESSAGES_COLLECTION.to_string(),
+            Some(filter),
+            EVENT_FIELDS.to_string(),
+            move |result| {
+                let _ = sender.send(EventsSourceItem::Message(result.map(|data| data.result)));
+                futures::future::ready(())
+            },
+        )
+        .await?;
+
+    let processor = EventsProcessor {
+        context: context.clone(),
+        addresses,
+        abi: params.abi,
+        event_names: params.event_names,
+        from_lt,
+        started_at: (context.env.now_ms() / 1000) as u32,
+        last_lt: HashMap::new(),
+    };
+    context.env.spawn(processor.run(receiver, callback));
+
+    Ok(ResultOfSubscribeCollection { handle })
+}
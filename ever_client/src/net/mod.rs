@@ -11,9 +11,13 @@
 * limitations under the License.
 */
 
+pub use account_subscription::{
+    subscribe_account, AccountDataChange, AccountStateChange, ParamsOfSubscribeAccount,
+};
 pub use batch::{batch_query, ParamsOfBatchQuery, ResultOfBatchQuery};
 pub(crate) use endpoint::Endpoint;
 pub use errors::{Error, ErrorCode};
+pub use events::{subscribe_events, ContractEvent, ParamsOfSubscribeEvents};
 pub use iterators::block_iterator::{
     create_block_iterator, resume_block_iterator, ParamsOfCreateBlockIterator,
     ParamsOfResumeBlockIterator,
@@ -46,17 +50,25 @@ pub use transaction_tree::{
     query_transaction_tree, MessageNode, ParamsOfQueryTransactionTree,
     ResultOfQueryTransactionTree, TransactionNode,
 };
+pub(crate) use types::RetryOperation;
+pub use transaction_tree_render::{
+    render_transaction_tree, AbortedTransactionInfo, ParamsOfRenderTransactionTree,
+    ResultOfRenderTransactionTree, TransactionTreeNode, TransactionTreeSummary,
+};
 pub use types::{
-    NetworkConfig, NetworkQueriesProtocol, ACCOUNTS_COLLECTION, BLOCKS_COLLECTION,
-    MESSAGES_COLLECTION, TRANSACTIONS_COLLECTION,
+    EndpointSendingResult, MessageSendingStrategy, NetworkConfig, NetworkQueriesProtocol,
+    NetworkTlsConfig, RetryPolicies, RetryPolicy,
+    ACCOUNTS_COLLECTION, BLOCKS_COLLECTION, MESSAGES_COLLECTION, TRANSACTIONS_COLLECTION,
 };
 
 use crate::client::ClientContext;
 use crate::error::ClientResult;
 
+pub(crate) mod account_subscription;
 pub(crate) mod batch;
 mod endpoint;
 mod errors;
+pub(crate) mod events;
 mod gql;
 pub(crate) mod iterators;
 pub(crate) mod network_params;
@@ -65,6 +77,7 @@ mod server_link;
 pub(crate) mod subscriptions;
 mod ton_gql;
 pub(crate) mod transaction_tree;
+pub(crate) mod transaction_tree_render;
 pub(crate) mod types;
 mod websocket_link;
 
@@ -15,7 +15,10 @@ use crate::client::{ClientEnv, FetchMethod};
 use crate::error::{AddNetworkUrl, ClientError, ClientResult};
 use crate::net::endpoint::Endpoint;
 use crate::net::ton_gql::GraphQLQuery;
-use crate::net::types::NetworkQueriesProtocol;
+use crate::net::types::{
+    EndpointSendingResult, MessageSendingStrategy, NetworkQueriesProtocol, RetryOperation,
+    RetryPolicy,
+};
 use crate::net::websocket_link::WebsocketLink;
 use crate::net::{
     Error, GraphQLQueryEvent, NetworkConfig, ParamsOfAggregateCollection, ParamsOfQueryCollection,
@@ -27,7 +30,7 @@ use serde_json::Value;
 use std::cmp::{max, min};
 use std::collections::{HashMap, HashSet};
 use std::pin::Pin;
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::sync::{watch, Mutex, RwLock};
 use ever_block::UInt256;
@@ -66,6 +69,7 @@ pub(crate) struct NetworkState {
     endpoint_addresses: RwLock<Vec<String>>,
     has_multiple_endpoints: AtomicBool,
     bad_delivery_addresses: RwLock<HashSet<String>>,
+    round_robin_index: AtomicUsize,
     suspended: watch::Receiver<bool>,
     suspend_regulation: Arc<Mutex<SuspendRegulation>>,
     resume_timeout: AtomicU32,
@@ -111,6 +115,7 @@ impl NetworkState {
             endpoint_addresses: RwLock::new(endpoint_addresses),
             has_multiple_endpoints,
             bad_delivery_addresses: RwLock::new(HashSet::new()),
+            round_robin_index: AtomicUsize::new(0),
             suspended: receiver,
             suspend_regulation: Arc::new(Mutex::new(regulation)),
             resume_timeout: AtomicU32::new(0),
@@ -210,6 +215,31 @@ impl NetworkState {
         addresses
     }
 
+    /// Returns endpoint addresses in the order they should be used for sending
+    /// with the specified strategy.
+    pub async fn get_addresses_for_strategy(
+        &self,
+        strategy: MessageSendingStrategy,
+    ) -> Vec<String> {
+        match strategy {
+            MessageSendingStrategy::Random | MessageSendingStrategy::Broadcast => {
+                self.get_addresses_for_sending().await
+            }
+            MessageSendingStrategy::PrimaryWithFailover => {
+                self.endpoint_addresses.read().await.clone()
+            }
+            MessageSendingStrategy::RoundRobin => {
+                let mut addresses = self.endpoint_addresses.read().await.clone();
+                if !addresses.is_empty() {
+                    let start = self.round_robin_index.fetch_add(1, Ordering::Relaxed);
+                    let len = addresses.len();
+                    addresses.rotate_left(start % len);
+                }
+                addresses
+            }
+        }
+    }
+
     pub async fn update_stat(&self, addresses: &[String], stat: EndpointStat) {
         let bad_delivery = self.bad_delivery_addresses.read().await.clone();
         let addresses: HashSet<_> = addresses.iter().cloned().collect();
@@ -260,6 +290,7 @@ impl NetworkState {
                 _ => false,
             };
         let start = self.client_env.now_ms();
+        let mut retry_index = 0;
         loop {
             let mut futures = vec![];
             for address in self.endpoint_addresses.read().await.iter() {
@@ -299,13 +330,14 @@ impl NetworkState {
             if let Some(unauthorised) = unauthorised {
                 return Err(unauthorised);
             }
-            if !self.can_retry_network_error(start) {
+            if !self.can_retry(RetryOperation::Query, retry_index, start) {
                 return selected;
             }
             let _ = self
                 .client_env
-                .set_timer(self.next_resume_timeout() as u64)
+                .set_timer(self.next_retry_timeout(RetryOperation::Query, retry_index) as u64)
                 .await;
+            retry_index += 1;
         }
     }
 
@@ -359,6 +391,41 @@ impl NetworkState {
         self.client_env.now_ms() < start + self.config.max_reconnect_timeout as u64
     }
 
+    pub fn retry_policy(&self, operation: RetryOperation) -> Option<&RetryPolicy> {
+        self.config.retry_policy(operation)
+    }
+
+    /// Checks if the error is retryable by the policy of the operation.
+    /// Without the policy only network errors are retryable.
+    pub fn is_retryable_error(&self, operation: RetryOperation, error: &ClientError) -> bool {
+        match self.retry_policy(operation) {
+            Some(policy) => policy.is_retryable_error(error),
+            None => crate::client::Error::is_network_error(error),
+        }
+    }
+
+    /// Checks if the retry with the specified index (starting from 0) is allowed by the policy
+    /// of the operation. Without the policy retries are limited by `max_reconnect_timeout`.
+    pub fn can_retry(&self, operation: RetryOperation, retry_index: u32, start: u64) -> bool {
+        match self.retry_policy(operation) {
+            Some(policy) => policy.can_retry(
+                retry_index,
+                self.client_env.now_ms(),
+                start + self.config.max_reconnect_timeout as u64,
+            ),
+            None => self.can_retry_network_error(start),
+        }
+    }
+
+    /// Returns the delay before the retry with the specified index (starting from 0).
+    /// Without the policy the shared resume timeout is used.
+    pub fn next_retry_timeout(&self, operation: RetryOperation, retry_index: u32) -> u32 {
+        match self.retry_policy(operation) {
+            Some(policy) => policy.delay(retry_index),
+            None => self.next_resume_timeout(),
+        }
+    }
+
     pub fn env(&self) -> &Arc<ClientEnv> {
         &self.client_env
     }
@@ -468,6 +535,7 @@ impl ServerLink {
 
         let mut id = None;
         let start = self.client_env.now_ms();
+        let mut retry_index = 0;
         loop {
             match event_receiver.recv().await {
                 Some(GraphQLQueryEvent::Id(received_id)) => id = Some(received_id),
@@ -488,10 +556,17 @@ impl ServerLink {
                         continue;
                     }
                     let is_retryable = err.code != ErrorCode::GraphqlWebsocketInitError as u32
-                        && crate::client::Error::is_network_error(&err);
-                    if !is_retryable || !self.state.can_retry_network_error(start) {
+                        && self
+                            .state
+                            .is_retryable_error(RetryOperation::Subscription, &err);
+                    if !is_retryable
+                        || !self
+                            .state
+                            .can_retry(RetryOperation::Subscription, retry_index, start)
+                    {
                         return Err(err);
                     }
+                    retry_index += 1;
                 }
                 Some(GraphQLQueryEvent::Started) => break,
                 None => {
@@ -539,6 +614,7 @@ impl ServerLink {
         &self,
         query: &GraphQLQuery,
         endpoint: Option<&Endpoint>,
+        operation: RetryOperation,
     ) -> ClientResult<Value> {
         let request = json!({
             "query": query.query,
@@ -554,6 +630,7 @@ impl ServerLink {
 
         let mut current_endpoint: Option<Arc<Endpoint>>;
         let start = self.client_env.now_ms();
+        let mut retry_index = 0;
         loop {
             let endpoint = if let Some(endpoint) = endpoint {
                 endpoint
@@ -591,20 +668,20 @@ impl ServerLink {
             };
 
             if let Err(err) = &result {
-                if crate::client::Error::is_network_error(err) {
-                    let multiple_endpoints = self.state.has_multiple_endpoints();
+                if self.state.is_retryable_error(operation, err) {
+                    let multiple_endpoints = crate::client::Error::is_network_error(err)
+                        && self.state.has_multiple_endpoints();
                     if multiple_endpoints {
                         self.state.internal_suspend().await;
                         self.websocket_link.suspend().await;
                         self.websocket_link.resume().await;
                     }
-                    if self.state.can_retry_network_error(start) {
+                    if self.state.can_retry(operation, retry_index, start) {
                         if !multiple_endpoints {
-                            let _ = self
-                                .client_env
-                                .set_timer(self.state.next_resume_timeout() as u64)
-                                .await;
+                            let timeout = self.state.next_retry_timeout(operation, retry_index);
+                            let _ = self.client_env.set_timer(timeout as u64).await;
                         }
+                        retry_index += 1;
                         continue;
                     }
                 }
@@ -614,11 +691,16 @@ impl ServerLink {
         }
     }
 
-    pub(crate) async fn query_ws(&self, query: &GraphQLQuery) -> ClientResult<Value> {
+    pub(crate) async fn query_ws(
+        &self,
+        query: &GraphQLQuery,
+        operation: RetryOperation,
+    ) -> ClientResult<Value> {
         let mut receiver = self.websocket_link.start_operation(query.clone()).await?;
         let mut id = None::<u32>;
         let mut result = Ok(Value::Null);
         let start = self.client_env.now_ms();
+        let mut retry_index = 0;
         loop {
             match receiver.recv().await {
                 Some(GraphQLQueryEvent::Id(received_id)) => id = Some(received_id),
@@ -634,11 +716,12 @@ impl ServerLink {
                         continue;
                     }
                     let is_retryable = err.code != ErrorCode::GraphqlWebsocketInitError as u32
-                        && crate::client::Error::is_network_error(&err);
+                        && self.state.is_retryable_error(operation, &err);
                     result = Err(err);
-                    if !is_retryable || !self.state.can_retry_network_error(start) {
+                    if !is_retryable || !self.state.can_retry(operation, retry_index, start) {
                         break;
                     }
+                    retry_index += 1;
                 }
                 Some(GraphQLQueryEvent::Started) => {}
                 None => break,
@@ -654,10 +737,20 @@ impl ServerLink {
         &self,
         query: &GraphQLQuery,
         endpoint: Option<&Endpoint>,
+    ) -> ClientResult<Value> {
+        self.query_with_retry_policy(query, endpoint, RetryOperation::Query)
+            .await
+    }
+
+    async fn query_with_retry_policy(
+        &self,
+        query: &GraphQLQuery,
+        endpoint: Option<&Endpoint>,
+        operation: RetryOperation,
     ) -> ClientResult<Value> {
         match self.config.queries_protocol {
-            NetworkQueriesProtocol::HTTP => self.query_http(query, endpoint).await,
-            NetworkQueriesProtocol::WS => self.query_ws(query).await,
+            NetworkQueriesProtocol::HTTP => self.query_http(query, endpoint, operation).await,
+            NetworkQueriesProtocol::WS => self.query_ws(query, operation).await,
         }
     }
 
@@ -748,21 +841,36 @@ impl ServerLink {
             .remove(0))
     }
 
-    // Sends message to node
+    // Sends message to node and reports the endpoint response
     pub async fn send_message(
         &self,
         key: &[u8],
         value: &[u8],
         endpoint: Option<&Endpoint>,
-    ) -> ClientResult<Option<ClientError>> {
+    ) -> ClientResult<EndpointSendingResult> {
         let request = PostRequest {
             id: base64::encode(key),
             body: base64::encode(value),
         };
+        // the query endpoint is resolved once and used both for sending and reporting
+        let query_endpoint;
+        let endpoint = match endpoint {
+            Some(endpoint) => endpoint,
+            None => {
+                query_endpoint = self.state.get_query_endpoint().await?;
+                query_endpoint.as_ref()
+            }
+        };
 
+        let start = self.client_env.now_ms();
         let result = self
-            .query(&GraphQLQuery::with_post_requests(&[request]), endpoint)
+            .query_with_retry_policy(
+                &GraphQLQuery::with_post_requests(&[request]),
+                Some(endpoint),
+                RetryOperation::Send,
+            )
             .await;
+        let latency_ms = self.client_env.now_ms().saturating_sub(start);
 
         // send message is always successful in order to process case when server received message
         // but client didn't receive response
@@ -770,7 +878,12 @@ impl ServerLink {
             log::warn!("Post message error: {}", err.message);
         }
 
-        Ok(result.err())
+        Ok(EndpointSendingResult {
+            endpoint: endpoint.query_url.clone(),
+            accepted: result.is_ok(),
+            latency_ms,
+            error: result.err(),
+        })
     }
 
     pub async fn send_messages(
@@ -786,7 +899,11 @@ impl ServerLink {
             })
         }
         let result = self
-            .query(&GraphQLQuery::with_post_requests(&requests), endpoint)
+            .query_with_retry_policy(
+                &GraphQLQuery::with_post_requests(&requests),
+                endpoint,
+                RetryOperation::Send,
+            )
             .await;
 
         // Send messages is always successful in order to process case when server received message
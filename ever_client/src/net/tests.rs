@@ -3,7 +3,7 @@ use tokio::sync::Mutex;
 use crate::abi::{CallSet, DeploySet, ParamsOfEncodeMessage, Signer};
 use crate::error::ClientError;
 use crate::processing::ParamsOfProcessMessage;
-use crate::tests::{TestClient, HELLO};
+use crate::tests::{TestClient, EVENTS_OLD, HELLO};
 
 use super::*;
 use crate::client::NetworkMock;
@@ -92,6 +92,7 @@ async fn not_authorized_response_text() {
                 is_batch: false,
             },
             None,
+            RetryOperation::Query,
         )
         .await;
     if let Err(err) = result {
@@ -121,6 +122,7 @@ async fn not_authorized() {
                 is_batch: false,
             },
             None,
+            RetryOperation::Query,
         )
         .await
         .unwrap();
@@ -155,6 +157,83 @@ async fn auth_header() {
     assert_eq!(None, client.context().config.network.get_auth_header());
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn extra_headers() {
+    let client = TestClient::new_with_config(json!({
+        "network": {
+            "headers": {
+                "X-Custom-Header": "custom",
+                "X-Number": "1",
+            }
+        }
+    }));
+    let mut headers = client.context().config.network.get_extra_headers();
+    headers.sort();
+    assert_eq!(
+        headers,
+        vec![
+            ("X-Custom-Header".to_string(), "custom".to_string()),
+            ("X-Number".to_string(), "1".to_string()),
+        ]
+    );
+    let client = TestClient::new();
+    assert!(client.context().config.network.get_extra_headers().is_empty());
+
+    let config: Result<NetworkConfig, _> = serde_json::from_value(json!({
+        "headers": { "X-Number": 1 }
+    }));
+    assert!(config.is_err());
+}
+
+#[test]
+fn retry_policy() {
+    let policy: RetryPolicy = serde_json::from_value(json!({
+        "max_attempts": 3,
+        "base_delay": 100,
+        "max_delay": 1000,
+        "retryable_error_codes": [507],
+    }))
+    .unwrap();
+    assert_eq!(policy.delay(0), 100);
+    assert_eq!(policy.delay(1), 200);
+    assert_eq!(policy.delay(3), 800);
+    assert_eq!(policy.delay(4), 1000);
+    assert_eq!(policy.delay(100), 1000);
+
+    assert!(policy.can_retry(0, 10, 0));
+    assert!(policy.can_retry(1, 10, 0));
+    assert!(!policy.can_retry(2, 0, 10));
+
+    assert!(policy.is_retryable_code(507));
+    assert!(!policy.is_retryable_code(508));
+
+    let policy = RetryPolicy {
+        jitter: 0.5,
+        ..Default::default()
+    };
+    for _ in 0..10 {
+        let delay = policy.delay(0);
+        assert!(delay >= 250 && delay <= 500);
+    }
+    assert!(policy.can_retry(100, 0, 10));
+    assert!(!policy.can_retry(0, 10, 10));
+
+    let config: NetworkConfig = serde_json::from_value(json!({
+        "retry_policies": {
+            "queries": { "max_attempts": 1 },
+        }
+    }))
+    .unwrap();
+    assert_eq!(
+        config
+            .retry_policy(RetryOperation::Query)
+            .and_then(|policy| policy.max_attempts),
+        Some(1)
+    );
+    assert!(config.retry_policy(RetryOperation::Send).is_none());
+    assert!(config.retry_policy(RetryOperation::Resend).is_none());
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn endpoints_with_graphql_suffix() {
     let url = TestClient::endpoints()[0].clone();
@@ -462,6 +541,41 @@ async fn message_sending_addresses() {
     assert!(a_good && e_good)
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn message_sending_strategy_addresses() {
+    let client = ClientContext::new(ClientConfig {
+        network: NetworkConfig {
+            endpoints: Some(vec!["a".into(), "b".into(), "c".into()]),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .unwrap();
+    let state = client.get_server_link().unwrap().state();
+    for _ in 0..3 {
+        assert_eq!(
+            state
+                .get_addresses_for_strategy(MessageSendingStrategy::PrimaryWithFailover)
+                .await,
+            vec!["a", "b", "c"]
+        );
+    }
+    let mut first = Vec::new();
+    for _ in 0..4 {
+        let addresses = state
+            .get_addresses_for_strategy(MessageSendingStrategy::RoundRobin)
+            .await;
+        assert_eq!(addresses.len(), 3);
+        first.push(addresses[0].clone());
+    }
+    assert_eq!(first, vec!["a", "b", "c", "a"]);
+    let mut broadcast = state
+        .get_addresses_for_strategy(MessageSendingStrategy::Broadcast)
+        .await;
+    broadcast.sort();
+    assert_eq!(broadcast, vec!["a", "b", "c"]);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn subscribe_for_transactions_with_addresses() {
     let client =  TestClient::new();
@@ -605,6 +719,7 @@ async fn subscribe_for_transactions_with_addresses() {
             ParamsOfProcessMessage {
                 message_encode_params: deploy_params,
                 send_events: false,
+                ..Default::default()
             },
             TestClient::default_callback,
         )
@@ -643,6 +758,7 @@ async fn subscribe_for_transactions_with_addresses() {
                     ..Default::default()
                 },
                 send_events: false,
+                ..Default::default()
             },
             TestClient::default_callback,
         )
@@ -731,6 +847,263 @@ async fn subscribe_for_messages() {
         .unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn subscribe_events() {
+    let client = TestClient::new();
+    let (abi, tvc) = TestClient::package(EVENTS_OLD, Some(2));
+    let keys = client.generate_sign_keys();
+    let address = client
+        .deploy_with_giver_async(
+            ParamsOfEncodeMessage {
+                abi: abi.clone(),
+                deploy_set: DeploySet::some_with_tvc(tvc),
+                call_set: CallSet::some_with_function("constructor"),
+                signer: Signer::Keys { keys: keys.clone() },
+                ..Default::default()
+            },
+            None,
+        )
+        .await;
+
+    let subscribe = |events: Arc<Mutex<Vec<ContractEvent>>>| {
+        move |result: Value, response_type: SubscriptionResponseType| {
+            let events = events.clone();
+            async move {
+                if let SubscriptionResponseType::Ok = response_type {
+                    let event = serde_json::from_value::<ContractEvent>(result).unwrap();
+                    events.lock().await.push(event);
+                }
+            }
+        }
+    };
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let handle: ResultOfSubscribeCollection = client
+        .request_async_callback(
+            "net.subscribe_events",
+            ParamsOfSubscribeEvents {
+                addresses: vec![address.clone()],
+                abi: abi.clone(),
+                event_names: Some(vec!["EventThrown".to_owned()]),
+                from_lt: None,
+            },
+            subscribe(events.clone()),
+        )
+        .await
+        .unwrap();
+
+    let output = client
+        .net_process_message(
+            ParamsOfProcessMessage {
+                message_encode_params: ParamsOfEncodeMessage {
+                    abi: abi.clone(),
+                    address: Some(address.clone()),
+                    call_set: CallSet::some_with_function_and_input(
+                        "returnValue",
+                        json!({ "id": "0x1" }),
+                    ),
+                    signer: Signer::Keys { keys },
+                    ..Default::default()
+                },
+                send_events: false,
+                ..Default::default()
+            },
+            TestClient::default_callback,
+        )
+        .await
+        .unwrap();
+
+    // give some time for subscription to receive all data
+    tokio::time::sleep(std::time::Duration::from_millis(2000)).await;
+
+    let transaction_id = output.transaction["id"].as_str().map(|id| id.to_string());
+    let lt = {
+        let events = events.lock().await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event.name, "EventThrown");
+        assert_eq!(events[0].address, address);
+        assert_eq!(events[0].transaction_id, transaction_id);
+        events[0].lt.clone()
+    };
+
+    let _: () = client
+        .request_async("net.unsubscribe", handle)
+        .await
+        .unwrap();
+
+    // already produced events are delivered when resuming from the lt
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let from_lt = (lt.parse::<u64>().unwrap() - 1).to_string();
+    let handle: ResultOfSubscribeCollection = client
+        .request_async_callback(
+            "net.subscribe_events",
+            ParamsOfSubscribeEvents {
+                addresses: vec![address.clone()],
+                abi: abi.clone(),
+                event_names: None,
+                from_lt: Some(from_lt),
+            },
+            subscribe(events.clone()),
+        )
+        .await
+        .unwrap();
+
+    tokio::time::sleep(std::time::Duration::from_millis(2000)).await;
+
+    {
+        let events = events.lock().await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].lt, lt);
+        assert_eq!(events[0].transaction_id, transaction_id);
+    }
+
+    let _: () = client
+        .request_async("net.unsubscribe", handle)
+        .await
+        .unwrap();
+
+    let error = client
+        .request_async_callback::<_, ResultOfSubscribeCollection, _, _, _>(
+            "net.subscribe_events",
+            ParamsOfSubscribeEvents {
+                addresses: vec![address],
+                abi,
+                event_names: None,
+                from_lt: Some("not_a_number".to_owned()),
+            },
+            subscribe(Arc::new(Mutex::new(Vec::new()))),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidEventsFilter as u32);
+    assert_eq!(error.data["from_lt"], "not_a_number");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn subscribe_account() {
+    let client = TestClient::new();
+    let keys = client.generate_sign_keys();
+    let abi = TestClient::abi(HELLO, None);
+    let address = client
+        .deploy_with_giver_async(
+            ParamsOfEncodeMessage {
+                abi: abi.clone(),
+                deploy_set: Some(DeploySet {
+                    tvc: TestClient::tvc(HELLO, None),
+                    ..Default::default()
+                }),
+                call_set: CallSet::some_with_function("constructor"),
+                signer: Signer::Keys { keys: keys.clone() },
+                ..Default::default()
+            },
+            None,
+        )
+        .await;
+
+    let states = Arc::new(Mutex::new(Vec::new()));
+    let states_copy = states.clone();
+    let callback = move |result: Value, response_type: SubscriptionResponseType| {
+        let states = states_copy.clone();
+        async move {
+            if let SubscriptionResponseType::Ok = response_type {
+                let state = serde_json::from_value::<AccountStateChange>(result).unwrap();
+                states.lock().await.push(state);
+            }
+        }
+    };
+
+    let handle: ResultOfSubscribeCollection = client
+        .request_async_callback(
+            "net.subscribe_account",
+            ParamsOfSubscribeAccount {
+                address: address.clone(),
+                result: Some("balance(format: DEC) last_trans_lt".to_owned()),
+                abi: None,
+            },
+            callback,
+        )
+        .await
+        .unwrap();
+
+    // give some time to deliver the current state
+    tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
+    assert_eq!(states.lock().await.len(), 1);
+
+    client
+        .net_process_message(
+            ParamsOfProcessMessage {
+                message_encode_params: ParamsOfEncodeMessage {
+                    abi,
+                    signer: Signer::Keys { keys },
+                    address: Some(address.clone()),
+                    call_set: CallSet::some_with_function("touch"),
+                    ..Default::default()
+                },
+                send_events: false,
+                ..Default::default()
+            },
+            TestClient::default_callback,
+        )
+        .await
+        .unwrap();
+
+    tokio::time::sleep(std::time::Duration::from_millis(2000)).await;
+
+    {
+        let states = states.lock().await;
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[1].address, address);
+        assert!(
+            states[1].last_trans_lt.parse::<u64>().unwrap()
+                > states[0].last_trans_lt.parse::<u64>().unwrap()
+        );
+        assert!(states[1].account["balance"].is_string());
+        // requested fields are returned as is, the internal ones are not exposed
+        assert!(states[1].account["last_trans_lt"]
+            .as_str()
+            .unwrap()
+            .starts_with("0x"));
+        assert_eq!(states[1].account.as_object().unwrap().len(), 2);
+        assert!(states[1].decoded_data.is_none());
+    }
+
+    let _: () = client
+        .request_async("net.unsubscribe", handle)
+        .await
+        .unwrap();
+}
+
+#[test]
+fn account_data_changes() {
+    let mut changes = Vec::new();
+    account_subscription::diff_values(
+        "",
+        &json!({ "owner": "1", "counter": 1, "map": { "a/b": 1, "c": 2 } }),
+        &json!({ "owner": "1", "counter": 2, "map": { "c": 2, "d": 3 } }),
+        &mut changes,
+    );
+    assert_eq!(
+        changes,
+        vec![
+            AccountDataChange {
+                path: "/counter".to_owned(),
+                before: Some(json!(1)),
+                after: Some(json!(2)),
+            },
+            AccountDataChange {
+                path: "/map/a~1b".to_owned(),
+                before: Some(json!(1)),
+                after: None,
+            },
+            AccountDataChange {
+                path: "/map/d".to_owned(),
+                before: None,
+                after: Some(json!(3)),
+            },
+        ]
+    );
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn find_last_shard_block() {
     let client = TestClient::new();
@@ -1462,6 +1835,87 @@ async fn transaction_tree() {
     assert_eq!(result.transactions.len(), 2);
 }
 
+#[test]
+fn render_transaction_tree() {
+    let client = TestClient::new();
+    let address_a = format!("0:{}", "a".repeat(64));
+    let address_b = format!("0:{}", "b".repeat(64));
+    let message = |id: &str, src: Option<&str>, dst: &str, value: &str, bounce: bool| {
+        MessageNode {
+            id: id.to_owned(),
+            src: src.map(|x| x.to_owned()),
+            dst: Some(dst.to_owned()),
+            value: Some(value.to_owned()),
+            bounce,
+            ..Default::default()
+        }
+    };
+    let transaction = |id: &str, in_msg: &str, account: &str, fees: &str, out_msgs: Vec<&str>| {
+        TransactionNode {
+            id: id.to_owned(),
+            in_msg: in_msg.to_owned(),
+            out_msgs: out_msgs.into_iter().map(|x| x.to_owned()).collect(),
+            account_addr: account.to_owned(),
+            total_fees: fees.to_owned(),
+            aborted: false,
+            exit_code: Some(0),
+        }
+    };
+    let mut aborted = transaction("t2", "m2", &address_b, "0x5", vec!["m3"]);
+    aborted.aborted = true;
+    aborted.exit_code = Some(101);
+
+    let result: ResultOfRenderTransactionTree = client
+        .request(
+            "net.render_transaction_tree",
+            ParamsOfRenderTransactionTree {
+                in_msg: "m1".to_owned(),
+                messages: vec![
+                    message("m1", None, &address_a, "0x0", false),
+                    message("m2", Some(&address_a), &address_b, "0x64", true),
+                    message("m3", Some(&address_b), &address_a, "0x5a", false),
+                ],
+                transactions: vec![
+                    transaction("t1", "m1", &address_a, "0x10", vec!["m2", "m4"]),
+                    aborted,
+                    transaction("t3", "m3", &address_a, "0x1", vec![]),
+                ],
+            },
+        )
+        .unwrap();
+
+    assert_eq!(result.tree.subtree_fees, "22");
+    assert_eq!(result.tree.children.len(), 1);
+    assert_eq!(result.tree.children[0].subtree_fees, "6");
+    assert!(result.tree.children[0].aborted);
+    assert!(result.tree.children[0].children[0].bounced);
+    assert_eq!(result.summary.transaction_count, 3);
+    assert_eq!(result.summary.message_count, 3);
+    assert_eq!(result.summary.total_fees, "22");
+    assert_eq!(result.summary.bounced_value, "90");
+    assert_eq!(result.summary.bounced_messages, vec!["m3"]);
+    assert_eq!(result.summary.missing_messages, vec!["m4"]);
+    assert_eq!(result.summary.aborted.len(), 1);
+    assert_eq!(result.summary.aborted[0].exit_code, Some(101));
+    assert!(result.dot.starts_with("digraph transaction_tree {"));
+    assert!(result.dot.contains("aborted, exit code 101"));
+    assert!(result.mermaid.starts_with("graph TD"));
+    assert!(result.mermaid.contains("class n1 aborted"));
+
+    let error = client
+        .request::<_, ResultOfRenderTransactionTree>(
+            "net.render_transaction_tree",
+            ParamsOfRenderTransactionTree {
+                in_msg: "m0".to_owned(),
+                messages: vec![message("m1", None, &address_a, "0x0", false)],
+                transactions: vec![],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidTransactionTree as u32);
+    assert_eq!(error.data["in_msg"], "m0");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn order_by_fallback() {
     let params: ParamsOfQueryCollection = serde_json::from_str(
@@ -101,8 +101,7 @@ pub struct MessageNode {
 impl MessageNode {
     fn from(
         value: &Value,
-        client: &Arc<ClientContext>,
-        abi_registry: &Option<Vec<Abi>>,
+        decoder: Option<(&Arc<ClientContext>, &Vec<Abi>)>,
         src_transactions: &HashMap<String, Option<String>>,
     ) -> ClientResult<Self> {
         let id = required_string(value, "id")?;
@@ -115,16 +114,15 @@ impl MessageNode {
             dst: get_string(value, "dst"),
             value: get_string(value, "value"),
             bounce: value["bounce"].as_bool().unwrap_or(false),
-            decoded_body: Self::try_decode_body(value, client, abi_registry),
+            decoded_body: Self::try_decode_body(value, decoder),
         })
     }
 
     fn try_decode_body(
         message: &Value,
-        client: &Arc<ClientContext>,
-        abi_registry: &Option<Vec<Abi>>,
+        decoder: Option<(&Arc<ClientContext>, &Vec<Abi>)>,
     ) -> Option<DecodedMessageBody> {
-        if let Some(abi_registry) = abi_registry {
+        if let Some((client, abi_registry)) = decoder {
             if !abi_registry.is_empty() {
                 if let Some(body) = message["body"].as_str() {
                     let is_internal = message["msg_type"].as_u64().unwrap_or(0) == 0;
@@ -309,22 +307,40 @@ pub async fn query_transaction_tree(
     context: Arc<ClientContext>,
     params: ParamsOfQueryTransactionTree,
 ) -> ClientResult<ResultOfQueryTransactionTree> {
-    let server_link = context.get_server_link()?;
-    let mut transaction_nodes = Vec::new();
-    let mut message_nodes = Vec::new();
-    let mut query_queue: Vec<(Option<String>, String)> = vec![(None, params.in_msg.clone())];
     let timeout = params.timeout.unwrap_or(DEFAULT_WAITING_TIMEOUT);
     let transaction_max_count = params
         .transaction_max_count
         .unwrap_or(DEFAULT_TRANSACTION_MAX_COUNT) as usize;
+    query_transaction_tree_nodes(
+        context.get_server_link()?,
+        &params.in_msg,
+        timeout,
+        transaction_max_count,
+        params.abi_registry.as_ref().map(|abi| (&context, abi)),
+    )
+    .await
+}
+
+/// Retrieves a tree of transactions triggered by a specific message.
+///
+/// Message bodies are decoded only if the `decoder` is specified.
+pub(crate) async fn query_transaction_tree_nodes(
+    server_link: &ServerLink,
+    in_msg: &str,
+    timeout: u32,
+    transaction_max_count: usize,
+    decoder: Option<(&Arc<ClientContext>, &Vec<Abi>)>,
+) -> ClientResult<ResultOfQueryTransactionTree> {
+    let mut transaction_nodes = Vec::new();
+    let mut message_nodes = Vec::new();
+    let mut query_queue: Vec<(Option<String>, String)> = vec![(None, in_msg.to_string())];
     while !query_queue.is_empty()
         && (transaction_max_count == 0 || transaction_nodes.len() < transaction_max_count)
     {
         let (messages, src_transactions) =
             query_next_portion(server_link, timeout, &mut query_queue).await?;
         for message in messages {
-            let message_node =
-                MessageNode::from(&message, &context, &params.abi_registry, &src_transactions)?;
+            let message_node = MessageNode::from(&message, decoder, &src_transactions)?;
             let transaction = &message["dst_transaction"];
             if transaction.is_object() {
                 let transaction_node = TransactionNode::from(&transaction, &message_node)?;
@@ -0,0 +1,403 @@
+/*
+* Copyright 2018-2021 EverX Labs Ltd.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific EVERX DEV software governing permissions and
+* limitations under the License.
+*/
+
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use crate::net::{MessageNode, TransactionNode};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::sync::Arc;
+
+//------------------------------------------------------------------------- render_transaction_tree
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone)]
+pub struct ParamsOfRenderTransactionTree {
+    /// Id of the root message of the tree.
+    pub in_msg: String,
+
+    /// Messages returned by `query_transaction_tree`.
+    pub messages: Vec<MessageNode>,
+
+    /// Transactions returned by `query_transaction_tree`.
+    pub transactions: Vec<TransactionNode>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone, Debug, PartialEq)]
+pub struct TransactionTreeNode {
+    /// Message id.
+    pub message_id: String,
+
+    /// Source address. Missing for an external inbound message.
+    pub src: Option<String>,
+
+    /// Destination address. Missing for an external outbound message.
+    pub dst: Option<String>,
+
+    /// Transferred value in nanotokens (decimal string).
+    pub value: String,
+
+    /// Decoded function or event name of the message body.
+    pub function_name: Option<String>,
+
+    /// Message is a bounced part of an aborted transaction inbound value.
+    pub bounced: bool,
+
+    /// Destination transaction id. Missing if the message has not been processed.
+    pub transaction_id: Option<String>,
+
+    /// Destination transaction aborted flag.
+    pub aborted: bool,
+
+    /// Destination transaction compute phase exit code.
+    pub exit_code: Option<u32>,
+
+    /// Destination transaction total fees in nanotokens (decimal string).
+    pub fees: String,
+
+    /// Total fees of the destination transaction and all the transactions
+    /// of the subtree in nanotokens (decimal string).
+    pub subtree_fees: String,
+
+    /// Nodes of the messages produced by the destination transaction.
+    pub children: Vec<TransactionTreeNode>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone, Debug, PartialEq)]
+pub struct AbortedTransactionInfo {
+    /// Transaction id.
+    pub transaction_id: String,
+
+    /// Account address.
+    pub account_addr: String,
+
+    /// Inbound message id.
+    pub in_msg: String,
+
+    /// Decoded function name of the inbound message.
+    pub function_name: Option<String>,
+
+    /// Compute phase exit code.
+    pub exit_code: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone, Debug, PartialEq)]
+pub struct TransactionTreeSummary {
+    /// Number of the transactions in the tree.
+    pub transaction_count: u32,
+
+    /// Number of the messages in the tree.
+    pub message_count: u32,
+
+    /// Total fees of all the transactions in nanotokens (decimal string).
+    pub total_fees: String,
+
+    /// Aborted transactions.
+    pub aborted: Vec<AbortedTransactionInfo>,
+
+    /// Total bounced value in nanotokens (decimal string).
+    pub bounced_value: String,
+
+    /// Ids of the bounced messages.
+    pub bounced_messages: Vec<String>,
+
+    /// Ids of the out messages which are missing in the provided messages.
+    ///
+    /// Non empty list means that the tree is incomplete.
+    pub missing_messages: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone, Debug)]
+pub struct ResultOfRenderTransactionTree {
+    /// Tree in Graphviz DOT format.
+    pub dot: String,
+
+    /// Tree in Mermaid flowchart format.
+    pub mermaid: String,
+
+    /// Nested tree starting from the root message.
+    pub tree: TransactionTreeNode,
+
+    /// Tree summary.
+    pub summary: TransactionTreeSummary,
+}
+
+fn parse_tokens(value: &str) -> ClientResult<u128> {
+    let result = if let Some(hex) = value.strip_prefix("0x") {
+        u128::from_str_radix(hex, 16)
+    } else if value.is_empty() {
+        Ok(0)
+    } else {
+        value.parse()
+    };
+    result.map_err(|err| crate::net::Error::invalid_transaction_tree_tokens(value, err))
+}
+
+fn short_address(address: &str) -> String {
+    match address.find(':') {
+        Some(pos) if address.len() > pos + 11 => format!(
+            "{}{}..{}",
+            &address[..pos + 1],
+            &address[pos + 1..pos + 7],
+            &address[address.len() - 4..]
+        ),
+        _ => address.to_string(),
+    }
+}
+
+struct TreeBuilder<'a> {
+    messages: HashMap<&'a str, &'a MessageNode>,
+    transactions: HashMap<&'a str, &'a TransactionNode>,
+    visited: HashSet<&'a str>,
+    summary: TransactionTreeSummary,
+    total_fees: u128,
+    bounced_value: u128,
+}
+
+impl<'a> TreeBuilder<'a> {
+    fn new(params: &'a ParamsOfRenderTransactionTree) -> Self {
+        Self {
+            messages: params.messages.iter().map(|x| (x.id.as_str(), x)).collect(),
+            transactions: params
+                .transactions
+                .iter()
+                .map(|x| (x.in_msg.as_str(), x))
+                .collect(),
+            visited: HashSet::new(),
+            summary: TransactionTreeSummary::default(),
+            total_fees: 0,
+            bounced_value: 0,
+        }
+    }
+
+    fn build(&mut self, message_id: &'a str, bounced: bool) -> ClientResult<TransactionTreeNode> {
+        self.visited.insert(message_id);
+        self.summary.message_count += 1;
+        let message = self.messages[message_id];
+        let function_name = message.decoded_body.as_ref().map(|x| x.name.clone());
+        let value = parse_tokens(message.value.as_deref().unwrap_or_default())?;
+        if bounced {
+            self.bounced_value += value;
+            self.summary.bounced_messages.push(message.id.clone());
+        }
+        let mut node = TransactionTreeNode {
+            message_id: message.id.clone(),
+            src: message.src.clone(),
+            dst: message.dst.clone(),
+            value: value.to_string(),
+            function_name: function_name.clone(),
+            bounced,
+            fees: "0".to_string(),
+            subtree_fees: "0".to_string(),
+            ..Default::default()
+        };
+        let transaction = match self.transactions.get(message_id) {
+            Some(transaction) => *transaction,
+            None => return Ok(node),
+        };
+        self.summary.transaction_count += 1;
+        let fees = parse_tokens(&transaction.total_fees)?;
+        self.total_fees += fees;
+        if transaction.aborted {
+            self.summary.aborted.push(AbortedTransactionInfo {
+                transaction_id: transaction.id.clone(),
+                account_addr: transaction.account_addr.clone(),
+                in_msg: message.id.clone(),
+                function_name,
+                exit_code: transaction.exit_code,
+            });
+        }
+        let mut subtree_fees = fees;
+        for out_msg in &transaction.out_msgs {
+            let out_message = match self.messages.get(out_msg.as_str()) {
+                Some(out_message) => *out_message,
+                None => {
+                    self.summary.missing_messages.push(out_msg.clone());
+                    continue;
+                }
+            };
+            if self.visited.contains(out_message.id.as_str()) {
+                continue;
+            }
+            // aborted transaction returns the bounceable inbound value to the sender
+            let bounced = transaction.aborted
+                && message.bounce
+                && message.src.is_some()
+                && out_message.dst == message.src;
+            let child = self.build(out_message.id.as_str(), bounced)?;
+            subtree_fees += parse_tokens(&child.subtree_fees)?;
+            node.children.push(child);
+        }
+        node.transaction_id = Some(transaction.id.clone());
+        node.aborted = transaction.aborted;
+        node.exit_code = transaction.exit_code;
+        node.fees = fees.to_string();
+        node.subtree_fees = subtree_fees.to_string();
+        Ok(node)
+    }
+}
+
+fn node_label(node: &TransactionTreeNode) -> Vec<String> {
+    let mut lines = Vec::new();
+    match &node.dst {
+        Some(dst) => lines.push(short_address(dst)),
+        None => lines.push("external".to_string()),
+    }
+    if node.transaction_id.is_some() {
+        if node.aborted {
+            lines.push(format!(
+                "aborted, exit code {}",
+                node.exit_code
+                    .map(|x| x.to_string())
+                    .unwrap_or_else(|| "-".to_string())
+            ));
+        }
+        lines.push(format!("fees: {}", node.fees));
+        lines.push(format!("subtree fees: {}", node.subtree_fees));
+    }
+    lines
+}
+
+fn edge_label(node: &TransactionTreeNode) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(name) = &node.function_name {
+        lines.push(name.clone());
+    }
+    lines.push(format!("value: {}", node.value));
+    if node.bounced {
+        lines.push("bounced".to_string());
+    }
+    lines
+}
+
+fn dot_escape(lines: Vec<String>) -> String {
+    lines
+        .iter()
+        .map(|x| x.replace('\\', "\\\\").replace('"', "\\\""))
+        .collect::<Vec<String>>()
+        .join("\\n")
+}
+
+fn mermaid_escape(lines: Vec<String>) -> String {
+    lines
+        .iter()
+        .map(|x| x.replace('"', "#quot;"))
+        .collect::<Vec<String>>()
+        .join("<br/>")
+}
+
+fn render_dot(node: &TransactionTreeNode, parent: &str, index: &mut usize, out: &mut String) {
+    let id = format!("n{}", index);
+    *index += 1;
+    let style = if node.aborted {
+        ", color=red"
+    } else if node.transaction_id.is_none() {
+        ", style=dashed"
+    } else {
+        ""
+    };
+    let _ = writeln!(
+        out,
+        "    {} [label=\"{}\"{}];",
+        id,
+        dot_escape(node_label(node)),
+        style
+    );
+    let edge_style = if node.bounced { ", style=dashed" } else { "" };
+    let _ = writeln!(
+        out,
+        "    {} -> {} [label=\"{}\"{}];",
+        parent,
+        id,
+        dot_escape(edge_label(node)),
+        edge_style
+    );
+    for child in &node.children {
+        render_dot(child, &id, index, out);
+    }
+}
+
+fn render_mermaid(node: &TransactionTreeNode, parent: &str, index: &mut usize, out: &mut String) {
+    let id = format!("n{}", index);
+    *index += 1;
+    let _ = writeln!(out, "    {}[\"{}\"]", id, mermaid_escape(node_label(node)));
+    let arrow = if node.bounced { "-.->" } else { "-->" };
+    let _ = writeln!(
+        out,
+        "    {} {}|\"{}\"| {}",
+        parent,
+        arrow,
+        mermaid_escape(edge_label(node)),
+        id
+    );
+    if node.aborted {
+        let _ = writeln!(out, "    class {} aborted", id);
+    }
+    for child in &node.children {
+        render_mermaid(child, &id, index, out);
+    }
+}
+
+fn root_label(tree: &TransactionTreeNode) -> String {
+    tree.src
+        .as_ref()
+        .map(|x| short_address(x))
+        .unwrap_or_else(|| "external".to_string())
+}
+
+/// Renders a transaction tree returned by `query_transaction_tree`.
+///
+/// Produces the tree in Graphviz DOT format, as a Mermaid flowchart and as a nested
+/// JSON structure starting from the root message. Each node contains the decoded function
+/// name (if the messages were decoded with `abi_registry`), transferred value, transaction
+/// fees and total fees of the subtree.
+///
+/// The summary contains aborted transactions, bounced value and the list of messages
+/// referenced by the transactions but missing in the provided messages.
+#[api_function]
+pub fn render_transaction_tree(
+    _context: Arc<ClientContext>,
+    params: ParamsOfRenderTransactionTree,
+) -> ClientResult<ResultOfRenderTransactionTree> {
+    let mut builder = TreeBuilder::new(&params);
+    if !builder.messages.contains_key(params.in_msg.as_str()) {
+        return Err(crate::net::Error::transaction_tree_root_missing(&params.in_msg));
+    }
+    let tree = builder.build(&params.in_msg, false)?;
+    let mut summary = builder.summary;
+    summary.total_fees = builder.total_fees.to_string();
+    summary.bounced_value = builder.bounced_value.to_string();
+
+    let mut dot = String::from("digraph transaction_tree {\n    node [shape=box];\n");
+    let _ = writeln!(
+        dot,
+        "    root [label=\"{}\", shape=ellipse];",
+        dot_escape(vec![root_label(&tree)])
+    );
+    render_dot(&tree, "root", &mut 0, &mut dot);
+    dot.push_str("}\n");
+
+    let mut mermaid = String::from("graph TD\n    classDef aborted fill:#fdd,stroke:#c00\n");
+    let _ = writeln!(
+        mermaid,
+        "    root([\"{}\"])",
+        mermaid_escape(vec![root_label(&tree)])
+    );
+    render_mermaid(&tree, "root", &mut 0, &mut mermaid);
+
+    Ok(ResultOfRenderTransactionTree {
+        dot,
+        mermaid,
+        tree,
+        summary,
+    })
+}
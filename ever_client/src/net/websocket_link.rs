@@ -18,7 +18,7 @@ use crate::net::endpoint::Endpoint;
 use crate::net::gql::{GraphQLMessageFromClient, GraphQLMessageFromServer};
 use crate::net::server_link::NetworkState;
 use crate::net::ton_gql::{GraphQLQuery, GraphQLQueryEvent};
-use crate::net::{Error, NetworkConfig};
+use crate::net::{Error, NetworkConfig, RetryOperation};
 use futures::stream::{Fuse, FusedStream};
 use futures::Sink;
 use futures::{SinkExt, StreamExt};
@@ -145,6 +145,9 @@ pub(crate) struct LinkHandler {
     last_operation_id: u32,
     operations: HashMap<u32, RunningOperation>,
     keep_alive: KeepAlive,
+    reconnect_index: u32,
+    // time of the first failed attempt of the current reconnect series
+    reconnect_start: Option<u64>,
     state: Arc<NetworkState>,
     config: NetworkConfig,
 }
@@ -180,6 +183,8 @@ impl LinkHandler {
                 last_operation_id: 0,
                 operations: HashMap::new(),
                 keep_alive: KeepAlive::WaitFirst,
+                reconnect_index: 0,
+                reconnect_start: None,
                 state,
                 config,
             }
@@ -430,6 +435,7 @@ impl LinkHandler {
                     };
                 } else {
                     self.state.reset_resume_timeout();
+                    self.reset_reconnect();
                     self.notify_with_remove(false, &id, GraphQLQueryEvent::Data(data))
                         .await;
                 };
@@ -500,23 +506,41 @@ impl LinkHandler {
         }
     }
 
+    // Without the subscriptions retry policy the single endpoint is reconnected endlessly
+    fn can_reconnect(&mut self) -> bool {
+        if self.state.retry_policy(RetryOperation::Subscription).is_none() {
+            return true;
+        }
+        let now = self.client_env.now_ms();
+        let start = *self.reconnect_start.get_or_insert(now);
+        self.state
+            .can_retry(RetryOperation::Subscription, self.reconnect_index, start)
+    }
+
+    fn reset_reconnect(&mut self) {
+        self.reconnect_index = 0;
+        self.reconnect_start = None;
+    }
+
     async fn handle_network_error(&mut self, err: ClientError, suspended: bool) -> Phase {
         self.send_error_to_running_operations(err.add_network_url_from_state(&self.state).await)
             .await;
         if !suspended {
-            if !self.state.has_multiple_endpoints() {
+            if !self.state.has_multiple_endpoints() && self.can_reconnect() {
                 HandlerAction::Resume
                     .send(&mut self.internal_action_sender.clone())
                     .await;
-                let _ = self
-                    .client_env
-                    .set_timer(self.state.next_resume_timeout() as u64)
-                    .await;
+                let timeout = self
+                    .state
+                    .next_retry_timeout(RetryOperation::Subscription, self.reconnect_index);
+                self.reconnect_index = self.reconnect_index.saturating_add(1);
+                let _ = self.client_env.set_timer(timeout as u64).await;
                 return Phase::Reconnecting;
             }
             self.send_error_to_running_operations(Error::network_module_suspended())
                 .await;
         }
+        self.reset_reconnect();
         self.state.internal_suspend().await;
 
         // send resume - it will try to reconnect after internal suspend timer in NetworkState ends
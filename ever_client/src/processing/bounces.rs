@@ -0,0 +1,189 @@
+/*
+ * Copyright 2018-2021 EverX Labs Ltd.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ *
+ */
+
+use crate::abi::Abi;
+use crate::boc::internal::{deserialize_cell_from_boc, deserialize_object_from_boc};
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use crate::net::{ParamsOfWaitForCollection, TRANSACTIONS_COLLECTION};
+use ever_block::{Message, SliceData};
+use serde_json::Value;
+use std::sync::Arc;
+
+const DEFAULT_BOUNCE_WAITING_TIMEOUT: u32 = 40000;
+const BOUNCE_BODY_PREFIX: u32 = 0xFFFFFFFF;
+const BOUNCE_TRANSACTION_FIELDS: &str = r#"
+    id aborted compute { exit_code }
+    out_messages { id bounced value(format: DEC) body }
+"#;
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct BounceTrackingOptions {
+    /// Maximum time in ms to wait for the transactions of the bounceable
+    /// out messages. Default is 40000 (40 sec).
+    pub timeout: Option<u32>,
+
+    /// ABIs used to find the called function by the function id from the bounced body.
+    ///
+    /// The ABI of the processed message is used if not specified.
+    pub abi_registry: Option<Vec<Abi>>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, PartialEq, Clone)]
+pub struct BouncedMessage {
+    /// Id of the out message that has been bounced.
+    pub message_id: String,
+
+    /// Destination address of the bounced message.
+    pub dst: String,
+
+    /// Id of the aborted transaction on the destination account.
+    pub transaction_id: String,
+
+    /// Compute phase exit code of the aborted transaction.
+    pub exit_code: Option<i32>,
+
+    /// Id of the bounce message returned to the sender.
+    ///
+    /// Missing if the value of the message was not enough to produce the bounce.
+    pub bounce_message_id: Option<String>,
+
+    /// Value returned with the bounce message in nanotokens (decimal string).
+    pub value: Option<String>,
+
+    /// Id of the called function taken from the bounced body. Encoded as `0x` prefixed hex.
+    pub function_id: Option<String>,
+
+    /// Name of the called function resolved with the ABI registry.
+    pub function_name: Option<String>,
+}
+
+struct BounceableMessage {
+    id: String,
+    dst: String,
+}
+
+/// Reads the function id from the bounced message body.
+///
+/// Bounced body starts with 32 bits `0xFFFFFFFF` followed by the first
+/// 256 bits of the original body.
+pub(crate) fn bounced_function_id(context: &ClientContext, body: &str) -> Option<u32> {
+    let (_, cell) = deserialize_cell_from_boc(context, body, "bounced message body").ok()?;
+    let mut slice = SliceData::load_cell(cell).ok()?;
+    if slice.get_next_u32().ok()? != BOUNCE_BODY_PREFIX {
+        return None;
+    }
+    slice.get_next_u32().ok()
+}
+
+pub(crate) fn resolve_function_name(abi_registry: &[Abi], function_id: u32) -> Option<String> {
+    abi_registry.iter().find_map(|abi| {
+        abi.abi()
+            .ok()?
+            .function_by_id(function_id, true)
+            .ok()
+            .map(|function| function.name.clone())
+    })
+}
+
+fn bounceable_messages(
+    context: &ClientContext,
+    out_messages: &[String],
+) -> ClientResult<Vec<BounceableMessage>> {
+    let mut messages = Vec::new();
+    for boc in out_messages {
+        let message = deserialize_object_from_boc::<Message>(context, boc, "message")?;
+        if let Some(header) = message.object.int_header() {
+            if header.bounce {
+                messages.push(BounceableMessage {
+                    id: message.cell.repr_hash().as_hex_string(),
+                    dst: header.dst.to_string(),
+                });
+            }
+        }
+    }
+    Ok(messages)
+}
+
+/// Waits for the transactions of the bounceable out messages and returns
+/// the messages which have been bounced.
+///
+/// Messages which transactions do not appear within the timeout are not reported.
+pub(crate) async fn find_bounced_messages(
+    context: &Arc<ClientContext>,
+    out_messages: &[String],
+    options: &BounceTrackingOptions,
+    abi: &Abi,
+) -> ClientResult<Vec<BouncedMessage>> {
+    let messages = bounceable_messages(context, out_messages)?;
+    if messages.is_empty() {
+        return Ok(Vec::new());
+    }
+    let abi_registry = options
+        .abi_registry
+        .clone()
+        .unwrap_or_else(|| vec![abi.clone()]);
+    let server_link = context.get_server_link()?;
+    let deadline =
+        context.env.now_ms() + options.timeout.unwrap_or(DEFAULT_BOUNCE_WAITING_TIMEOUT) as u64;
+
+    let mut bounced = Vec::new();
+    for message in messages {
+        let timeout = deadline.saturating_sub(context.env.now_ms()) as u32;
+        let transaction = server_link
+            .wait_for_collection(
+                ParamsOfWaitForCollection {
+                    collection: TRANSACTIONS_COLLECTION.to_string(),
+                    filter: Some(json!({ "in_msg": { "eq": message.id } })),
+                    result: BOUNCE_TRANSACTION_FIELDS.to_string(),
+                    timeout: Some(std::cmp::max(timeout, 1)),
+                },
+                None,
+            )
+            .await;
+        let transaction = match transaction {
+            Ok(transaction) => transaction,
+            Err(err) if err.code == crate::net::ErrorCode::WaitForTimeout as u32 => continue,
+            Err(err) => return Err(err),
+        };
+        if !transaction["aborted"].as_bool().unwrap_or(false) {
+            continue;
+        }
+        let bounce = transaction["out_messages"]
+            .as_array()
+            .and_then(|messages| {
+                messages
+                    .iter()
+                    .find(|message| message["bounced"].as_bool() == Some(true))
+            })
+            .cloned()
+            .unwrap_or(Value::Null);
+        let function_id = bounce["body"]
+            .as_str()
+            .and_then(|body| bounced_function_id(context, body));
+        bounced.push(BouncedMessage {
+            message_id: message.id,
+            dst: message.dst,
+            transaction_id: transaction["id"].as_str().unwrap_or_default().to_string(),
+            exit_code: transaction["compute"]["exit_code"]
+                .as_i64()
+                .map(|code| code as i32),
+            bounce_message_id: bounce["id"].as_str().map(|id| id.to_string()),
+            value: bounce["value"].as_str().map(|value| value.to_string()),
+            function_id: function_id.map(|id| format!("0x{:08x}", id)),
+            function_name: function_id.and_then(|id| resolve_function_name(&abi_registry, id)),
+        });
+    }
+    Ok(bounced)
+}
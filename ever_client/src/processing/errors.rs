@@ -34,6 +34,13 @@ pub enum ErrorCode {
     MessageRejected = 514,
     InvalidRempStatus = 515,
     NextRempStatusTimeout = 516,
+    PreflightTransactionAborted = 517,
+    PreflightLowBalance = 518,
+    PreflightFeesLimitExceeded = 519,
+    MonitorQueueRequired = 520,
+    InvalidPreflightMaxFees = 521,
+    InvalidSendScheduling = 522,
+    InvalidBroadcastWindow = 523,
 }
 
 pub struct Error;
@@ -233,4 +240,131 @@ impl Error {
     pub fn next_remp_status_timeout() -> ClientError {
         error(ErrorCode::NextRempStatusTimeout, format!("Next REMP status awaiting timeout"))
     }
+
+    pub fn preflight_transaction_aborted(
+        message_id: &str,
+        local_error: ClientError,
+    ) -> ClientError {
+        let mut error = Self::processing_error(
+            ErrorCode::PreflightTransactionAborted,
+            format!(
+                "Pre-flight check failed: transaction will be aborted: {}",
+                local_error.message
+            ),
+            message_id,
+            None,
+        );
+        error.data["local_error"] = serde_json::to_value(&local_error).unwrap_or_default();
+        error
+    }
+
+    pub fn preflight_low_balance(message_id: &str, balance: u64, required: u64) -> ClientError {
+        let mut error = Self::processing_error(
+            ErrorCode::PreflightLowBalance,
+            format!(
+                "Pre-flight check failed: account balance {} is less than required {}",
+                balance, required
+            ),
+            message_id,
+            None,
+        );
+        error.data["balance"] = balance.to_string().into();
+        error.data["required"] = required.to_string().into();
+        error
+    }
+
+    pub fn preflight_fees_limit_exceeded(
+        message_id: &str,
+        fees: u64,
+        max_fees: u64,
+    ) -> ClientError {
+        let mut error = Self::processing_error(
+            ErrorCode::PreflightFeesLimitExceeded,
+            format!(
+                "Pre-flight check failed: estimated fees {} exceed the limit {}",
+                fees, max_fees
+            ),
+            message_id,
+            None,
+        );
+        error.data["fees"] = fees.to_string().into();
+        error.data["max_fees"] = max_fees.to_string().into();
+        error
+    }
+
+    pub fn monitor_queue_required(option: &str) -> ClientError {
+        let mut error = error(
+            ErrorCode::MonitorQueueRequired,
+            format!(
+                "`{}` option requires `monitor_queue` to be specified",
+                option
+            ),
+        );
+        error.data = json!({ "option": option });
+        error
+    }
+
+    pub fn invalid_preflight_max_fees<E: std::fmt::Display>(max_fees: &str, err: E) -> ClientError {
+        let mut error = error(
+            ErrorCode::InvalidPreflightMaxFees,
+            format!("Invalid pre-flight `max_fees` value: {}", err),
+        );
+        error.data = json!({ "max_fees": max_fees });
+        error
+    }
+
+    pub fn scheduled_message_address_missing() -> ClientError {
+        error(
+            ErrorCode::InvalidSendScheduling,
+            "Scheduled message must have the destination address".to_owned(),
+        )
+    }
+
+    pub fn scheduled_message_time_specified(time: u64) -> ClientError {
+        let mut error = error(
+            ErrorCode::InvalidSendScheduling,
+            "`time` header of the scheduled message is assigned by the scheduler and can not be specified".to_owned(),
+        );
+        error.data = json!({ "time": time });
+        error
+    }
+
+    pub fn scheduled_message_seqno_missing() -> ClientError {
+        error(
+            ErrorCode::InvalidSendScheduling,
+            "`seqno` must be specified for the first scheduled message of the address".to_owned(),
+        )
+    }
+
+    pub fn scheduled_message_input_not_object() -> ClientError {
+        error(
+            ErrorCode::InvalidSendScheduling,
+            "Function input of the scheduled message must be an object to receive the sequence number".to_owned(),
+        )
+    }
+
+    pub fn scheduled_message_in_batch(index: usize) -> ClientError {
+        let mut error = error(
+            ErrorCode::InvalidSendScheduling,
+            "Scheduled sending is not supported by the batch processing".to_owned(),
+        );
+        error.data = json!({ "message_index": index });
+        error
+    }
+
+    pub fn empty_broadcast_window() -> ClientError {
+        error(
+            ErrorCode::InvalidBroadcastWindow,
+            "Broadcast window can not be empty".to_owned(),
+        )
+    }
+
+    pub fn broadcast_window_out_of_range(wait_until: u64) -> ClientError {
+        let mut error = error(
+            ErrorCode::InvalidBroadcastWindow,
+            "Broadcast window exceeds the `expire` header range".to_owned(),
+        );
+        error.data = json!({ "wait_until": wait_until });
+        error
+    }
 }
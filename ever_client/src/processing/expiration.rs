@@ -0,0 +1,177 @@
+/*
+ * Copyright 2018-2021 EverX Labs Ltd.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ *
+ */
+
+use crate::abi::Abi;
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use crate::net::{OrderBy, ParamsOfQueryCollection, SortDirection, BLOCKS_COLLECTION};
+use crate::processing::Error;
+use ever_block::MASTERCHAIN_ID;
+use std::sync::Arc;
+
+const DEFAULT_WAIT_UNTIL_MARGIN_MS: u32 = 15000;
+const DEFAULT_BLOCK_INTERVAL_MS: u32 = 5000;
+const BLOCK_RATE_SAMPLE_SIZE: u32 = 10;
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct ParamsOfPlanExpiration {
+    /// Delay in ms from the current network time till the start of the broadcast window.
+    /// Default is 0.
+    pub broadcast_delay: Option<u32>,
+
+    /// Duration of the broadcast window in ms.
+    ///
+    /// Message must be accepted by the network if it is sent at any moment of the window.
+    /// Default is `AbiConfig.message_expiration_timeout`.
+    pub broadcast_window: Option<u32>,
+
+    /// Margin in ms added to the `expire` to get the `wait_until`.
+    ///
+    /// Covers the difference between the network time and the time of the
+    /// blocks the message is checked against. Default is 15000.
+    pub wait_until_margin: Option<u32>,
+
+    /// Contract ABI.
+    ///
+    /// If specified then only the headers declared in the ABI are returned.
+    pub abi: Option<Abi>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct ResultOfPlanExpiration {
+    /// Recommended `time` header value in ms.
+    ///
+    /// Equals to the network time at the start of the broadcast window.
+    pub time: Option<u64>,
+
+    /// Recommended `expire` header value in seconds.
+    pub expire: Option<u32>,
+
+    /// Recommended `MessageSendingParams.wait_until` value in seconds.
+    pub wait_until: u32,
+
+    /// Current network time in ms.
+    pub network_time: u64,
+
+    /// Difference between the network time and the client time in ms.
+    pub clock_delta: i64,
+
+    /// Average interval between the recent masterchain blocks in ms.
+    pub block_interval: u32,
+}
+
+async fn query_block_interval(context: &Arc<ClientContext>) -> ClientResult<u32> {
+    let server_link = context.get_server_link()?;
+    let query_blocks = |filter: serde_json::Value| ParamsOfQueryCollection {
+        collection: BLOCKS_COLLECTION.to_string(),
+        filter: Some(filter),
+        result: "gen_utime".to_string(),
+        order: Some(vec![OrderBy {
+            path: "seq_no".to_owned(),
+            direction: SortDirection::DESC,
+        }]),
+        limit: Some(BLOCK_RATE_SAMPLE_SIZE),
+    };
+    let mut blocks = server_link
+        .query_collection(
+            query_blocks(json!({ "workchain_id": { "eq": MASTERCHAIN_ID } })),
+            None,
+        )
+        .await?;
+    if blocks.as_array().map_or(0, |blocks| blocks.len()) < 2 {
+        // Evernode SE case - there is no masterchain
+        blocks = server_link
+            .query_collection(query_blocks(json!({ "workchain_id": { "eq": 0 } })), None)
+            .await?;
+    }
+    let times = blocks
+        .as_array()
+        .map(|blocks| {
+            blocks
+                .iter()
+                .filter_map(|block| block["gen_utime"].as_u64())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    if times.len() < 2 {
+        return Ok(DEFAULT_BLOCK_INTERVAL_MS);
+    }
+    let span_ms = times[0].saturating_sub(times[times.len() - 1]) * 1000;
+    let interval = span_ms / (times.len() as u64 - 1);
+    Ok(if interval > 0 {
+        interval as u32
+    } else {
+        DEFAULT_BLOCK_INTERVAL_MS
+    })
+}
+
+/// Calculates the message header values for the message which will be
+/// signed now and sent later within the specified broadcast window.
+///
+/// Function detects the current network time and the block production rate.
+/// `expire` covers the whole broadcast window with the margin of one block
+/// interval, `wait_until` adds `wait_until_margin` to the `expire`
+/// so the monitoring does not resolve the message as expired before the network does.
+#[api_function]
+pub async fn plan_expiration(
+    context: Arc<ClientContext>,
+    params: ParamsOfPlanExpiration,
+) -> ClientResult<ResultOfPlanExpiration> {
+    let headers = params
+        .abi
+        .as_ref()
+        .map(|abi| -> ClientResult<_> {
+            let abi = abi.abi()?;
+            let has = |name: &str| abi.header().iter().any(|x| x.name == name);
+            Ok((has("time"), has("expire")))
+        })
+        .transpose()?;
+    let (has_time, has_expire) = headers.unwrap_or((true, true));
+
+    let endpoint = context.get_server_link()?.get_query_endpoint().await?;
+    let clock_delta = endpoint.server_time_delta();
+    let network_time = (context.env.now_ms() as i64 + clock_delta) as u64;
+    let block_interval = query_block_interval(&context).await?;
+    let wait_until_margin = params
+        .wait_until_margin
+        .unwrap_or(DEFAULT_WAIT_UNTIL_MARGIN_MS);
+
+    let start = network_time + params.broadcast_delay.unwrap_or(0) as u64;
+    let window = params
+        .broadcast_window
+        .unwrap_or(context.config.abi.message_expiration_timeout);
+    if window == 0 {
+        return Err(Error::empty_broadcast_window());
+    }
+    // `expire` header is specified in seconds
+    let expire_ms = start + window as u64 + block_interval as u64;
+    let expire = (expire_ms + 999) / 1000;
+    let wait_until = expire + (wait_until_margin as u64 + 999) / 1000;
+    if wait_until > u32::MAX as u64 {
+        return Err(Error::broadcast_window_out_of_range(wait_until));
+    }
+
+    Ok(ResultOfPlanExpiration {
+        time: if has_time { Some(start) } else { None },
+        expire: if has_expire {
+            Some(expire as u32)
+        } else {
+            None
+        },
+        wait_until: wait_until as u32,
+        network_time,
+        clock_delta,
+        block_interval,
+    })
+}
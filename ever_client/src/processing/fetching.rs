@@ -6,7 +6,10 @@ use crate::net::{
     wait_for_collection, ParamsOfWaitForCollection, MAX_TIMEOUT, TRANSACTIONS_COLLECTION,
 };
 use crate::processing::blocks_walking::wait_next_block;
-use crate::processing::internal::{can_retry_network_error, resolve_error};
+use crate::net::RetryOperation;
+use crate::processing::internal::{
+    can_retry_operation, is_retryable_by_policy, resolve_error, wait_before_retry,
+};
 use crate::processing::parsing::{decode_output, parse_transaction_boc};
 use crate::processing::{
     Error, ParamsOfWaitForTransaction, ProcessingEvent, ResultOfProcessMessage,
@@ -28,6 +31,8 @@ pub async fn fetch_next_shard_block<F: futures::Future<Output = ()> + Send>(
     callback: impl Fn(ProcessingEvent) -> F + Send + Sync,
 ) -> ClientResult<Block> {
     let start = context.env.now_ms();
+    let operation = RetryOperation::WaitForTransaction;
+    let mut retry_index = 0;
 
     // Network retries loop
     loop {
@@ -47,7 +52,8 @@ pub async fn fetch_next_shard_block<F: futures::Future<Output = ()> + Send>(
             Ok(block) => return Ok(block),
             Err(err) => {
                 let is_retryable_error = crate::client::Error::is_network_error(&err) ||
-                    err.code == crate::net::ErrorCode::WaitForTimeout as u32;
+                    err.code == crate::net::ErrorCode::WaitForTimeout as u32 ||
+                    is_retryable_by_policy(context, operation, err.code);
                 let error = Error::fetch_block_failed(err, &message_id, &block_id.to_string());
 
                 // Notify app about error
@@ -62,11 +68,13 @@ pub async fn fetch_next_shard_block<F: futures::Future<Output = ()> + Send>(
                     .await;
                 }
 
-                // If network retries timeout has reached, return error
-                if !is_retryable_error || !can_retry_network_error(context, start)
+                // If network retries limit has reached, return error
+                if !is_retryable_error || !can_retry_operation(context, operation, retry_index, start)
                 {
                     return Err(error);
                 }
+                wait_before_retry(context, operation, retry_index).await;
+                retry_index += 1;
             }
         }
     }
@@ -257,6 +265,8 @@ pub async fn fetch_transaction_result(
         out_messages,
         decoded: abi_decoded,
         fees,
+        bounced: None,
+        remp_timeline: None,
     })
 }
 
@@ -267,6 +277,8 @@ async fn fetch_transaction_boc(
     shard_block_id: &str,
 ) -> ClientResult<TransactionBoc> {
     let start = context.env.now_ms();
+    let operation = RetryOperation::WaitForTransaction;
+    let mut retry_index = 0;
 
     // Network retries loop
     loop {
@@ -280,12 +292,15 @@ async fn fetch_transaction_boc(
                 return Ok(TransactionBoc::from(value, message_id, shard_block_id)?);
             }
             Err(error) => {
-                // If network retries timeout has reached, return error
-                if !crate::client::Error::is_network_error(&error) ||
-                    !can_retry_network_error(context, start)
+                let is_retryable_error = crate::client::Error::is_network_error(&error) ||
+                    is_retryable_by_policy(context, operation, error.code);
+                // If network retries limit has reached, return error
+                if !is_retryable_error || !can_retry_operation(context, operation, retry_index, start)
                 {
                     return Err(error);
                 }
+                wait_before_retry(context, operation, retry_index).await;
+                retry_index += 1;
             }
         }
     }
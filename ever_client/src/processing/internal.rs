@@ -3,6 +3,7 @@ use super::fetching::fetch_account;
 use crate::abi::{Abi, ParamsOfDecodeMessage};
 use crate::client::ClientContext;
 use crate::error::{ClientError, ClientResult};
+use crate::net::RetryOperation;
 use crate::processing::Error;
 use crate::tvm::{AccountForExecutor, ExecutionOptions, ParamsOfRunExecutor};
 use std::sync::Arc;
@@ -21,7 +22,54 @@ pub fn can_retry_network_error(context: &Arc<ClientContext>, start: u64) -> bool
 }
 
 pub(crate) fn can_retry_expired_message(context: &Arc<ClientContext>, retries: u8) -> bool {
-    can_retry_more(retries, context.config.network.message_retries_count)
+    let policy = context.config.network.retry_policy(RetryOperation::Resend);
+    match policy.and_then(|policy| policy.max_attempts) {
+        Some(max_attempts) => (retries as u32).saturating_add(1) < max_attempts,
+        None => can_retry_more(retries, context.config.network.message_retries_count),
+    }
+}
+
+/// Returns `true` if the retry policy of the operation lists the error code as retryable.
+pub(crate) fn is_retryable_by_policy(
+    context: &Arc<ClientContext>,
+    operation: RetryOperation,
+    code: u32,
+) -> bool {
+    context
+        .config
+        .network
+        .retry_policy(operation)
+        .map(|policy| policy.is_retryable_code(code))
+        .unwrap_or(false)
+}
+
+/// Checks if the retry with the specified index (starting from 0) is allowed by the policy
+/// of the operation. Without the policy retries are limited by `max_reconnect_timeout`.
+pub(crate) fn can_retry_operation(
+    context: &Arc<ClientContext>,
+    operation: RetryOperation,
+    retry_index: u32,
+    start: u64,
+) -> bool {
+    match context.config.network.retry_policy(operation) {
+        Some(policy) => policy.can_retry(
+            retry_index,
+            context.env.now_ms(),
+            start + context.config.network.max_reconnect_timeout as u64,
+        ),
+        None => can_retry_network_error(context, start),
+    }
+}
+
+/// Waits for the delay defined by the policy of the operation. Does nothing if there is no policy.
+pub(crate) async fn wait_before_retry(
+    context: &Arc<ClientContext>,
+    operation: RetryOperation,
+    retry_index: u32,
+) {
+    if let Some(policy) = context.config.network.retry_policy(operation) {
+        let _ = context.env.set_timer(policy.delay(retry_index) as u64).await;
+    }
 }
 
 pub fn find_transactions(
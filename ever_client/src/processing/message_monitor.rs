@@ -1,9 +1,12 @@
 use crate::error::ClientResult;
 use crate::ClientContext;
-use std::sync::Arc;
 use ever_client_processing::{
     MessageMonitoringParams, MessageMonitoringResult, MonitorFetchWaitMode, MonitoringQueueInfo,
 };
+use futures::{Future, FutureExt};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{oneshot, Mutex};
 
 #[derive(Deserialize, Default, ApiType)]
 pub struct ParamsOfMonitorMessages {
@@ -72,6 +75,8 @@ pub struct ResultOfFetchNextMonitorResults {
 ///
 /// Results and waiting options are depends on the `wait` parameter.
 /// All returned results will be removed from the queue's resolved list.
+///
+/// Returned results are also removed from the persistent outbox (see `restore_outbox`).
 #[api_function]
 pub async fn fetch_next_monitor_results(
     context: Arc<ClientContext>,
@@ -84,9 +89,105 @@ pub async fn fetch_next_monitor_results(
             params.wait_mode.unwrap_or(MonitorFetchWaitMode::NoWait),
         )
         .await?;
+    // results are already removed from the queue, so they are returned
+    // even if the outbox can not be updated
+    let outbox = context.outbox.read().await.clone();
+    if let Some(outbox) = outbox {
+        if let Err(err) = outbox.resolve(&results).await {
+            log::warn!("Outbox update error: {}", err.message);
+        }
+    }
     Ok(ResultOfFetchNextMonitorResults { results })
 }
 
+#[derive(Serialize, Deserialize, Default, ApiType, Clone)]
+pub struct ParamsOfSubscribeMonitorResults {
+    /// Name of the monitoring queue.
+    pub queue: String,
+}
+
+#[derive(Serialize, Deserialize, Default, ApiType, Clone, Debug)]
+pub struct ResultOfSubscribeMonitorResults {
+    /// Subscription handle. Must be closed with `unsubscribe_monitor_results`.
+    pub handle: u32,
+}
+
+/// Handles of the monitoring results subscriptions.
+#[derive(Default)]
+pub(crate) struct MonitorSubscriptions {
+    handles: Mutex<HashMap<u32, oneshot::Sender<()>>>,
+}
+
+impl MonitorSubscriptions {
+    async fn add(&self, handle: u32, finish: oneshot::Sender<()>) {
+        self.handles.lock().await.insert(handle, finish);
+    }
+
+    async fn remove(&self, handle: u32) {
+        if let Some(finish) = self.handles.lock().await.remove(&handle) {
+            let _ = finish.send(());
+        }
+    }
+}
+
+pub async fn subscribe_monitor_results<F: Future<Output = ()> + Send>(
+    context: Arc<ClientContext>,
+    params: ParamsOfSubscribeMonitorResults,
+    callback: impl Fn(ClientResult<MessageMonitoringResult>) -> F + Send + Sync + 'static,
+) -> ClientResult<ResultOfSubscribeMonitorResults> {
+    let (sender, receiver) = oneshot::channel();
+    let handle = context.get_next_id();
+    context.monitor_subscriptions.add(handle, sender).await;
+
+    let queue = params.queue;
+    let context_copy = context.clone();
+    context.env.spawn(async move {
+        let context = context_copy;
+        let wait_finish = receiver.fuse();
+        futures::pin_mut!(wait_finish);
+        loop {
+            let fetch = context
+                .message_monitor
+                .fetch_next_monitor_results(&queue, MonitorFetchWaitMode::AtLeastOne)
+                .fuse();
+            futures::pin_mut!(fetch);
+            let fetched = futures::select!(
+                results = fetch => results,
+                _action = wait_finish => break,
+            );
+            match fetched {
+                Ok(results) => {
+                    let outbox = context.outbox.read().await.clone();
+                    if let Some(outbox) = outbox {
+                        if let Err(err) = outbox.resolve(&results).await {
+                            callback(Err(err)).await;
+                        }
+                    }
+                    for result in results {
+                        callback(Ok(result)).await;
+                    }
+                }
+                Err(err) => callback(Err(err.into())).await,
+            }
+        }
+    });
+
+    Ok(ResultOfSubscribeMonitorResults { handle })
+}
+
+/// Cancels a subscription to the monitoring results
+///
+/// Cancels a subscription created with `subscribe_monitor_results`.
+/// Unresolved messages stay in the monitoring queue.
+#[api_function]
+pub async fn unsubscribe_monitor_results(
+    context: Arc<ClientContext>,
+    params: ResultOfSubscribeMonitorResults,
+) -> ClientResult<()> {
+    context.monitor_subscriptions.remove(params.handle).await;
+    Ok(())
+}
+
 #[derive(Deserialize, ApiType, Default)]
 pub struct ParamsOfGetMonitorInfo {
     /// Name of the monitoring queue.
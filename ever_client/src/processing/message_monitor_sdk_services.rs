@@ -1,18 +1,48 @@
 use crate::boc::cache::Bocs;
 use crate::error::{ClientError, ClientResult};
-use crate::net::{NetworkContext, ResultOfSubscription};
+use crate::net::transaction_tree::query_transaction_tree_nodes;
+use crate::net::{
+    ErrorCode as NetErrorCode, NetworkContext, ResultOfQueryTransactionTree, ResultOfSubscription,
+    RetryOperation,
+};
+use crate::processing::remp::RempStatus;
 use async_trait::async_trait;
 use serde_json::Value;
 use std::future::Future;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
 use ever_client_processing::{
-    MessageMonitorSdkServices, MessageMonitoringParams, MessageMonitoringResult,
-    MessageMonitoringStatus, MessageMonitoringTransaction, MessageMonitoringTransactionCompute,
-    MonitoredMessage, NetSubscription,
+    MessageMonitorSdkServices, MessageMonitoringAbortedTransaction, MessageMonitoringParams,
+    MessageMonitoringResult, MessageMonitoringStatus, MessageMonitoringTransaction,
+    MessageMonitoringTransactionCompute, MessageMonitoringTransactionTree, MonitoredMessage,
+    NetSubscription, RempTimelineEntry,
 };
 use ever_block::Cell;
 
+const TRANSACTION_TREE_TIMEOUT_MS: u64 = 60000;
+const TRANSACTION_TREE_POLLING_PERIOD_MS: u64 = 1000;
+
+fn transaction_tree_summary(
+    message_hash: &str,
+    tree: ResultOfQueryTransactionTree,
+    complete: bool,
+) -> MessageMonitoringTransactionTree {
+    MessageMonitoringTransactionTree {
+        transaction_count: tree.transactions.len() as u32,
+        complete,
+        aborted: tree
+            .transactions
+            .into_iter()
+            .filter(|x| x.aborted && x.in_msg != message_hash)
+            .map(|x| MessageMonitoringAbortedTransaction {
+                hash: x.id,
+                address: x.account_addr,
+                exit_code: x.exit_code.map(|x| x as i32),
+            })
+            .collect(),
+    }
+}
+
 pub(crate) struct SdkServices {
     net: Arc<NetworkContext>,
     bocs: Arc<Bocs>,
@@ -84,6 +114,16 @@ fn deserialize_subscription_data(
     Ok(vec![result])
 }
 
+fn deserialize_remp_receipt(value: Value) -> ever_client_processing::Result<RempTimelineEntry> {
+    let status = serde_json::from_value::<RempStatus>(value).map_err(|err| {
+        crate::processing::Error::invalid_remp_status(format!(
+            "can not parse REMP status message: {}",
+            err
+        ))
+    })?;
+    Ok(status.timeline_entry())
+}
+
 #[async_trait]
 impl MessageMonitorSdkServices for SdkServices {
     async fn subscribe_for_recent_ext_in_message_statuses<F: Future<Output = ()> + Send>(
@@ -98,6 +138,7 @@ impl MessageMonitorSdkServices for SdkServices {
         let callback = Arc::new(callback);
         let (query, vars) = Self::subscription(messages);
         let retry_start = Arc::new(AtomicU64::new(0));
+        let retry_index = Arc::new(AtomicU32::new(0));
         let net_state = self.net.get_server_link()?.state();
         let subscription = self
             .net
@@ -109,10 +150,12 @@ impl MessageMonitorSdkServices for SdkServices {
                     let callback = callback.clone();
                     let net_state = net_state.clone();
                     let retry_start = retry_start.clone();
+                    let retry_index = retry_index.clone();
                     async move {
                         match evt {
                             Ok(evt) => {
                                 retry_start.store(0, Ordering::Relaxed);
+                                retry_index.store(0, Ordering::Relaxed);
                                 callback(deserialize_subscription_data(evt)).await;
                             }
                             Err(err) => {
@@ -127,8 +170,10 @@ impl MessageMonitorSdkServices for SdkServices {
                                 {
                                     return;
                                 }
-                                if !crate::client::Error::is_network_error(&err)
-                                    || !net_state.can_retry_network_error(start)
+                                let operation = RetryOperation::Subscription;
+                                let index = retry_index.fetch_add(1, Ordering::Relaxed);
+                                if !net_state.is_retryable_error(operation, &err)
+                                    || !net_state.can_retry(operation, index, start)
                                 {
                                     callback(Err(err.into()));
                                 }
@@ -141,6 +186,49 @@ impl MessageMonitorSdkServices for SdkServices {
         Ok(NetSubscription(subscription as usize))
     }
 
+    async fn subscribe_for_remp_receipts<F: Future<Output = ()> + Send>(
+        &self,
+        message_hash: &str,
+        callback: impl Fn(ever_client_processing::Result<RempTimelineEntry>) -> F
+            + Send
+            + Sync
+            + 'static,
+    ) -> ever_client_processing::Result<NetSubscription> {
+        let callback = Arc::new(callback);
+        let query = format!(
+            r#"
+            subscription {{
+                rempReceipts(messageId: "{}") {{
+                    messageId kind timestamp json
+                }}
+            }}
+            "#,
+            message_hash
+        );
+        let subscription = self
+            .net
+            .subscribe(
+                query,
+                None,
+                move |evt: ClientResult<ResultOfSubscription>| {
+                    let callback = callback.clone();
+                    async move {
+                        match evt {
+                            Ok(mut evt) => {
+                                let receipt = evt.result["rempReceipts"].take();
+                                if !receipt.is_null() {
+                                    callback(deserialize_remp_receipt(receipt)).await;
+                                }
+                            }
+                            Err(err) => callback(Err(err.into())).await,
+                        }
+                    }
+                },
+            )
+            .await?;
+        Ok(NetSubscription(subscription as usize))
+    }
+
     async fn unsubscribe(
         &self,
         subscription: NetSubscription,
@@ -148,6 +236,55 @@ impl MessageMonitorSdkServices for SdkServices {
         Ok(self.net.unsubscribe(subscription.0 as u32).await?)
     }
 
+    async fn wait_for_transaction_tree(
+        &self,
+        message_hash: &str,
+    ) -> ever_client_processing::Result<MessageMonitoringTransactionTree> {
+        // single deadline for the whole waiting: both the tree queries and the polling
+        let time_limit = self.net.env.now_ms() + TRANSACTION_TREE_TIMEOUT_MS;
+        let mut pending_tree = None;
+        loop {
+            let timeout = time_limit.saturating_sub(self.net.env.now_ms()).max(1);
+            let tree = match query_transaction_tree_nodes(
+                self.net.get_server_link()?,
+                message_hash,
+                timeout as u32,
+                0,
+                None,
+            )
+            .await
+            {
+                Ok(tree) => tree,
+                Err(err) if err.code == NetErrorCode::QueryTransactionTreeTimeout as u32 => {
+                    // the tree polled before the deadline is reported as incomplete
+                    return match pending_tree.take() {
+                        Some(tree) => Ok(transaction_tree_summary(message_hash, tree, false)),
+                        None => Err(err.into()),
+                    };
+                }
+                Err(err) => return Err(err.into()),
+            };
+            // internal message without destination transaction is not processed yet
+            let pending = tree.messages.iter().any(|message| {
+                message.src.as_ref().map_or(false, |x| !x.is_empty())
+                    && message.dst.as_ref().map_or(false, |x| !x.is_empty())
+                    && message.dst_transaction_id.is_none()
+            });
+            let now = self.net.env.now_ms();
+            if !pending || now >= time_limit {
+                return Ok(transaction_tree_summary(message_hash, tree, !pending));
+            }
+            pending_tree = Some(tree);
+            self.net
+                .env
+                .set_timer(std::cmp::min(
+                    TRANSACTION_TREE_POLLING_PERIOD_MS,
+                    time_limit - now,
+                ))
+                .await?;
+        }
+    }
+
     fn spawn(&self, future: impl Future<Output = ()> + Send + 'static) {
         self.net.env.spawn(future);
     }
@@ -211,6 +348,8 @@ impl From<GraphQLMessageMonitoringResult> for MessageMonitoringResult {
             transaction: value.transaction.map(|x| x.into()),
             error: value.error,
             user_data: None,
+            transaction_tree: None,
+            remp_timeline: None,
         }
     }
 }
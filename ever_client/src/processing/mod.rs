@@ -16,37 +16,65 @@
 mod tests;
 
 pub(crate) mod blocks_walking;
+pub(crate) mod bounces;
 mod errors;
-mod fetching;
+mod expiration;
+pub(crate) mod fetching;
 mod internal;
 mod message_monitor;
 mod message_monitor_sdk_services;
+pub(crate) mod outbox;
 pub(crate) mod parsing;
+mod preflight;
 pub(crate) mod process_message;
+pub(crate) mod process_messages;
 mod remp;
+pub(crate) mod remp_metrics;
 pub(crate) mod send_message;
 mod send_messages;
+pub(crate) mod send_scheduler;
 mod types;
 pub(crate) mod wait_for_transaction;
 
+pub use bounces::{BounceTrackingOptions, BouncedMessage};
 pub use errors::{Error, ErrorCode};
+pub use expiration::{
+    plan_expiration, plan_expiration_api, ParamsOfPlanExpiration, ResultOfPlanExpiration,
+};
 pub use message_monitor::{
     cancel_monitor, cancel_monitor_api, fetch_next_monitor_results, fetch_next_monitor_results_api,
     get_monitor_info, get_monitor_info_api, monitor_messages, monitor_messages_api,
+    subscribe_monitor_results, unsubscribe_monitor_results, unsubscribe_monitor_results_api,
     ParamsOfCancelMonitor, ParamsOfFetchNextMonitorResults, ParamsOfGetMonitorInfo,
-    ParamsOfMonitorMessages, ResultOfFetchNextMonitorResults,
+    ParamsOfMonitorMessages, ParamsOfSubscribeMonitorResults, ResultOfFetchNextMonitorResults,
+    ResultOfSubscribeMonitorResults,
 };
+pub(crate) use message_monitor::MonitorSubscriptions;
 pub(crate) use message_monitor_sdk_services::SdkServices;
+pub(crate) use outbox::Outbox;
+pub use outbox::{restore_outbox, restore_outbox_api, ResultOfRestoreOutbox};
+pub use preflight::PreflightOptions;
 pub use process_message::{process_message, ParamsOfProcessMessage};
+pub use process_messages::{
+    process_messages, ParamsOfProcessMessages, ProcessMessageOutcome, ResultOfProcessMessages,
+};
+pub(crate) use send_scheduler::SendScheduler;
+pub(crate) use remp_metrics::RempMetrics;
+pub use remp_metrics::{
+    get_remp_latency_stats, get_remp_latency_stats_api, ParamsOfGetRempLatencyStats,
+    RempLatencyBucket, RempStageLatency, ResultOfGetRempLatencyStats,
+};
+pub use send_scheduler::SendSchedulingOptions;
 pub use send_message::{send_message, ParamsOfSendMessage, ResultOfSendMessage};
 pub use send_messages::{
     send_messages, send_messages_api, MessageSendingParams, ParamsOfSendMessages,
     ResultOfSendMessages,
 };
 pub use ever_client_processing::{
-    MessageMonitoringParams, MessageMonitoringResult, MessageMonitoringStatus,
-    MessageMonitoringTransaction, MessageMonitoringTransactionCompute, MonitorFetchWaitMode,
-    MonitoredMessage, MonitoringQueueInfo,
+    MessageMonitoringAbortedTransaction, MessageMonitoringParams, MessageMonitoringResult,
+    MessageMonitoringStatus, MessageMonitoringTransaction, MessageMonitoringTransactionCompute,
+    MessageMonitoringTransactionTree, MonitorFetchWaitMode, MonitoredMessage, MonitoringQueueInfo,
+    RempTimelineEntry,
 };
 pub use types::{DecodedOutput, ProcessingEvent, ProcessingResponseType, ResultOfProcessMessage};
 pub use wait_for_transaction::{wait_for_transaction, ParamsOfWaitForTransaction};
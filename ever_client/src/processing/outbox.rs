@@ -0,0 +1,276 @@
+/*
+ * Copyright 2018-2021 EverX Labs Ltd.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ *
+ */
+
+use crate::client::storage::KeyValueStorage;
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use crate::processing::MessageSendingParams;
+use ever_client_processing::{MessageMonitoringParams, MessageMonitoringResult, MonitoredMessage};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+const INDEX_KEY: &str = "index";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub(crate) enum OutboxMessageStatus {
+    /// Message is recorded but sending is not confirmed yet.
+    ///
+    /// The message can be sent or not if the application has been
+    /// stopped in this status.
+    Sending,
+    /// Message is sent and monitored for the processing result.
+    Monitoring,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub(crate) struct OutboxRecord {
+    pub queue: String,
+    pub hash: String,
+    pub message: MessageSendingParams,
+    pub status: OutboxMessageStatus,
+}
+
+impl OutboxRecord {
+    pub fn monitoring_params(&self) -> MessageMonitoringParams {
+        MessageMonitoringParams {
+            message: MonitoredMessage::Boc {
+                boc: self.message.boc.clone(),
+            },
+            wait_until: self.message.wait_until,
+            user_data: self.message.user_data.clone(),
+            wait_transaction_tree: self.message.wait_transaction_tree,
+            collect_remp_timeline: self.message.collect_remp_timeline,
+        }
+    }
+}
+
+/// Persistent list of the messages sent with `send_messages` and not yet
+/// delivered to the application as the monitoring results.
+pub(crate) struct Outbox {
+    storage: Arc<dyn KeyValueStorage>,
+    index_lock: Mutex<()>,
+    /// Hashes of the records sent or restored by this process. Such records
+    /// are already in their monitoring queues and must not be restored again.
+    monitored: Mutex<HashSet<String>>,
+}
+
+impl Outbox {
+    pub fn new(storage: Arc<dyn KeyValueStorage>) -> Self {
+        Self {
+            storage,
+            index_lock: Mutex::new(()),
+            monitored: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub async fn obtain(context: &Arc<ClientContext>) -> ClientResult<Arc<Outbox>> {
+        if let Some(outbox) = context.outbox.read().await.as_ref() {
+            return Ok(Arc::clone(outbox));
+        }
+
+        // messages are valid only for the network they were sent to
+        let network_uid = context.net.get_current_network_uid().await?;
+        let storage_name = format!(
+            "outbox/{}",
+            hex::encode(&network_uid.zerostate_root_hash.as_slice()[..4]),
+        );
+        let storage = crate::client::LocalStorage::new(
+            context.config.local_storage_path.clone(),
+            storage_name,
+        )
+        .await?;
+        let new_outbox = Arc::new(Outbox::new(Arc::new(storage)));
+
+        let mut write_guard = context.outbox.write().await;
+        if let Some(outbox) = write_guard.as_ref() {
+            return Ok(Arc::clone(outbox));
+        }
+        *write_guard = Some(Arc::clone(&new_outbox));
+
+        Ok(new_outbox)
+    }
+
+    fn record_key(hash: &str) -> String {
+        format!("message_{}", hash)
+    }
+
+    async fn load_index(&self) -> ClientResult<Vec<String>> {
+        match self.storage.get_str(INDEX_KEY).await? {
+            Some(index) => serde_json::from_str(&index)
+                .map_err(|err| crate::client::Error::internal_error(err)),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn save_index(&self, index: &Vec<String>) -> ClientResult<()> {
+        self.storage
+            .put_str(INDEX_KEY, &json!(index).to_string())
+            .await
+    }
+
+    async fn save_record(&self, record: &OutboxRecord) -> ClientResult<()> {
+        self.storage
+            .put_str(&Self::record_key(&record.hash), &json!(record).to_string())
+            .await
+    }
+
+    async fn load_record(&self, hash: &str) -> ClientResult<Option<OutboxRecord>> {
+        match self.storage.get_str(&Self::record_key(hash)).await? {
+            Some(record) => serde_json::from_str(&record)
+                .map(Some)
+                .map_err(|err| crate::client::Error::internal_error(err)),
+            None => Ok(None),
+        }
+    }
+
+    /// Records messages before they are sent.
+    pub async fn add(
+        &self,
+        queue: &str,
+        messages: &[(String, MessageSendingParams)],
+    ) -> ClientResult<()> {
+        let _guard = self.index_lock.lock().await;
+        let mut index = self.load_index().await?;
+        let mut monitored = self.monitored.lock().await;
+        for (hash, message) in messages {
+            monitored.insert(hash.clone());
+            self.save_record(&OutboxRecord {
+                queue: queue.to_string(),
+                hash: hash.clone(),
+                message: message.clone(),
+                status: OutboxMessageStatus::Sending,
+            })
+            .await?;
+            if !index.contains(hash) {
+                index.push(hash.clone());
+            }
+        }
+        self.save_index(&index).await
+    }
+
+    pub async fn set_status(
+        &self,
+        hashes: &[String],
+        status: OutboxMessageStatus,
+    ) -> ClientResult<()> {
+        let _guard = self.index_lock.lock().await;
+        for hash in hashes {
+            if let Some(mut record) = self.load_record(hash).await? {
+                record.status = status.clone();
+                self.save_record(&record).await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn remove(&self, hashes: &[String]) -> ClientResult<()> {
+        if hashes.is_empty() {
+            return Ok(());
+        }
+        let _guard = self.index_lock.lock().await;
+        let mut monitored = self.monitored.lock().await;
+        for hash in hashes {
+            monitored.remove(hash);
+        }
+        let mut index = self.load_index().await?;
+        let len = index.len();
+        index.retain(|hash| !hashes.contains(hash));
+        if index.len() == len {
+            return Ok(());
+        }
+        for hash in hashes {
+            self.storage.remove(&Self::record_key(hash)).await?;
+        }
+        self.save_index(&index).await
+    }
+
+    /// Removes records of the results delivered to the application.
+    pub async fn resolve(&self, results: &[MessageMonitoringResult]) -> ClientResult<()> {
+        let hashes = results.iter().map(|x| x.hash.clone()).collect::<Vec<_>>();
+        self.remove(&hashes).await
+    }
+
+    async fn load_records(&self) -> ClientResult<Vec<OutboxRecord>> {
+        let mut records = Vec::new();
+        for hash in self.load_index().await? {
+            if let Some(record) = self.load_record(&hash).await? {
+                records.push(record);
+            }
+        }
+        Ok(records)
+    }
+
+    pub async fn unresolved(&self) -> ClientResult<Vec<OutboxRecord>> {
+        let _guard = self.index_lock.lock().await;
+        self.load_records().await
+    }
+
+    /// Returns the unresolved records that are not monitored by this process
+    /// and marks them as monitored.
+    pub async fn claim_unmonitored(&self) -> ClientResult<Vec<OutboxRecord>> {
+        let _guard = self.index_lock.lock().await;
+        let records = self.load_records().await?;
+        let mut monitored = self.monitored.lock().await;
+        Ok(records
+            .into_iter()
+            .filter(|record| monitored.insert(record.hash.clone()))
+            .collect())
+    }
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct ResultOfRestoreOutbox {
+    /// Number of the restored messages in each monitoring queue.
+    ///
+    /// Object with the queue names as keys.
+    pub queues: serde_json::Value,
+}
+
+/// Restores monitoring of the unresolved messages from the persistent outbox.
+///
+/// Messages sent with `send_messages` with `persist` flag are recorded into the
+/// local storage before sending and removed from it when the monitoring result
+/// is fetched by the application with `fetch_next_monitor_results`.
+///
+/// Application should call this function on startup to put the messages left
+/// unresolved by the previous run back into their monitoring queues.
+/// Messages which sending was not confirmed are monitored too: if such a message
+/// has not reached the network the monitor resolves it with `Timeout` status.
+///
+/// Messages already monitored by this client (sent or restored earlier) are skipped,
+/// so the function can be called more than once.
+#[api_function]
+pub async fn restore_outbox(context: Arc<ClientContext>) -> ClientResult<ResultOfRestoreOutbox> {
+    let outbox = Outbox::obtain(&context).await?;
+    let mut queues = HashMap::<String, Vec<OutboxRecord>>::new();
+    for record in outbox.claim_unmonitored().await? {
+        queues.entry(record.queue.clone()).or_default().push(record);
+    }
+    let mut result = serde_json::Map::new();
+    for (queue, records) in queues {
+        result.insert(queue.clone(), json!(records.len()));
+        let hashes = records.iter().map(|x| x.hash.clone()).collect::<Vec<_>>();
+        context.message_monitor.monitor_messages(
+            &queue,
+            records.iter().map(|x| x.monitoring_params()).collect(),
+        )?;
+        outbox
+            .set_status(&hashes, OutboxMessageStatus::Monitoring)
+            .await?;
+    }
+    Ok(ResultOfRestoreOutbox {
+        queues: result.into(),
+    })
+}
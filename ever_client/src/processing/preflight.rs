@@ -0,0 +1,119 @@
+/*
+ * Copyright 2018-2021 EverX Labs Ltd.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ *
+ */
+
+use super::fetching::fetch_account;
+use crate::abi::{Abi, ResultOfEncodeMessage};
+use crate::client::ClientContext;
+use crate::encoding::account_decode;
+use crate::error::ClientResult;
+use crate::processing::{Error, ProcessingEvent};
+use crate::tvm::{AccountForExecutor, ParamsOfRunExecutor};
+use ever_sdk::TransactionFees;
+use std::sync::Arc;
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct PreflightOptions {
+    /// Maximum acceptable account fees for the transaction in nanotokens
+    /// (decimal string).
+    ///
+    /// If the estimated `account_fees` exceed this value then processing fails
+    /// with `PreflightFeesLimitExceeded` error. Fees are not limited if not specified.
+    pub max_fees: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PreflightAccount {
+    boc: Option<String>,
+    #[serde(with = "ever_sdk::json_helper::uint")]
+    balance: u64,
+}
+
+/// Runs the encoded message on the current account state locally
+/// and checks the result before the message is sent.
+pub(crate) async fn check_message<F: futures::Future<Output = ()> + Send>(
+    context: &Arc<ClientContext>,
+    options: &PreflightOptions,
+    message: &ResultOfEncodeMessage,
+    abi: &Abi,
+    send_events: bool,
+    callback: &(impl Fn(ProcessingEvent) -> F + Send + Sync + 'static),
+) -> ClientResult<TransactionFees> {
+    let max_fees = options
+        .max_fees
+        .as_ref()
+        .map(|fees| {
+            fees.parse::<u64>()
+                .map_err(|err| Error::invalid_preflight_max_fees(fees, err))
+        })
+        .transpose()?;
+    let message_id = message.message_id.as_str();
+    let address = account_decode(&message.address)?;
+    let account = fetch_account(context.clone(), &address, "boc balance").await?;
+    if account.is_null() {
+        return Err(crate::tvm::Error::account_missing(&address));
+    }
+    let account: PreflightAccount = serde_json::from_value(account)
+        .map_err(|err| Error::invalid_data(format!("can not parse account: {}", err)))?;
+    let boc = account
+        .boc
+        .ok_or_else(|| crate::tvm::Error::account_missing(&address))?;
+
+    // balance is checked separately to distinguish the low balance from the
+    // contract failure
+    let result = crate::tvm::run_executor_internal(
+        context.clone(),
+        ParamsOfRunExecutor {
+            message: message.message.clone(),
+            account: AccountForExecutor::Account {
+                boc,
+                unlimited_balance: Some(true),
+            },
+            abi: Some(abi.clone()),
+            ..Default::default()
+        },
+        true,
+    )
+    .await
+    .map_err(|err| Error::preflight_transaction_aborted(message_id, err))?;
+    let fees = result.fees;
+
+    if send_events {
+        callback(ProcessingEvent::PreflightEstimated {
+            message_id: message_id.to_string(),
+            message_dst: address.to_string(),
+            fees: fees.clone(),
+            balance: account.balance.to_string(),
+        })
+        .await;
+    }
+
+    let required = fees.account_fees.saturating_add(fees.total_output);
+    if account.balance < required {
+        return Err(Error::preflight_low_balance(
+            message_id,
+            account.balance,
+            required,
+        ));
+    }
+    if let Some(max_fees) = max_fees {
+        if fees.account_fees > max_fees {
+            return Err(Error::preflight_fees_limit_exceeded(
+                message_id,
+                fees.account_fees,
+                max_fees,
+            ));
+        }
+    }
+    Ok(fees)
+}
@@ -1,7 +1,13 @@
 use crate::abi::ParamsOfEncodeMessage;
 use crate::client::ClientContext;
 use crate::error::{AddNetworkUrl, ClientResult};
-use crate::processing::internal::can_retry_expired_message;
+use crate::net::RetryOperation;
+use crate::processing::internal::{
+    can_retry_expired_message, is_retryable_by_policy, wait_before_retry,
+};
+use crate::processing::bounces::{find_bounced_messages, BounceTrackingOptions};
+use crate::processing::preflight::{check_message, PreflightOptions};
+use crate::processing::send_scheduler::SendSchedulingOptions;
 use crate::processing::{
     send_message, wait_for_transaction, ErrorCode, ParamsOfSendMessage, ParamsOfWaitForTransaction,
     ProcessingEvent, ResultOfProcessMessage, ResultOfSendMessage,
@@ -18,6 +24,32 @@ pub struct ParamsOfProcessMessage {
     /// Default is `false`.
     #[serde(default)]
     pub send_events: bool,
+
+    /// Pre-flight mode options.
+    ///
+    /// If specified, the message is executed locally on the current account state
+    /// before sending. Processing fails without sending if the transaction will be
+    /// aborted, the account balance is too low or the estimated fees exceed
+    /// `max_fees`.
+    pub preflight: Option<PreflightOptions>,
+
+    /// Sequential sending options.
+    ///
+    /// If specified, the messages to the same address are encoded and sent one by one,
+    /// each message gets the `time` header greater than the previous one. The `time`
+    /// header must not be specified in `message_encode_params`. With `seqno_param`
+    /// the next sequence number is passed as a function input parameter and the next
+    /// message is encoded only after the previous one has been processed.
+    /// Expired messages are re-encoded with the new `time` and `expire` calculated
+    /// with `message_expiration_timeout_grow_factor`.
+    pub scheduling: Option<SendSchedulingOptions>,
+
+    /// Bounce tracking options.
+    ///
+    /// If specified, the library waits for the transactions of the bounceable internal
+    /// messages produced by the message transaction and reports the bounced ones in
+    /// `ResultOfProcessMessage.bounced`.
+    pub bounces: Option<BounceTrackingOptions>,
 }
 
 pub async fn process_message<F: futures::Future<Output = ()> + Send>(
@@ -26,19 +58,46 @@ pub async fn process_message<F: futures::Future<Output = ()> + Send>(
     callback: impl Fn(ProcessingEvent) -> F + Send + Sync + 'static,
 ) -> ClientResult<ResultOfProcessMessage> {
     let abi = params.message_encode_params.abi.clone();
+    let mut scheduled = None;
 
     let mut try_index = 0;
     loop {
         // Encode message
         let mut encode_params = params.message_encode_params.clone();
         encode_params.processing_try_index = Some(try_index);
+        if let Some(options) = &params.scheduling {
+            if scheduled.is_none() {
+                scheduled = Some(
+                    context
+                        .send_scheduler
+                        .lock(context.env.now_ms(), &params.message_encode_params, options)
+                        .await?,
+                );
+            }
+            if let Some(state) = &mut scheduled {
+                state.prepare(context.env.now_ms(), options, &mut encode_params)?;
+            }
+        }
         let message = crate::abi::encode_message(context.clone(), encode_params)
             .await?;
 
+        if let Some(preflight) = &params.preflight {
+            check_message(
+                &context,
+                preflight,
+                &message,
+                &abi,
+                params.send_events,
+                &callback,
+            )
+            .await?;
+        }
+
         // Send
         let ResultOfSendMessage {
             shard_block_id,
             sending_endpoints,
+            ..
         } = send_message(
             context.clone(),
             ParamsOfSendMessage {
@@ -51,6 +110,14 @@ pub async fn process_message<F: futures::Future<Output = ()> + Send>(
         .await
         .add_network_url_from_context(&context)
         .await?;
+        // the next message to the address can be sent while this one is awaited
+        if !params
+            .scheduling
+            .as_ref()
+            .map_or(false, |options| options.is_sequential())
+        {
+            scheduled = None;
+        }
 
         let wait_for = wait_for_transaction(
             context.clone(),
@@ -68,16 +135,27 @@ pub async fn process_message<F: futures::Future<Output = ()> + Send>(
         .await;
 
         match wait_for {
-            Ok(output) => {
+            Ok(mut output) => {
+                if let Some(state) = &mut scheduled {
+                    state.message_processed();
+                }
+                if let Some(bounces) = &params.bounces {
+                    output.bounced = Some(
+                        find_bounced_messages(&context, &output.out_messages, bounces, &abi)
+                            .await?,
+                    );
+                }
                 // Waiting is complete, return output
                 return Ok(output);
             }
             Err(err) => {
                 let local_exit_code = &err.data["local_error"]["data"]["exit_code"];
-                let can_retry = err.code == ErrorCode::MessageExpired as u32
+                let is_retryable_expiration = err.code == ErrorCode::MessageExpired as u32
                     && (err.data["local_error"].is_null()
                         || local_exit_code == StdContractError::ReplayProtection as i32
-                        || local_exit_code == StdContractError::ExtMessageExpired as i32)
+                        || local_exit_code == StdContractError::ExtMessageExpired as i32);
+                let can_retry = (is_retryable_expiration
+                    || is_retryable_by_policy(&context, RetryOperation::Resend, err.code))
                     && can_retry_expired_message(&context, try_index);
                 if !can_retry {
                     // Waiting error is unrecoverable, return it
@@ -92,6 +170,7 @@ pub async fn process_message<F: futures::Future<Output = ()> + Send>(
                     }).await;
                 }
                 // Waiting is failed but we can retry
+                wait_before_retry(&context, RetryOperation::Resend, try_index as u32).await;
             }
         };
         try_index = try_index.checked_add(1).unwrap_or(try_index);
@@ -0,0 +1,450 @@
+/*
+ * Copyright 2018-2021 EverX Labs Ltd.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ *
+ */
+
+use crate::abi::ResultOfEncodeMessage;
+use crate::client::ClientContext;
+use crate::encoding::account_decode;
+use crate::error::{AddNetworkUrl, ClientError, ClientResult};
+use crate::net::RetryOperation;
+use crate::processing::bounces::find_bounced_messages;
+use crate::processing::fetching::fetch_transaction_result;
+use crate::processing::internal::{
+    can_retry_expired_message, get_message_expiration_time, is_retryable_by_policy,
+    wait_before_retry,
+};
+use crate::processing::preflight::check_message;
+use crate::processing::{
+    Error, MessageMonitoringParams, MessageMonitoringResult, MessageMonitoringStatus,
+    MonitorFetchWaitMode, MonitoredMessage, ParamsOfProcessMessage, ProcessingEvent,
+    ResultOfProcessMessage,
+};
+use futures::StreamExt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use ever_block::{MsgAddressInt, UInt256};
+
+const DEFAULT_MAX_CONCURRENCY: u32 = 10;
+
+static NEXT_BATCH_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug)]
+pub struct ParamsOfProcessMessages {
+    /// Messages to process.
+    ///
+    /// `scheduling` option is not supported for the batch processing.
+    pub messages: Vec<ParamsOfProcessMessage>,
+
+    /// Maximum number of messages that are encoded, checked in pre-flight mode
+    /// or fetched concurrently. Default is 10.
+    pub max_concurrency: Option<u32>,
+
+    /// Flag for requesting `BatchProgress` events.
+    /// Events of the individual messages are controlled by their own `send_events` flags.
+    /// Default is `false`.
+    #[serde(default)]
+    pub send_events: bool,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct ProcessMessageOutcome {
+    /// Result of the message processing. Missing if the processing has failed.
+    pub result: Option<ResultOfProcessMessage>,
+
+    /// Processing error. Missing if the message has been processed successfully.
+    pub error: Option<ClientError>,
+}
+
+impl From<ClientResult<ResultOfProcessMessage>> for ProcessMessageOutcome {
+    fn from(result: ClientResult<ResultOfProcessMessage>) -> Self {
+        match result {
+            Ok(result) => Self {
+                result: Some(result),
+                error: None,
+            },
+            Err(error) => Self {
+                result: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct ResultOfProcessMessages {
+    /// Outcomes of the message processing in the order of `ParamsOfProcessMessages.messages`.
+    pub results: Vec<ProcessMessageOutcome>,
+}
+
+struct PreparedMessage {
+    index: usize,
+    message: ResultOfEncodeMessage,
+    hash: UInt256,
+    boc: String,
+    dst: MsgAddressInt,
+    wait_until: u32,
+}
+
+#[derive(Default)]
+struct BatchProgress {
+    total: u32,
+    encoded: u32,
+    sent: u32,
+    succeeded: u32,
+    failed: u32,
+}
+
+impl BatchProgress {
+    fn event(&self) -> ProcessingEvent {
+        ProcessingEvent::BatchProgress {
+            total: self.total,
+            encoded: self.encoded,
+            sent: self.sent,
+            succeeded: self.succeeded,
+            failed: self.failed,
+        }
+    }
+}
+
+async fn prepare_message<F: futures::Future<Output = ()> + Send>(
+    context: &Arc<ClientContext>,
+    index: usize,
+    params: &ParamsOfProcessMessage,
+    try_index: u8,
+    callback: &(impl Fn(ProcessingEvent) -> F + Send + Sync + 'static),
+) -> ClientResult<PreparedMessage> {
+    let abi = &params.message_encode_params.abi;
+    let mut encode_params = params.message_encode_params.clone();
+    encode_params.processing_try_index = Some(try_index);
+    let message = crate::abi::encode_message(context.clone(), encode_params).await?;
+    if let Some(preflight) = &params.preflight {
+        check_message(
+            context,
+            preflight,
+            &message,
+            abi,
+            params.send_events,
+            callback,
+        )
+        .await?;
+    }
+    let (hash, boc) = context
+        .bocs
+        .resolve_boc_with_hash(&message.message, "message")?;
+    let dst = account_decode(&message.address)?;
+    let wait_until = get_message_expiration_time(context.clone(), Some(abi), &message.message)?
+        .unwrap_or_else(|| {
+            context.env.now_ms() + context.config.network.message_processing_timeout as u64
+        });
+    Ok(PreparedMessage {
+        index,
+        message,
+        hash,
+        boc,
+        dst,
+        wait_until: (wait_until / 1000) as u32,
+    })
+}
+
+async fn fetch_result(
+    context: &Arc<ClientContext>,
+    params: &ParamsOfProcessMessage,
+    prepared: &PreparedMessage,
+    monitoring: &MessageMonitoringResult,
+) -> ClientResult<ResultOfProcessMessage> {
+    let abi = &params.message_encode_params.abi;
+    let transaction_id = monitoring
+        .transaction
+        .as_ref()
+        .and_then(|transaction| transaction.hash.as_ref())
+        .ok_or_else(|| {
+            Error::fetch_transaction_result_failed(
+                "monitoring result has no transaction",
+                &prepared.message.message_id,
+                "",
+            )
+        })?;
+    let mut result = fetch_transaction_result(
+        context,
+        "",
+        &prepared.message.message_id,
+        &prepared.message.message,
+        Some(transaction_id),
+        &Some(abi.clone()),
+        prepared.dst.clone(),
+        prepared.wait_until,
+        (context.env.now_ms() / 1000) as u32,
+    )
+    .await
+    .add_network_url_from_context(context)
+    .await?;
+    if let Some(bounces) = &params.bounces {
+        result.bounced =
+            Some(find_bounced_messages(context, &result.out_messages, bounces, abi).await?);
+    }
+    Ok(result)
+}
+
+/// Stores the error as the result of each message which has no result yet.
+fn fail_unresolved<'a>(
+    prepared: impl IntoIterator<Item = &'a PreparedMessage>,
+    err: &ClientError,
+    results: &mut Vec<Option<ClientResult<ResultOfProcessMessage>>>,
+    progress: &mut BatchProgress,
+) {
+    for message in prepared {
+        if results[message.index].is_none() {
+            results[message.index] = Some(Err(err.clone()));
+            progress.failed += 1;
+        }
+    }
+}
+
+async fn send_round(
+    context: &Arc<ClientContext>,
+    prepared: &[PreparedMessage],
+) -> ClientResult<()> {
+    let server_link = context.get_server_link()?;
+    let endpoint = server_link.state().get_query_endpoint().await?;
+    server_link
+        .send_messages(
+            prepared
+                .iter()
+                .map(|message| (message.hash.clone(), message.boc.clone()))
+                .collect(),
+            Some(&endpoint),
+        )
+        .await
+        .add_endpoint_from_context(context, &endpoint)
+        .await?;
+    Ok(())
+}
+
+/// Processes messages of one retry round: sends them, monitors them in a single
+/// monitoring queue and fetches the results.
+///
+/// Errors of the round are stored as the results of the messages they affect.
+/// Returns the messages which have expired and can be retried.
+async fn process_round<F: futures::Future<Output = ()> + Send>(
+    context: &Arc<ClientContext>,
+    params: &ParamsOfProcessMessages,
+    prepared: Vec<PreparedMessage>,
+    try_index: u8,
+    results: &mut Vec<Option<ClientResult<ResultOfProcessMessage>>>,
+    progress: &mut BatchProgress,
+    callback: &(impl Fn(ProcessingEvent) -> F + Send + Sync + 'static),
+) -> Vec<usize> {
+    if let Err(err) = send_round(context, &prepared).await {
+        fail_unresolved(&prepared, &err, results, progress);
+        return Vec::new();
+    }
+    progress.sent += prepared.len() as u32;
+    if params.send_events {
+        callback(progress.event()).await;
+    }
+
+    let queue = format!(
+        "process_messages_{}",
+        NEXT_BATCH_ID.fetch_add(1, Ordering::Relaxed)
+    );
+    let monitored = context.message_monitor.monitor_messages(
+        &queue,
+        prepared
+            .iter()
+            .enumerate()
+            .map(|(i, message)| MessageMonitoringParams {
+                message: MonitoredMessage::HashAddress {
+                    hash: message.hash.as_hex_string(),
+                    address: message.dst.to_string(),
+                },
+                wait_until: message.wait_until,
+                user_data: Some(json!(i)),
+                wait_transaction_tree: None,
+                collect_remp_timeline: None,
+            })
+            .collect(),
+    );
+    if let Err(err) = monitored {
+        fail_unresolved(&prepared, &ClientError::from(err), results, progress);
+        return Vec::new();
+    }
+
+    let max_concurrency = params
+        .max_concurrency
+        .unwrap_or(DEFAULT_MAX_CONCURRENCY)
+        .max(1) as usize;
+    let mut expired = Vec::new();
+    let mut unresolved = prepared.len();
+    while unresolved > 0 {
+        let monitoring = match context
+            .message_monitor
+            .fetch_next_monitor_results(&queue, MonitorFetchWaitMode::AtLeastOne)
+            .await
+        {
+            Ok(monitoring) => monitoring,
+            Err(err) => {
+                let _ = context.message_monitor.cancel_monitor(&queue);
+                let unresolved = prepared
+                    .iter()
+                    .filter(|message| !expired.contains(&message.index));
+                fail_unresolved(unresolved, &ClientError::from(err), results, progress);
+                return expired;
+            }
+        };
+        unresolved = unresolved.saturating_sub(monitoring.len());
+        let fetched = futures::stream::iter(monitoring.iter())
+            .filter_map(|monitoring| {
+                let message = monitoring
+                    .user_data
+                    .as_ref()
+                    .and_then(|i| i.as_u64())
+                    .and_then(|i| prepared.get(i as usize));
+                futures::future::ready(message.map(|message| (message, monitoring)))
+            })
+            .map(|(message, monitoring)| async move {
+                let params = &params.messages[message.index];
+                let result = match monitoring.status {
+                    MessageMonitoringStatus::Finalized => {
+                        fetch_result(context, params, message, monitoring).await
+                    }
+                    _ => Err(Error::message_expired(
+                        &message.message.message_id,
+                        "",
+                        message.wait_until,
+                        (context.env.now_ms() / 1000) as u32,
+                        &message.dst,
+                    )),
+                };
+                (message, result)
+            })
+            .buffer_unordered(max_concurrency)
+            .collect::<Vec<_>>()
+            .await;
+        for (message, result) in fetched {
+            let retry = match &result {
+                Err(err) => {
+                    (err.code == crate::processing::ErrorCode::MessageExpired as u32
+                        || is_retryable_by_policy(context, RetryOperation::Resend, err.code))
+                        && can_retry_expired_message(context, try_index)
+                }
+                Ok(_) => false,
+            };
+            if retry {
+                let params = &params.messages[message.index];
+                if params.send_events {
+                    if let Err(err) = &result {
+                        callback(ProcessingEvent::MessageExpired {
+                            message_id: message.message.message_id.clone(),
+                            message_dst: message.message.address.clone(),
+                            message: message.message.message.clone(),
+                            error: err.clone(),
+                        })
+                        .await;
+                    }
+                }
+                expired.push(message.index);
+                continue;
+            }
+            if result.is_ok() {
+                progress.succeeded += 1;
+            } else {
+                progress.failed += 1;
+            }
+            results[message.index] = Some(result);
+        }
+        if params.send_events {
+            callback(progress.event()).await;
+        }
+    }
+    let _ = context.message_monitor.cancel_monitor(&queue);
+    expired
+}
+
+pub async fn process_messages<F: futures::Future<Output = ()> + Send>(
+    context: Arc<ClientContext>,
+    params: ParamsOfProcessMessages,
+    callback: impl Fn(ProcessingEvent) -> F + Send + Sync + 'static,
+) -> ClientResult<ResultOfProcessMessages> {
+    if let Some(index) = params
+        .messages
+        .iter()
+        .position(|message| message.scheduling.is_some())
+    {
+        return Err(Error::scheduled_message_in_batch(index));
+    }
+    let max_concurrency = params
+        .max_concurrency
+        .unwrap_or(DEFAULT_MAX_CONCURRENCY)
+        .max(1) as usize;
+    let mut results = params.messages.iter().map(|_| None).collect::<Vec<_>>();
+    let mut progress = BatchProgress {
+        total: params.messages.len() as u32,
+        ..Default::default()
+    };
+
+    let mut pending = (0..params.messages.len()).collect::<Vec<_>>();
+    let mut try_index = 0;
+    while !pending.is_empty() {
+        let encoded = futures::stream::iter(pending.iter())
+            .map(|index| {
+                prepare_message(
+                    &context,
+                    *index,
+                    &params.messages[*index],
+                    try_index,
+                    &callback,
+                )
+            })
+            .buffered(max_concurrency)
+            .collect::<Vec<_>>()
+            .await;
+        let mut prepared = Vec::new();
+        for (index, result) in pending.iter().zip(encoded) {
+            match result {
+                Ok(message) => prepared.push(message),
+                Err(err) => {
+                    results[*index] = Some(Err(err));
+                    progress.failed += 1;
+                }
+            }
+        }
+        progress.encoded += prepared.len() as u32;
+        if params.send_events {
+            callback(progress.event()).await;
+        }
+        if prepared.is_empty() {
+            break;
+        }
+        pending = process_round(
+            &context,
+            &params,
+            prepared,
+            try_index,
+            &mut results,
+            &mut progress,
+            &callback,
+        )
+        .await;
+        if !pending.is_empty() {
+            wait_before_retry(&context, RetryOperation::Resend, try_index as u32).await;
+        }
+        try_index = try_index.checked_add(1).unwrap_or(try_index);
+    }
+
+    Ok(ResultOfProcessMessages {
+        results: results
+            .into_iter()
+            .map(|result| result.map(ProcessMessageOutcome::from).unwrap_or_default())
+            .collect(),
+    })
+}
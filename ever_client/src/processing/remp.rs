@@ -1,4 +1,5 @@
 use super::ProcessingEvent;
+use ever_client_processing::RempTimelineEntry;
 
 
 #[derive(Deserialize, Debug, Clone)]
@@ -57,4 +58,45 @@ impl RempStatus {
             },
         }
     }
-}
\ No newline at end of file
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RempStatus::RejectedByFullnode(_) => "RejectedByFullnode",
+            RempStatus::SentToValidators(_) => "SentToValidators",
+            RempStatus::IncludedIntoBlock(_) => "IncludedIntoBlock",
+            RempStatus::IncludedIntoAcceptedBlock(_) => "IncludedIntoAcceptedBlock",
+            RempStatus::Finalized(_) => "Finalized",
+            RempStatus::Other(_) => "Other",
+        }
+    }
+
+    pub fn data(&self) -> &RempStatusData {
+        match self {
+            RempStatus::RejectedByFullnode(data)
+            | RempStatus::SentToValidators(data)
+            | RempStatus::IncludedIntoBlock(data)
+            | RempStatus::IncludedIntoAcceptedBlock(data)
+            | RempStatus::Finalized(data)
+            | RempStatus::Other(data) => data,
+        }
+    }
+
+    pub fn timeline_entry(&self) -> RempTimelineEntry {
+        let data = self.data();
+        let source = data.json["source_id"]
+            .as_str()
+            .or_else(|| data.json["source"].as_str());
+        let rejection_reason = match self {
+            RempStatus::RejectedByFullnode(_) => {
+                Some(data.json["error"].as_str().unwrap_or("unknown error"))
+            }
+            _ => data.json["error"].as_str(),
+        };
+        RempTimelineEntry {
+            kind: self.kind().to_string(),
+            timestamp: data.timestamp,
+            source: source.map(|source| source.to_string()),
+            rejection_reason: rejection_reason.map(|reason| reason.to_string()),
+        }
+    }
+}
@@ -0,0 +1,164 @@
+/*
+ * Copyright 2018-2021 EverX Labs Ltd.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ *
+ */
+
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Upper bounds (inclusive) of the latency histogram buckets in ms.
+/// Latencies above the last bound are counted in the overflow bucket.
+const LATENCY_BUCKETS_MS: [u64; 9] = [100, 250, 500, 1000, 2500, 5000, 10000, 30000, 60000];
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct RempLatencyBucket {
+    /// Upper bound of the bucket in ms (inclusive).
+    ///
+    /// Missing for the overflow bucket.
+    pub le_ms: Option<u64>,
+
+    /// Number of the statuses in the bucket.
+    pub count: u64,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct RempStageLatency {
+    /// REMP status kind, e.g. `SentToValidators`, `Finalized`.
+    pub stage: String,
+
+    /// Number of the received statuses.
+    pub count: u64,
+
+    /// Minimal latency in ms.
+    pub min_ms: u64,
+
+    /// Maximal latency in ms.
+    pub max_ms: u64,
+
+    /// Average latency in ms.
+    pub avg_ms: u64,
+
+    /// Latency histogram.
+    pub buckets: Vec<RempLatencyBucket>,
+}
+
+#[derive(Default)]
+struct StageLatency {
+    count: u64,
+    sum_ms: u64,
+    min_ms: u64,
+    max_ms: u64,
+    buckets: [u64; LATENCY_BUCKETS_MS.len() + 1],
+}
+
+impl StageLatency {
+    fn record(&mut self, latency_ms: u64) {
+        if self.count == 0 || latency_ms < self.min_ms {
+            self.min_ms = latency_ms;
+        }
+        self.max_ms = std::cmp::max(self.max_ms, latency_ms);
+        self.count += 1;
+        self.sum_ms = self.sum_ms.saturating_add(latency_ms);
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|le_ms| latency_ms <= *le_ms)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.buckets[bucket] += 1;
+    }
+
+    fn stats(&self, stage: &str) -> RempStageLatency {
+        let buckets = self
+            .buckets
+            .iter()
+            .enumerate()
+            .map(|(i, count)| RempLatencyBucket {
+                le_ms: LATENCY_BUCKETS_MS.get(i).cloned(),
+                count: *count,
+            })
+            .collect();
+        RempStageLatency {
+            stage: stage.to_string(),
+            count: self.count,
+            min_ms: self.min_ms,
+            max_ms: self.max_ms,
+            avg_ms: if self.count > 0 {
+                self.sum_ms / self.count
+            } else {
+                0
+            },
+            buckets,
+        }
+    }
+}
+
+/// Latencies of the REMP statuses collected by `process_message` and
+/// `wait_for_transaction`. Latency of a status is measured from the moment
+/// the client has started waiting for the message.
+#[derive(Default)]
+pub(crate) struct RempMetrics {
+    stages: Mutex<HashMap<String, StageLatency>>,
+}
+
+impl RempMetrics {
+    pub fn record(&self, stage: &str, latency_ms: u64) {
+        self.stages
+            .lock()
+            .unwrap()
+            .entry(stage.to_string())
+            .or_default()
+            .record(latency_ms);
+    }
+
+    pub fn stats(&self, reset: bool) -> Vec<RempStageLatency> {
+        let mut stages = self.stages.lock().unwrap();
+        let mut result = stages
+            .iter()
+            .map(|(stage, latency)| latency.stats(stage))
+            .collect::<Vec<_>>();
+        result.sort_by(|a, b| a.stage.cmp(&b.stage));
+        if reset {
+            stages.clear();
+        }
+        result
+    }
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct ParamsOfGetRempLatencyStats {
+    /// Clear the collected statistics after reading. Default is `false`.
+    pub reset: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct ResultOfGetRempLatencyStats {
+    /// Latency statistics per REMP status kind.
+    pub stages: Vec<RempStageLatency>,
+}
+
+/// Returns the latency statistics of the REMP statuses received by the context.
+///
+/// Latency of a status is the time passed from the start of the message waiting
+/// till the status timestamp. The start time is corrected with the difference between
+/// the server and the client clocks, since the status timestamps are set by the server.
+/// Statistics is collected for the messages processed with `process_message` and
+/// `wait_for_transaction` on the networks with REMP enabled.
+#[api_function]
+pub fn get_remp_latency_stats(
+    context: Arc<ClientContext>,
+    params: ParamsOfGetRempLatencyStats,
+) -> ClientResult<ResultOfGetRempLatencyStats> {
+    Ok(ResultOfGetRempLatencyStats {
+        stages: context.remp_metrics.stats(params.reset.unwrap_or_default()),
+    })
+}
@@ -18,7 +18,7 @@ use crate::boc::internal::{deserialize_object_from_boc, DeserializedObject};
 use crate::client::ClientContext;
 use crate::encoding::{base64_decode, hex_decode};
 use crate::error::{AddNetworkUrl, ClientResult};
-use crate::net::EndpointStat;
+use crate::net::{EndpointSendingResult, EndpointStat, MessageSendingStrategy};
 use crate::processing::internal::get_message_expiration_time;
 use crate::processing::types::ProcessingEvent;
 use crate::processing::Error;
@@ -64,6 +64,74 @@ pub struct ResultOfSendMessage {
     /// This list id must be used as a parameter of the
     /// `wait_for_transaction`.
     pub sending_endpoints: Vec<String>,
+
+    /// Results of sending the message to each of the used endpoints.
+    ///
+    /// Unlike `sending_endpoints`, contains the endpoints which have rejected the message
+    /// with the failover sending strategies. Endpoints which could not be reached at all
+    /// are not listed.
+    pub endpoint_results: Vec<EndpointSendingResult>,
+}
+
+pub(crate) struct EndpointsSending {
+    /// Endpoints which have received the message.
+    pub sending_endpoints: Vec<String>,
+    /// Results of all endpoints the message was sent to.
+    pub results: Vec<EndpointSendingResult>,
+}
+
+/// Sends the message to the endpoints in the order and the quantity defined by the strategy.
+///
+/// The endpoint could deliver the message even if it has returned an error, so only
+/// the failover strategies do not consider the rejecting endpoint as the sending one
+/// and switch to the next endpoint.
+pub(crate) async fn send_by_strategy<F>(
+    strategy: MessageSendingStrategy,
+    sending_endpoint_count: usize,
+    addresses: &[String],
+    send_to_address: impl Fn(String) -> F,
+) -> ClientResult<EndpointsSending>
+where
+    F: futures::Future<Output = ClientResult<EndpointSendingResult>>,
+{
+    let (chunk_size, succeeded_limit, failover) = match strategy {
+        MessageSendingStrategy::Random => (sending_endpoint_count, sending_endpoint_count, false),
+        MessageSendingStrategy::Broadcast => {
+            (std::cmp::max(addresses.len(), 1), addresses.len(), false)
+        }
+        MessageSendingStrategy::RoundRobin | MessageSendingStrategy::PrimaryWithFailover => {
+            (1, 1, true)
+        }
+    };
+    let mut last_error = None;
+    let mut sending = EndpointsSending {
+        sending_endpoints: Vec::new(),
+        results: Vec::new(),
+    };
+    'sending: for selected_addresses in addresses.chunks(chunk_size) {
+        let futures = selected_addresses
+            .iter()
+            .map(|address| send_to_address(address.clone()))
+            .collect::<Vec<_>>();
+        for result in futures::future::join_all(futures).await {
+            match result {
+                Ok(result) => {
+                    if result.accepted || !failover {
+                        sending.sending_endpoints.push(result.endpoint.clone());
+                    }
+                    sending.results.push(result);
+                    if sending.sending_endpoints.len() >= succeeded_limit {
+                        break 'sending;
+                    }
+                }
+                Err(err) => last_error = Some(err),
+            }
+        }
+    }
+    if sending.results.len() > 0 {
+        return Ok(sending);
+    }
+    Err(last_error.unwrap_or_else(|| Error::block_not_found("no endpoints".to_string())))
 }
 
 #[derive(Clone)]
@@ -145,64 +213,50 @@ impl SendingMessage {
         Ok(shard_block_id)
     }
 
-    async fn send(&self, context: &Arc<ClientContext>) -> ClientResult<Vec<String>> {
+    async fn send(&self, context: &Arc<ClientContext>) -> ClientResult<EndpointsSending> {
         let net = context.get_server_link()?;
         let endpoint = net.state().get_query_endpoint().await?;
         if endpoint.remp_enabled() {
-            let address = endpoint.query_url.clone();
-            return net
+            let result = net
                 .send_message(&hex_decode(&self.id)?, &self.body, Some(&endpoint))
                 .await
                 .add_endpoint_from_context(&context, &endpoint)
-                .await
-                .map(|_| vec![address]);
+                .await?;
+            return Ok(EndpointsSending {
+                sending_endpoints: vec![result.endpoint.clone()],
+                results: vec![result],
+            });
         }
 
-        let addresses = context.get_server_link()?.get_addresses_for_sending().await;
-        let mut last_result = None::<ClientResult<String>>;
-        let succeeded_limit = context.config.network.sending_endpoint_count as usize;
-        let mut succeeded = Vec::new();
-        'sending: for selected_addresses in addresses.chunks(succeeded_limit) {
-            let mut futures = vec![];
-            for address in selected_addresses {
+        let strategy = context.config.network.sending_strategy.unwrap_or_default();
+        let addresses = net.state().get_addresses_for_strategy(strategy).await;
+        send_by_strategy(
+            strategy,
+            context.config.network.sending_endpoint_count as usize,
+            &addresses,
+            |address| {
                 let context = context.clone();
                 let message = self.clone();
-                futures.push(Box::pin(async move {
-                    let result = message.send_to_address(context.clone(), address).await;
+                async move {
+                    let result = message.send_to_address(context.clone(), &address).await;
                     if result.is_err() {
                         context
                             .get_server_link()?
-                            .update_stat(&[address.to_owned()], EndpointStat::MessageUndelivered)
+                            .update_stat(&[address], EndpointStat::MessageUndelivered)
                             .await;
                     }
                     result
-                }));
-            }
-            for result in futures::future::join_all(futures).await {
-                if let Ok(address) = &result {
-                    succeeded.push(address.clone());
-                    if succeeded.len() >= succeeded_limit {
-                        break 'sending;
-                    }
                 }
-                last_result = Some(result);
-            }
-        }
-        if succeeded.len() > 0 {
-            return Ok(succeeded);
-        }
-        Err(if let Some(Err(err)) = last_result {
-            err
-        } else {
-            Error::block_not_found("no endpoints".to_string())
-        })
+            },
+        )
+        .await
     }
 
     async fn send_to_address(
         &self,
         context: Arc<ClientContext>,
         address: &str,
-    ) -> ClientResult<String> {
+    ) -> ClientResult<EndpointSendingResult> {
         let link = context.get_server_link()?;
         let endpoint = if let Some(endpoint) = link.state().get_resolved_endpoint(address).await {
             endpoint
@@ -215,7 +269,10 @@ impl SendingMessage {
             .await
             .add_endpoint_from_context(&context, &endpoint)
             .await
-            .map(|_| address.to_string())
+            .map(|result| EndpointSendingResult {
+                endpoint: address.to_string(),
+                ..result
+            })
     }
 }
 
@@ -252,8 +309,9 @@ pub async fn send_message<F: futures::Future<Output = ()> + Send>(
         })
         .await;
     }
-    result.map(|sending_endpoints| ResultOfSendMessage {
+    result.map(|sending| ResultOfSendMessage {
         shard_block_id,
-        sending_endpoints,
+        sending_endpoints: sending.sending_endpoints,
+        endpoint_results: sending.results,
     })
 }
@@ -14,6 +14,8 @@
 
 use crate::client::ClientContext;
 use crate::error::{AddNetworkUrl, ClientResult};
+use crate::processing::outbox::{Outbox, OutboxMessageStatus};
+use crate::processing::Error;
 use serde_json::Value;
 use std::sync::Arc;
 use ever_client_processing::{MessageMonitoringParams, MonitoredMessage};
@@ -30,6 +32,14 @@ pub struct MessageSendingParams {
     /// User defined data associated with this message.
     /// Helps to identify this message when user received `MessageMonitoringResult`.
     pub user_data: Option<Value>,
+
+    /// Wait for the whole tree of transactions caused by the message
+    /// before reporting the monitoring result. Default is `false`.
+    pub wait_transaction_tree: Option<bool>,
+
+    /// Collect the REMP statuses of the message into
+    /// `MessageMonitoringResult.remp_timeline`. Default is `false`.
+    pub collect_remp_timeline: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
@@ -40,6 +50,14 @@ pub struct ParamsOfSendMessages {
     /// Optional message monitor queue that starts monitoring for the processing
     /// results for sent messages.
     pub monitor_queue: Option<String>,
+
+    /// Record messages into the persistent outbox before sending.
+    ///
+    /// Recorded messages stay in the outbox until their monitoring results are fetched
+    /// with `fetch_next_monitor_results`, so they can be restored into the monitoring
+    /// queue with `restore_outbox` after the application restart.
+    /// Requires `monitor_queue`. Default is `false`.
+    pub persist: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, ApiType, Default, PartialEq, Debug)]
@@ -59,18 +77,33 @@ pub async fn send_messages(
     let messages = params
         .messages
         .iter()
-        .map(|x| {
-            context
-                .bocs
-                .resolve_boc_with_hash(&x.boc, "message")
-                .unwrap()
-        })
-        .collect();
-    server_link
+        .map(|x| context.bocs.resolve_boc_with_hash(&x.boc, "message"))
+        .collect::<ClientResult<Vec<_>>>()?;
+    let outbox = match (params.persist.unwrap_or(false), &params.monitor_queue) {
+        (false, _) => None,
+        (true, Some(queue)) => {
+            let outbox = Outbox::obtain(&context).await?;
+            let records = messages
+                .iter()
+                .zip(params.messages.iter())
+                .map(|((hash, _), message)| (hash.as_hex_string(), message.clone()))
+                .collect::<Vec<_>>();
+            outbox.add(queue, &records).await?;
+            Some((outbox, records.into_iter().map(|x| x.0).collect::<Vec<_>>()))
+        }
+        (true, None) => return Err(Error::monitor_queue_required("persist")),
+    };
+    let sent = server_link
         .send_messages(messages, Some(&endpoint))
         .await
         .add_endpoint_from_context(&context, &endpoint)
-        .await?;
+        .await;
+    if let Err(err) = sent {
+        if let Some((outbox, hashes)) = outbox {
+            outbox.remove(&hashes).await?;
+        }
+        return Err(err);
+    }
     let messages = params
         .messages
         .into_iter()
@@ -78,12 +111,19 @@ pub async fn send_messages(
             message: MonitoredMessage::Boc { boc: x.boc },
             wait_until: x.wait_until,
             user_data: x.user_data,
+            wait_transaction_tree: x.wait_transaction_tree,
+            collect_remp_timeline: x.collect_remp_timeline,
         })
         .collect::<Vec<_>>();
     if let Some(queue) = params.monitor_queue {
         context
             .message_monitor
             .monitor_messages(&queue, messages.clone())?;
+        if let Some((outbox, hashes)) = outbox {
+            outbox
+                .set_status(&hashes, OutboxMessageStatus::Monitoring)
+                .await?;
+        }
     }
     Ok(ResultOfSendMessages { messages })
 }
@@ -0,0 +1,145 @@
+/*
+ * Copyright 2018-2021 EverX Labs Ltd.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ *
+ */
+
+use crate::abi::{CallSet, FunctionHeader, ParamsOfEncodeMessage};
+use crate::encoding::account_decode;
+use crate::error::ClientResult;
+use crate::processing::Error;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+/// Address state is dropped after this period without the scheduled messages.
+const IDLE_ADDRESS_TIMEOUT_MS: u64 = 10 * 60 * 1000;
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct SendSchedulingOptions {
+    /// Name of the function input parameter that receives the sequence number.
+    ///
+    /// If specified, the scheduler puts the next sequence number of the address
+    /// into this input parameter. The sequence number is incremented after
+    /// each successfully processed message. Expired messages are re-encoded
+    /// with the same sequence number.
+    pub seqno_param: Option<String>,
+
+    /// Sequence number to start from.
+    ///
+    /// Required with `seqno_param` for the first message scheduled for the address
+    /// and after the address has been idle for 10 minutes.
+    /// If specified for the next messages, it replaces the sequence number tracked
+    /// by the scheduler.
+    pub seqno: Option<u64>,
+}
+
+impl SendSchedulingOptions {
+    /// Messages with the sequence number can be sent only after the previous one
+    /// has been processed.
+    pub fn is_sequential(&self) -> bool {
+        self.seqno_param.is_some()
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct AddressSendState {
+    last_time: u64,
+    next_seqno: Option<u64>,
+}
+
+impl AddressSendState {
+    /// Assigns the message `time` header greater than the one of the previous
+    /// message of this address and the sequence number if required.
+    pub fn prepare(
+        &mut self,
+        now_ms: u64,
+        options: &SendSchedulingOptions,
+        params: &mut ParamsOfEncodeMessage,
+    ) -> ClientResult<()> {
+        let call_set = params.call_set.get_or_insert_with(CallSet::default);
+        if let Some(time) = call_set.header.as_ref().and_then(|header| header.time) {
+            return Err(Error::scheduled_message_time_specified(time));
+        }
+        if let Some(seqno_param) = &options.seqno_param {
+            let seqno = self
+                .next_seqno
+                .ok_or_else(|| Error::scheduled_message_seqno_missing())?;
+            let input = call_set.input.get_or_insert_with(|| json!({}));
+            if !input.is_object() {
+                return Err(Error::scheduled_message_input_not_object());
+            }
+            input[seqno_param] = json!(seqno);
+        }
+
+        let time = std::cmp::max(now_ms, self.last_time + 1);
+        self.last_time = time;
+        call_set
+            .header
+            .get_or_insert_with(FunctionHeader::default)
+            .time = Some(time);
+        Ok(())
+    }
+
+    pub fn message_processed(&mut self) {
+        self.next_seqno = self.next_seqno.map(|seqno| seqno + 1);
+    }
+}
+
+/// Orders the messages sent to the same address.
+///
+/// Each address has its own state which is locked while the message is encoded
+/// and sent, so the messages get the increasing `time` headers in the sending
+/// order and the next message is sent while the previous one is awaited.
+/// Messages with the sequence number keep the state locked until they are
+/// processed.
+///
+/// States of the idle addresses are dropped.
+#[derive(Default)]
+pub(crate) struct SendScheduler {
+    addresses: std::sync::Mutex<HashMap<String, Arc<Mutex<AddressSendState>>>>,
+}
+
+impl SendScheduler {
+    pub async fn lock(
+        &self,
+        now_ms: u64,
+        params: &ParamsOfEncodeMessage,
+        options: &SendSchedulingOptions,
+    ) -> ClientResult<OwnedMutexGuard<AddressSendState>> {
+        let address = params
+            .address
+            .as_ref()
+            .ok_or_else(|| Error::scheduled_message_address_missing())?;
+        let address = account_decode(address)?.to_string();
+        let state = {
+            let mut addresses = self.addresses.lock().unwrap();
+            // state is in use while someone else holds or awaits it
+            addresses.retain(|_, state| {
+                Arc::strong_count(state) > 1
+                    || state.try_lock().map_or(true, |state| {
+                        now_ms <= state.last_time + IDLE_ADDRESS_TIMEOUT_MS
+                    })
+            });
+            addresses.entry(address).or_default().clone()
+        };
+        let mut state = state.lock_owned().await;
+        if options.seqno.is_some() {
+            state.next_seqno = options.seqno;
+        }
+        Ok(state)
+    }
+
+    #[cfg(test)]
+    pub fn address_count(&self) -> usize {
+        self.addresses.lock().unwrap().len()
+    }
+}
@@ -9,7 +9,7 @@ use crate::net::{ParamsOfQuery, ResultOfQuery};
 use crate::processing::types::DecodedOutput;
 use crate::processing::{
     ErrorCode, ParamsOfProcessMessage, ParamsOfSendMessage, ParamsOfWaitForTransaction,
-    ProcessingEvent, ProcessingResponseType,
+    PreflightOptions, ProcessingEvent, ProcessingResponseType,
 };
 use crate::tests::GIVER_V2;
 use crate::tests::{TestClient, EVENTS_OLD, HELLO};
@@ -23,6 +23,7 @@ use ever_block::{Serializable, StateInit};
 fn processing_event_name(e: Option<&ProcessingEvent>) -> &str {
     if let Some(e) = e {
         match e {
+            ProcessingEvent::PreflightEstimated { .. } => "PreflightEstimated",
             ProcessingEvent::DidSend { .. } => "DidSend",
             ProcessingEvent::FetchFirstBlockFailed { .. } => "FetchFirstBlockFailed",
             ProcessingEvent::FetchNextBlockFailed { .. } => "FetchNextBlockFailed",
@@ -38,6 +39,7 @@ fn processing_event_name(e: Option<&ProcessingEvent>) -> &str {
             }
             ProcessingEvent::RempOther { .. } => "RempOther",
             ProcessingEvent::RempError { .. } => "RempError",
+            ProcessingEvent::BatchProgress { .. } => "BatchProgress",
         }
     } else {
         ""
@@ -240,6 +242,7 @@ async fn test_process_message() {
             ParamsOfProcessMessage {
                 message_encode_params: encode_params,
                 send_events: true,
+                ..Default::default()
             },
             callback,
         )
@@ -285,6 +288,7 @@ async fn test_process_message() {
                     ..Default::default()
                 },
                 send_events: true,
+                ..Default::default()
             },
             callback,
         )
@@ -317,6 +321,101 @@ async fn test_process_message() {
     assert_events(&events.lock().await, remp_enabled);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_process_message_preflight() {
+    let client = TestClient::new();
+    let (abi, tvc) = TestClient::package(EVENTS_OLD, Some(2));
+    let keys = client.generate_sign_keys();
+    let address = client
+        .deploy_with_giver_async(
+            ParamsOfEncodeMessage {
+                abi: abi.clone(),
+                deploy_set: DeploySet::some_with_tvc(tvc),
+                call_set: CallSet::some_with_function("constructor"),
+                signer: Signer::Keys { keys: keys.clone() },
+                ..Default::default()
+            },
+            None,
+        )
+        .await;
+
+    let events = std::sync::Arc::new(tokio::sync::Mutex::new(vec![]));
+    let events_copy = events.clone();
+    let callback = move |result: ProcessingEvent, _: ProcessingResponseType| {
+        let events_copy = events_copy.clone();
+        async move {
+            events_copy.lock().await.push(result);
+        }
+    };
+    let call_params = ParamsOfEncodeMessage {
+        abi: abi.clone(),
+        address: Some(address),
+        call_set: CallSet::some_with_function_and_input("returnValue", json!({ "id": "0x1" })),
+        signer: Signer::Keys { keys },
+        ..Default::default()
+    };
+
+    let error = client
+        .net_process_message(
+            ParamsOfProcessMessage {
+                message_encode_params: call_params.clone(),
+                preflight: Some(PreflightOptions {
+                    max_fees: Some("one".to_owned()),
+                }),
+                ..Default::default()
+            },
+            TestClient::default_callback,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidPreflightMaxFees as u32);
+    assert_eq!(error.data["max_fees"], "one");
+
+    let error = client
+        .net_process_message(
+            ParamsOfProcessMessage {
+                message_encode_params: call_params.clone(),
+                send_events: true,
+                preflight: Some(PreflightOptions {
+                    max_fees: Some("1".to_owned()),
+                }),
+                ..Default::default()
+            },
+            callback.clone(),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::PreflightFeesLimitExceeded as u32);
+    {
+        let events = events.lock().await;
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            ProcessingEvent::PreflightEstimated { fees, .. } => assert!(fees.account_fees > 1),
+            event => panic!("unexpected event: {:?}", event),
+        }
+    }
+
+    let output = client
+        .net_process_message(
+            ParamsOfProcessMessage {
+                message_encode_params: call_params,
+                send_events: true,
+                preflight: Some(PreflightOptions {
+                    max_fees: Some(1_000_000_000.to_string()),
+                }),
+                ..Default::default()
+            },
+            callback,
+        )
+        .await
+        .unwrap();
+    assert_eq!(output.out_messages.len(), 2);
+    assert_eq!(
+        processing_event_name(events.lock().await.get(1)),
+        "PreflightEstimated"
+    );
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_error_resolving() {
     // skip on Evernode SE since it behaves different to real node
@@ -384,6 +483,7 @@ async fn test_error_resolving() {
             ParamsOfProcessMessage {
                 message_encode_params: deploy_params.clone(),
                 send_events: false,
+                ..Default::default()
             },
             TestClient::default_callback,
         )
@@ -411,6 +511,7 @@ async fn test_error_resolving() {
             ParamsOfProcessMessage {
                 message_encode_params: deploy_params.clone(),
                 send_events: false,
+                ..Default::default()
             },
             TestClient::default_callback,
         )
@@ -444,6 +545,7 @@ async fn test_error_resolving() {
             ParamsOfProcessMessage {
                 message_encode_params: run_params.clone(),
                 send_events: false,
+                ..Default::default()
             },
             TestClient::default_callback,
         )
@@ -467,6 +569,7 @@ async fn test_error_resolving() {
             ParamsOfProcessMessage {
                 message_encode_params: deploy_params.clone(),
                 send_events: false,
+                ..Default::default()
             },
             TestClient::default_callback,
         )
@@ -480,6 +583,7 @@ async fn test_error_resolving() {
             ParamsOfProcessMessage {
                 message_encode_params: run_params.clone(),
                 send_events: false,
+                ..Default::default()
             },
             TestClient::default_callback,
         )
@@ -552,6 +656,7 @@ async fn test_retries() {
                             ..Default::default()
                         },
                         send_events: false,
+                        ..Default::default()
                     },
                     TestClient::default_callback,
                 )
@@ -624,6 +729,7 @@ async fn test_fees() {
             ParamsOfProcessMessage {
                 message_encode_params: params,
                 send_events: false,
+                ..Default::default()
             },
             TestClient::default_callback,
         )
@@ -713,6 +819,7 @@ async fn test_deploy_from_tvc_v1() {
             ParamsOfProcessMessage {
                 message_encode_params: encode_params,
                 send_events: false,
+                ..Default::default()
             },
             |_: ProcessingEvent, _: ProcessingResponseType| async {},
         )
@@ -735,6 +842,7 @@ async fn test_deploy_from_tvc_v1() {
                     ..Default::default()
                 },
                 send_events: false,
+                ..Default::default()
             },
             move |_: ProcessingEvent, _: ProcessingResponseType| async {},
         )
@@ -798,6 +906,7 @@ fn test_process_message_sync() {
             ParamsOfProcessMessage {
                 message_encode_params: encode_params,
                 send_events: true,
+                ..Default::default()
             },
         )
         .unwrap();
@@ -828,6 +937,7 @@ fn test_process_message_sync() {
                     ..Default::default()
                 },
                 send_events: true,
+                ..Default::default()
             },
         )
         .unwrap();
@@ -855,3 +965,613 @@ fn test_process_message_sync() {
         })
     );
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_outbox() {
+    use crate::client::storage::{InMemoryKeyValueStorage, KeyValueStorage};
+    use crate::processing::outbox::{Outbox, OutboxMessageStatus};
+    use crate::processing::{
+        MessageMonitoringResult, MessageMonitoringStatus, MessageSendingParams,
+    };
+
+    let storage = std::sync::Arc::new(InMemoryKeyValueStorage::new());
+    let outbox = Outbox::new(storage.clone());
+    let message = |boc: &str| MessageSendingParams {
+        boc: boc.to_owned(),
+        wait_until: 100,
+        user_data: Some(json!({ "id": boc })),
+        wait_transaction_tree: None,
+        collect_remp_timeline: None,
+    };
+    outbox
+        .add(
+            "payouts",
+            &[
+                ("01".to_owned(), message("boc1")),
+                ("02".to_owned(), message("boc2")),
+            ],
+        )
+        .await
+        .unwrap();
+    outbox
+        .set_status(&["01".to_owned()], OutboxMessageStatus::Monitoring)
+        .await
+        .unwrap();
+
+    let unresolved = outbox.unresolved().await.unwrap();
+    assert_eq!(unresolved.len(), 2);
+    assert_eq!(unresolved[0].queue, "payouts");
+    assert_eq!(unresolved[0].status, OutboxMessageStatus::Monitoring);
+    assert_eq!(unresolved[1].status, OutboxMessageStatus::Sending);
+    assert_eq!(
+        unresolved[1].monitoring_params().user_data,
+        Some(json!({ "id": "boc2" }))
+    );
+
+    // messages sent by this process are already monitored
+    assert!(outbox.claim_unmonitored().await.unwrap().is_empty());
+    // restored messages are monitored only once after restart
+    let restarted = Outbox::new(storage.clone());
+    assert_eq!(restarted.claim_unmonitored().await.unwrap().len(), 2);
+    assert!(restarted.claim_unmonitored().await.unwrap().is_empty());
+
+    outbox
+        .resolve(&[MessageMonitoringResult {
+            hash: "01".to_owned(),
+            status: MessageMonitoringStatus::Finalized,
+            transaction: None,
+            error: None,
+            user_data: None,
+            transaction_tree: None,
+            remp_timeline: None,
+        }])
+        .await
+        .unwrap();
+    let unresolved = outbox.unresolved().await.unwrap();
+    assert_eq!(unresolved.len(), 1);
+    assert_eq!(unresolved[0].hash, "02");
+
+    storage.put_str("message_02", "corrupted").await.unwrap();
+    assert!(outbox.unresolved().await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_subscribe_monitor_results() {
+    use crate::net::SubscriptionResponseType;
+    use crate::processing::{
+        MessageMonitoringResult, MessageMonitoringStatus, MessageSendingParams,
+        ParamsOfSendMessages, ParamsOfSubscribeMonitorResults, ResultOfSendMessages,
+        ResultOfSubscribeMonitorResults,
+    };
+
+    let client = TestClient::new();
+    let keys = client.generate_sign_keys();
+    let encoded = client
+        .encode_message(ParamsOfEncodeMessage {
+            abi: TestClient::abi(HELLO, None),
+            deploy_set: DeploySet::some_with_tvc(TestClient::tvc(HELLO, None)),
+            call_set: CallSet::some_with_function("constructor"),
+            signer: Signer::Keys { keys },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    client
+        .get_tokens_from_giver_async(&encoded.address, None)
+        .await;
+
+    let results = std::sync::Arc::new(tokio::sync::Mutex::new(vec![]));
+    let results_copy = results.clone();
+    let callback = move |result: serde_json::Value, response_type: SubscriptionResponseType| {
+        let results = results_copy.clone();
+        async move {
+            if let SubscriptionResponseType::Ok = response_type {
+                let result = serde_json::from_value::<MessageMonitoringResult>(result).unwrap();
+                results.lock().await.push(result);
+            }
+        }
+    };
+    let subscription: ResultOfSubscribeMonitorResults = client
+        .request_async_callback(
+            "processing.subscribe_monitor_results",
+            ParamsOfSubscribeMonitorResults {
+                queue: "subscribed".to_owned(),
+            },
+            callback,
+        )
+        .await
+        .unwrap();
+
+    let _: ResultOfSendMessages = client
+        .request_async(
+            "processing.send_messages",
+            ParamsOfSendMessages {
+                messages: vec![MessageSendingParams {
+                    boc: encoded.message,
+                    wait_until: (client.context().env.now_ms() / 1000) as u32 + 60,
+                    user_data: Some(json!({ "id": 1 })),
+                    wait_transaction_tree: None,
+                    collect_remp_timeline: None,
+                }],
+                monitor_queue: Some("subscribed".to_owned()),
+                persist: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    for _ in 0..60 {
+        if !results.lock().await.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
+    }
+    {
+        let results = results.lock().await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status, MessageMonitoringStatus::Finalized);
+        assert_eq!(results[0].user_data, Some(json!({ "id": 1 })));
+    }
+
+    let _: () = client
+        .request_async("processing.unsubscribe_monitor_results", subscription)
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_send_scheduler() {
+    use crate::processing::{SendScheduler, SendSchedulingOptions};
+
+    let scheduler = std::sync::Arc::new(SendScheduler::default());
+    let params = ParamsOfEncodeMessage {
+        address: Some(
+            "0:1111111111111111111111111111111111111111111111111111111111111111".to_owned(),
+        ),
+        call_set: CallSet::some_with_function_and_input("sendTransaction", json!({})),
+        ..Default::default()
+    };
+    let options = SendSchedulingOptions {
+        seqno_param: Some("seqno".to_owned()),
+        seqno: Some(5),
+    };
+
+    let mut state = scheduler.lock(1000, &params, &options).await.unwrap();
+    let mut first = params.clone();
+    state.prepare(1000, &options, &mut first).unwrap();
+    // the same time is shifted to keep the order of the messages
+    let mut retried = params.clone();
+    state.prepare(1000, &options, &mut retried).unwrap();
+    state.message_processed();
+
+    let first_call_set = first.call_set.unwrap();
+    assert_eq!(first_call_set.input, Some(json!({ "seqno": 5 })));
+    assert_eq!(first_call_set.header.unwrap().time, Some(1000));
+    let retried_call_set = retried.call_set.unwrap();
+    assert_eq!(retried_call_set.input, Some(json!({ "seqno": 5 })));
+    assert_eq!(retried_call_set.header.unwrap().time, Some(1001));
+
+    // the next message to the same address waits for the previous one
+    let scheduler_copy = scheduler.clone();
+    let params_copy = params.clone();
+    let locked = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let locked_copy = locked.clone();
+    let next = tokio::spawn(async move {
+        let options = SendSchedulingOptions {
+            seqno_param: Some("seqno".to_owned()),
+            seqno: None,
+        };
+        let mut state = scheduler_copy
+            .lock(500, &params_copy, &options)
+            .await
+            .unwrap();
+        locked_copy.store(true, std::sync::atomic::Ordering::SeqCst);
+        let mut next = params_copy.clone();
+        state.prepare(500, &options, &mut next).unwrap();
+        next.call_set.unwrap()
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert!(!locked.load(std::sync::atomic::Ordering::SeqCst));
+    drop(state);
+
+    let next_call_set = next.await.unwrap();
+    assert_eq!(next_call_set.input, Some(json!({ "seqno": 6 })));
+    assert_eq!(next_call_set.header.unwrap().time, Some(1002));
+
+    // `time` header is assigned by the scheduler only
+    let mut with_time = params.clone();
+    with_time.call_set.as_mut().unwrap().header = Some(FunctionHeader {
+        time: Some(2000),
+        ..Default::default()
+    });
+    let mut state = scheduler.lock(2000, &params, &options).await.unwrap();
+    let error = state.prepare(2000, &options, &mut with_time).unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidSendScheduling as u32);
+    assert_eq!(error.data["time"], 2000);
+    drop(state);
+
+    // idle address state is dropped
+    let other = ParamsOfEncodeMessage {
+        address: Some(
+            "0:2222222222222222222222222222222222222222222222222222222222222222".to_owned(),
+        ),
+        ..params.clone()
+    };
+    let state = scheduler.lock(3000, &other, &options).await.unwrap();
+    assert_eq!(scheduler.address_count(), 2);
+    drop(state);
+    let _state = scheduler
+        .lock(3000 + 11 * 60 * 1000, &other, &options)
+        .await
+        .unwrap();
+    assert_eq!(scheduler.address_count(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_send_by_strategy() {
+    use crate::net::{EndpointSendingResult, MessageSendingStrategy};
+    use crate::processing::send_message::send_by_strategy;
+
+    let addresses = vec!["a".to_owned(), "b".to_owned(), "c".to_owned()];
+    // endpoint "a" rejects the message, "c" can not be reached
+    let send = |address: String| async move {
+        match address.as_str() {
+            "c" => Err(crate::client::Error::http_request_send_error(
+                "Network error",
+            )),
+            _ => Ok(EndpointSendingResult {
+                accepted: address != "a",
+                endpoint: address,
+                ..Default::default()
+            }),
+        }
+    };
+    let endpoints = |results: &[EndpointSendingResult]| {
+        results
+            .iter()
+            .map(|result| result.endpoint.clone())
+            .collect::<Vec<_>>()
+    };
+
+    let sending = send_by_strategy(
+        MessageSendingStrategy::PrimaryWithFailover,
+        1,
+        &addresses,
+        send,
+    )
+    .await
+    .unwrap();
+    assert_eq!(sending.sending_endpoints, vec!["b"]);
+    assert_eq!(endpoints(&sending.results), vec!["a", "b"]);
+    assert!(!sending.results[0].accepted);
+
+    // rejecting endpoint could deliver the message, so it is used without failover
+    let sending = send_by_strategy(MessageSendingStrategy::Broadcast, 1, &addresses, send)
+        .await
+        .unwrap();
+    assert_eq!(sending.sending_endpoints, vec!["a", "b"]);
+    assert_eq!(endpoints(&sending.results), vec!["a", "b"]);
+
+    let sending = send_by_strategy(MessageSendingStrategy::Random, 1, &addresses, send)
+        .await
+        .unwrap();
+    assert_eq!(sending.sending_endpoints, vec!["a"]);
+
+    let error = send_by_strategy(
+        MessageSendingStrategy::PrimaryWithFailover,
+        1,
+        &addresses[2..],
+        send,
+    )
+    .await
+    .err()
+    .unwrap();
+    assert!(error.message.contains("Network error"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_bounces() {
+    use crate::processing::BounceTrackingOptions;
+
+    let client = TestClient::new();
+    let (abi, tvc) = TestClient::package(GIVER_V2, Some(2));
+    let keys = client.generate_sign_keys();
+    let address = client
+        .deploy_with_giver_async(
+            ParamsOfEncodeMessage {
+                abi: abi.clone(),
+                deploy_set: DeploySet::some_with_tvc(tvc),
+                call_set: CallSet::some_with_function("constructor"),
+                signer: Signer::Keys { keys: keys.clone() },
+                ..Default::default()
+            },
+            None,
+        )
+        .await;
+
+    // bounceable message to the account which does not exist is bounced
+    let missing = format!("0:{}", client.generate_sign_keys().public);
+    let result = client
+        .net_process_message(
+            ParamsOfProcessMessage {
+                message_encode_params: ParamsOfEncodeMessage {
+                    abi,
+                    address: Some(address),
+                    call_set: CallSet::some_with_function_and_input(
+                        "sendTransaction",
+                        json!({
+                            "dest": missing,
+                            "value": 500_000_000u64,
+                            "bounce": true
+                        }),
+                    ),
+                    signer: Signer::Keys { keys },
+                    ..Default::default()
+                },
+                bounces: Some(BounceTrackingOptions::default()),
+                ..Default::default()
+            },
+            TestClient::default_callback,
+        )
+        .await
+        .unwrap();
+
+    let bounced = result.bounced.unwrap();
+    assert_eq!(bounced.len(), 1);
+    assert_eq!(bounced[0].dst, missing);
+    assert!(!bounced[0].transaction_id.is_empty());
+    assert!(bounced[0].bounce_message_id.is_some());
+    let value = bounced[0].value.as_ref().unwrap().parse::<u64>().unwrap();
+    assert!(value > 0 && value < 500_000_000);
+    // the message has no body, so there is no function to resolve
+    assert_eq!(bounced[0].function_id, None);
+    assert_eq!(bounced[0].function_name, None);
+}
+
+#[test]
+fn test_bounced_function_id() {
+    use crate::boc::internal::serialize_cell_to_base64;
+    use crate::processing::bounces::{bounced_function_id, resolve_function_name};
+    use ever_block::BuilderData;
+
+    let client = TestClient::new();
+    let abi = TestClient::abi(EVENTS_OLD, Some(2));
+    let function_id = abi
+        .abi()
+        .unwrap()
+        .function("returnValue")
+        .unwrap()
+        .get_input_id();
+
+    let mut body = BuilderData::new();
+    body.append_u32(0xFFFFFFFF).unwrap();
+    body.append_u32(function_id).unwrap();
+    body.append_u32(1).unwrap();
+    let body = serialize_cell_to_base64(&body.into_cell().unwrap(), "body").unwrap();
+
+    let decoded = bounced_function_id(&client.context(), &body);
+    assert_eq!(decoded, Some(function_id));
+    assert_eq!(
+        resolve_function_name(&[abi], function_id),
+        Some("returnValue".to_owned())
+    );
+
+    let mut body = BuilderData::new();
+    body.append_u32(function_id).unwrap();
+    let body = serialize_cell_to_base64(&body.into_cell().unwrap(), "body").unwrap();
+    assert_eq!(bounced_function_id(&client.context(), &body), None);
+}
+
+#[test]
+fn test_remp_latency_stats() {
+    use crate::processing::{ParamsOfGetRempLatencyStats, ResultOfGetRempLatencyStats};
+
+    let client = TestClient::new();
+    let context = client.context();
+    context.remp_metrics.record("SentToValidators", 80);
+    context.remp_metrics.record("SentToValidators", 400);
+    context.remp_metrics.record("Finalized", 90000);
+
+    let result: ResultOfGetRempLatencyStats = client
+        .request(
+            "processing.get_remp_latency_stats",
+            ParamsOfGetRempLatencyStats { reset: Some(true) },
+        )
+        .unwrap();
+    assert_eq!(result.stages.len(), 2);
+
+    let finalized = &result.stages[0];
+    assert_eq!(finalized.stage, "Finalized");
+    assert_eq!(finalized.count, 1);
+    assert_eq!(finalized.buckets.last().unwrap().le_ms, None);
+    assert_eq!(finalized.buckets.last().unwrap().count, 1);
+
+    let sent = &result.stages[1];
+    assert_eq!(sent.stage, "SentToValidators");
+    assert_eq!(
+        (sent.count, sent.min_ms, sent.max_ms, sent.avg_ms),
+        (2, 80, 400, 240)
+    );
+    assert_eq!(sent.buckets[0].count, 1);
+    assert_eq!(sent.buckets[2].le_ms, Some(500));
+    assert_eq!(sent.buckets[2].count, 1);
+
+    let result: ResultOfGetRempLatencyStats = client
+        .request(
+            "processing.get_remp_latency_stats",
+            ParamsOfGetRempLatencyStats::default(),
+        )
+        .unwrap();
+    assert!(result.stages.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_plan_expiration() {
+    use crate::processing::{ParamsOfPlanExpiration, ResultOfPlanExpiration};
+
+    let client = TestClient::new();
+    let plan: ResultOfPlanExpiration = client
+        .request_async(
+            "processing.plan_expiration",
+            ParamsOfPlanExpiration {
+                broadcast_delay: Some(60000),
+                broadcast_window: Some(30000),
+                wait_until_margin: None,
+                abi: None,
+            },
+        )
+        .await
+        .unwrap();
+    let time = plan.time.unwrap();
+    let expire = plan.expire.unwrap() as u64 * 1000;
+    assert!(time >= plan.network_time + 60000);
+    assert!(expire >= time + 30000 + plan.block_interval as u64);
+    assert!(plan.wait_until >= plan.expire.unwrap() + 15);
+
+    let plan: ResultOfPlanExpiration = client
+        .request_async(
+            "processing.plan_expiration",
+            ParamsOfPlanExpiration {
+                wait_until_margin: Some(0),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(Some(plan.wait_until), plan.expire);
+
+    let abi = TestClient::abi(HELLO, None);
+    let expire_header = abi
+        .abi()
+        .unwrap()
+        .header()
+        .iter()
+        .any(|x| x.name == "expire");
+    let plan: ResultOfPlanExpiration = client
+        .request_async(
+            "processing.plan_expiration",
+            ParamsOfPlanExpiration {
+                abi: Some(abi),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(plan.expire.is_some(), expire_header);
+
+    let error = client
+        .request_async::<_, ResultOfPlanExpiration>(
+            "processing.plan_expiration",
+            ParamsOfPlanExpiration {
+                broadcast_window: Some(0),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidBroadcastWindow as u32);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_process_messages() {
+    use crate::processing::{ParamsOfProcessMessages, ResultOfProcessMessages};
+
+    let client = TestClient::new();
+    let mut messages = Vec::new();
+    for _ in 0..2 {
+        let encode_params = ParamsOfEncodeMessage {
+            abi: TestClient::abi(HELLO, None),
+            deploy_set: DeploySet::some_with_tvc(TestClient::tvc(HELLO, None)),
+            call_set: CallSet::some_with_function("constructor"),
+            signer: Signer::Keys {
+                keys: client.generate_sign_keys(),
+            },
+            ..Default::default()
+        };
+        let encoded = client.encode_message(encode_params.clone()).await.unwrap();
+        client
+            .get_tokens_from_giver_async(&encoded.address, None)
+            .await;
+        messages.push(ParamsOfProcessMessage {
+            message_encode_params: encode_params,
+            send_events: false,
+            ..Default::default()
+        });
+    }
+    // this message can not be encoded
+    messages.insert(
+        1,
+        ParamsOfProcessMessage {
+            message_encode_params: ParamsOfEncodeMessage {
+                abi: TestClient::abi(HELLO, None),
+                call_set: CallSet::some_with_function("unknownFunction"),
+                address: Some(format!("0:{:064}", 0)),
+                signer: Signer::None,
+                ..Default::default()
+            },
+            send_events: false,
+            ..Default::default()
+        },
+    );
+
+    let events = std::sync::Arc::new(tokio::sync::Mutex::new(vec![]));
+    let events_copy = events.clone();
+    let callback = move |event: ProcessingEvent, _: ProcessingResponseType| {
+        let events = events_copy.clone();
+        async move {
+            events.lock().await.push(event);
+        }
+    };
+    let result: ResultOfProcessMessages = client
+        .request_async_callback(
+            "processing.process_messages",
+            ParamsOfProcessMessages {
+                messages,
+                max_concurrency: Some(2),
+                send_events: true,
+            },
+            callback,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result.results.len(), 3);
+    assert!(result.results[0].result.is_some());
+    assert!(result.results[1].error.is_some());
+    assert!(result.results[2].result.is_some());
+    assert!(result.results[2].error.is_none());
+
+    match events.lock().await.last() {
+        Some(ProcessingEvent::BatchProgress {
+            total,
+            succeeded,
+            failed,
+            ..
+        }) => assert_eq!((*total, *succeeded, *failed), (3, 2, 1)),
+        event => panic!("unexpected last event: {:?}", event),
+    }
+
+    let error = client
+        .request_async::<_, ResultOfProcessMessages>(
+            "processing.process_messages",
+            ParamsOfProcessMessages {
+                messages: vec![ParamsOfProcessMessage {
+                    message_encode_params: ParamsOfEncodeMessage {
+                        abi: TestClient::abi(HELLO, None),
+                        call_set: CallSet::some_with_function("touch"),
+                        address: Some(format!("0:{:064}", 0)),
+                        signer: Signer::None,
+                        ..Default::default()
+                    },
+                    scheduling: Some(Default::default()),
+                    ..Default::default()
+                }],
+                max_concurrency: None,
+                send_events: false,
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidSendScheduling as u32);
+    assert_eq!(error.data["message_index"], 0);
+}
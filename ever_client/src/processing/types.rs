@@ -1,4 +1,5 @@
 use crate::abi::DecodedMessageBody;
+use crate::processing::{BouncedMessage, RempTimelineEntry};
 use crate::error::ClientError;
 use serde_json::Value;
 use ever_sdk::TransactionFees;
@@ -33,6 +34,16 @@ pub struct ResultOfProcessMessage {
 
     /// Transaction fees
     pub fees: TransactionFees,
+
+    /// Bounced out messages.
+    ///
+    /// Present if `ParamsOfProcessMessage.bounces` is specified.
+    pub bounced: Option<Vec<BouncedMessage>>,
+
+    /// REMP statuses received while waiting for the transaction.
+    ///
+    /// Present if the message has been waited with REMP.
+    pub remp_timeline: Option<Vec<RempTimelineEntry>>,
 }
 
 #[derive(Clone, num_derive::FromPrimitive, PartialEq, Debug)]
@@ -43,6 +54,17 @@ pub enum ProcessingResponseType {
 #[derive(Serialize, Deserialize, ApiType, Debug, Clone)]
 #[serde(tag = "type")]
 pub enum ProcessingEvent {
+    /// Notifies the app about the result of the local message execution
+    /// performed in pre-flight mode before the message is sent.
+    ///
+    /// Contains the estimated transaction fees and the current account balance.
+    PreflightEstimated {
+        message_id: String,
+        message_dst: String,
+        fees: TransactionFees,
+        balance: String,
+    },
+
     /// Notifies the application that the account's current shard block will be fetched
     /// from the network.
     /// This step is performed before the message sending so that sdk knows starting
@@ -190,5 +212,17 @@ pub enum ProcessingEvent {
         message_id: String,
         message_dst: String,
         error: ClientError,
-    }
+    },
+
+    /// Notifies the app about the progress of `process_messages`.
+    ///
+    /// `encoded` and `sent` include the expired messages which have been
+    /// re-encoded and resent.
+    BatchProgress {
+        total: u32,
+        encoded: u32,
+        sent: u32,
+        succeeded: u32,
+        failed: u32,
+    },
 }
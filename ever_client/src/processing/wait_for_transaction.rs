@@ -6,7 +6,7 @@ use crate::error::{AddNetworkUrl, ClientResult};
 use crate::net::{EndpointStat, ResultOfSubscription};
 use crate::processing::internal::{get_message_expiration_time, resolve_error};
 use crate::processing::{fetching, internal, Error};
-use crate::processing::{ProcessingEvent, ResultOfProcessMessage};
+use crate::processing::{ProcessingEvent, RempTimelineEntry, ResultOfProcessMessage};
 use futures::{FutureExt, StreamExt};
 use std::convert::TryInto;
 use std::sync::Arc;
@@ -55,8 +55,9 @@ pub async fn wait_for_transaction<F: futures::Future<Output = ()> + Send>(
 
     let callback = Arc::new(callback);
 
-    if net.state().get_query_endpoint().await?.remp_enabled() {
-        wait_by_remp(context, params, callback).await
+    let endpoint = net.state().get_query_endpoint().await?;
+    if endpoint.remp_enabled() {
+        wait_by_remp(context, params, callback, endpoint.server_time_delta()).await
     } else {
         wait_by_block_walking(context, &params, callback).await
     }
@@ -66,6 +67,7 @@ async fn wait_by_remp<F: futures::Future<Output = ()> + Send>(
     context: Arc<ClientContext>,
     params: ParamsOfWaitForTransaction,
     callback: Arc<impl Fn(ProcessingEvent) -> F + Send + Sync>,
+    server_time_delta: i64,
 ) -> ClientResult<ResultOfProcessMessage> {
     // fallback to block walking in case of any error
     let notify = tokio::sync::Notify::new();
@@ -133,6 +135,10 @@ async fn wait_by_remp<F: futures::Future<Output = ()> + Send>(
 
     // wait for REMP statuses and process them
     // if no statuses received during timeout or any error occurred then activate fallback
+    // REMP status timestamps are set by the server, so the latencies are measured
+    // from the start converted to the server time
+    let start_ms = (context.env.now_ms() as i64 + server_time_delta) as u64;
+    let mut timeline = Vec::new();
     let mut timeout = context.config.network.first_remp_status_timeout;
     let mut fallback_activated = false;
     loop {
@@ -163,7 +169,9 @@ async fn wait_by_remp<F: futures::Future<Output = ()> + Send>(
                     &params,
                     &message_id,
                     &message_dst,
-                    remp_message
+                    remp_message,
+                    start_ms,
+                    &mut timeline,
                 ).await {
                     Err(error) => {
                         if params.send_events {
@@ -186,7 +194,12 @@ async fn wait_by_remp<F: futures::Future<Output = ()> + Send>(
                 let _ = crate::net::unsubscribe(context.clone(), subscription).await;
             }
 
-            return result;
+            return result.map(|mut result| {
+                if !timeline.is_empty() {
+                    result.remp_timeline = Some(timeline);
+                }
+                result
+            });
         }
     }
 }
@@ -198,11 +211,18 @@ async fn process_remp_message<F: futures::Future<Output = ()> + Send>(
     message_id: &str,
     message_dst: &MsgAddressInt,
     remp_message: ClientResult<serde_json::Value>,
+    start_ms: u64,
+    timeline: &mut Vec<RempTimelineEntry>,
 ) -> ClientResult<Option<ClientResult<ResultOfProcessMessage>>> {
     let remp_message = remp_message?;
     let status: RempStatus = serde_json::from_value(remp_message).map_err(|err| {
         Error::invalid_remp_status(format!("can not parse REMP status message: {}", err))
     })?;
+    let entry = status.timeline_entry();
+    context
+        .remp_metrics
+        .record(&entry.kind, entry.timestamp.saturating_sub(start_ms));
+    timeline.push(entry);
 
     match status {
         RempStatus::RejectedByFullnode(data) => Ok(Some(
@@ -703,6 +703,7 @@ impl TestClient {
                     ..Default::default()
                 },
                 send_events: false,
+                ..Default::default()
             },
             Self::default_callback,
         )
@@ -820,6 +821,7 @@ impl TestClient {
                 ParamsOfProcessMessage {
                     message_encode_params: params,
                     send_events: false,
+                    ..Default::default()
                 },
                 Self::default_callback,
             )
@@ -94,17 +94,23 @@ pub(crate) fn call_tvm(
     let gas_limit = 1_000_000_000;
     let gas = Gas::new(gas_limit, 0, gas_limit, 10);
 
+    let libraries = options.libraries_dict()?;
     let mut engine = Engine::with_capabilities(
         options.blockchain_config.capabilites()
-    ).setup(
+    ).setup_with_libraries(
         slice_from_cell(code)?,
         Some(ctrls),
         Some(stack),
         Some(gas),
+        vec![libraries],
     );
 
     engine.set_signature_id(options.signature_id);
     engine.modify_behavior(options.behavior_modifiers);
+    if let Some(coverage) = &options.coverage {
+        let trace = coverage.start_run();
+        engine.set_trace_callback(move |engine, info| trace(engine, info));
+    }
     // engine.set_trace_callback(move |engine, info| { trace_callback(engine, info, true); });
 
     match engine.execute() {
@@ -0,0 +1,234 @@
+/*
+ * Copyright 2018-2021 EverX Labs Ltd.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ *
+ */
+
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use crate::tvm::Error;
+use ever_block::UInt256;
+use ever_vm::executor::{Engine, EngineTraceInfo, EngineTraceInfoType};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+pub(crate) type TraceCallback = dyn Fn(&Engine, &EngineTraceInfo) + Send + Sync;
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct InstructionCoverage {
+    /// Offset of the instruction from the beginning of the code cell in bits.
+    pub offset: u32,
+
+    /// Number of the instruction executions.
+    pub hits: u64,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct CodeCellCoverage {
+    /// Representation hash of the code cell. Encoded as `hex`.
+    pub cell_hash: String,
+
+    /// Length of the code cell data in bits.
+    pub bit_length: u32,
+
+    /// Executed instructions of the cell ordered by offset.
+    pub instructions: Vec<InstructionCoverage>,
+}
+
+#[derive(Default)]
+struct CellHits {
+    bit_length: usize,
+    offsets: BTreeMap<usize, u64>,
+}
+
+/// Coverage session of the context. Accumulates executed instructions
+/// of all the runs with `ExecutionOptions.collect_coverage` enabled.
+#[derive(Default)]
+pub(crate) struct CoverageSession {
+    runs: AtomicU64,
+    cells: Mutex<HashMap<UInt256, CellHits>>,
+}
+
+impl CoverageSession {
+    /// Registers a new run and returns the trace callback which records
+    /// the executed instructions of this run.
+    pub fn start_run(self: &Arc<Self>) -> Arc<TraceCallback> {
+        self.runs.fetch_add(1, Ordering::Relaxed);
+        let session = self.clone();
+        Arc::new(move |_: &Engine, info: &EngineTraceInfo| session.record(info))
+    }
+
+    fn record(&self, info: &EngineTraceInfo) {
+        if info.info_type != EngineTraceInfoType::Normal {
+            return;
+        }
+        let cell = info.cmd_code.cell();
+        let mut cells = self.cells.lock().unwrap();
+        let hits = cells.entry(cell.repr_hash()).or_insert_with(|| CellHits {
+            bit_length: cell.bit_length(),
+            offsets: BTreeMap::new(),
+        });
+        *hits.offsets.entry(info.cmd_code.pos()).or_default() += 1;
+    }
+
+    fn snapshot(&self, reset: bool) -> (u64, Vec<CodeCellCoverage>) {
+        let mut cells = self.cells.lock().unwrap();
+        let runs = if reset {
+            self.runs.swap(0, Ordering::Relaxed)
+        } else {
+            self.runs.load(Ordering::Relaxed)
+        };
+        let mut result = cells
+            .iter()
+            .map(|(hash, hits)| CodeCellCoverage {
+                cell_hash: hash.as_hex_string(),
+                bit_length: hits.bit_length as u32,
+                instructions: hits
+                    .offsets
+                    .iter()
+                    .map(|(offset, hits)| InstructionCoverage {
+                        offset: *offset as u32,
+                        hits: *hits,
+                    })
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+        result.sort_by(|a, b| a.cell_hash.cmp(&b.cell_hash));
+        if reset {
+            cells.clear();
+        }
+        (runs, result)
+    }
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct ParamsOfGetCoverage {
+    /// Clear the collected coverage after reading. Default is `false`.
+    pub reset: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct ResultOfGetCoverage {
+    /// Number of the runs with the coverage collection enabled.
+    pub runs: u64,
+
+    /// Executed code cells ordered by cell hash.
+    pub cells: Vec<CodeCellCoverage>,
+}
+
+/// Returns the code coverage collected by the context.
+///
+/// Coverage is collected for `run_executor`, `run_tvm` and `run_get`
+/// calls with `ExecutionOptions.collect_coverage` set to `true` and is
+/// accumulated across all such runs until it is reset.
+#[api_function]
+pub fn get_coverage(
+    context: Arc<ClientContext>,
+    params: ParamsOfGetCoverage,
+) -> ClientResult<ResultOfGetCoverage> {
+    let (runs, cells) = context.coverage.snapshot(params.reset.unwrap_or_default());
+    Ok(ResultOfGetCoverage { runs, cells })
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct SourceMapEntry {
+    /// Representation hash of the code cell. Encoded as `hex`.
+    pub cell_hash: String,
+
+    /// Offset of the instruction from the beginning of the code cell in bits.
+    pub offset: u32,
+
+    /// Source file name.
+    pub file: String,
+
+    /// Source line number.
+    pub line: u32,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct ParamsOfExportCoverageLcov {
+    /// Source map of the contract code, e.g. produced from the compiler debug info.
+    ///
+    /// Lines of the instructions which were never executed are reported with zero hits.
+    pub source_map: Vec<SourceMapEntry>,
+
+    /// LCOV test name. Default is empty.
+    pub test_name: Option<String>,
+
+    /// Clear the collected coverage after reading. Default is `false`.
+    pub reset: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct ResultOfExportCoverageLcov {
+    /// Coverage report in LCOV tracefile format.
+    pub lcov: String,
+}
+
+/// Exports the code coverage collected by the context in LCOV format.
+///
+/// Instructions are mapped to the source lines with the provided source map.
+/// Hits of the several instructions mapped to the same line are summed up.
+/// Executed instructions missing in the source map are skipped.
+#[api_function]
+pub fn export_coverage_lcov(
+    context: Arc<ClientContext>,
+    params: ParamsOfExportCoverageLcov,
+) -> ClientResult<ResultOfExportCoverageLcov> {
+    if params.source_map.is_empty() {
+        return Err(Error::empty_coverage_source_map());
+    }
+    let (_, cells) = context.coverage.snapshot(params.reset.unwrap_or_default());
+    let hits = cells
+        .iter()
+        .flat_map(|cell| {
+            cell.instructions
+                .iter()
+                .map(move |instr| ((cell.cell_hash.clone(), instr.offset), instr.hits))
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut files = BTreeMap::<&str, BTreeMap<u32, u64>>::new();
+    for entry in &params.source_map {
+        let line_hits = files
+            .entry(entry.file.as_str())
+            .or_default()
+            .entry(entry.line)
+            .or_default();
+        *line_hits += hits
+            .get(&(entry.cell_hash.to_lowercase(), entry.offset))
+            .cloned()
+            .unwrap_or_default();
+    }
+
+    let mut lcov = String::new();
+    for (file, lines) in files {
+        let _ = writeln!(
+            lcov,
+            "TN:{}",
+            params.test_name.as_deref().unwrap_or_default()
+        );
+        let _ = writeln!(lcov, "SF:{}", file);
+        for (line, hits) in &lines {
+            let _ = writeln!(lcov, "DA:{},{}", line, hits);
+        }
+        let _ = writeln!(lcov, "LF:{}", lines.len());
+        let _ = writeln!(
+            lcov,
+            "LH:{}",
+            lines.values().filter(|hits| **hits > 0).count()
+        );
+        let _ = writeln!(lcov, "end_of_record");
+    }
+    Ok(ResultOfExportCoverageLcov { lcov })
+}
@@ -35,6 +35,13 @@ pub enum ErrorCode {
     InvalidMessageType = 413,
     ContractExecutionError = 414,
     AccountIsSuspended = 415,
+    InvalidOutputStack = 416,
+    ForkNotFound = 417,
+    ForkAccountModified = 418,
+    InvalidCoverageSourceMap = 419,
+    ForkBlockNotFound = 420,
+    LibraryNotFound = 421,
+    InvalidForkOptions = 422,
 }
 pub struct Error;
 
@@ -49,6 +56,12 @@ impl Error {
             format!("Invalid JSON value for stack item ({}): {}", stack, err),
         )
     }
+    pub fn invalid_output_stack<E: Display>(err: E) -> ClientError {
+        error(
+            ErrorCode::InvalidOutputStack,
+            format!("Invalid stack item returned by get-method: {}", err),
+        )
+    }
     pub fn invalid_account_boc<E: Display>(err: E) -> ClientError {
         error(
             ErrorCode::InvalidAccountBoc,
@@ -262,6 +275,65 @@ impl Error {
         error
     }
 
+    pub fn fork_not_found(fork: u32) -> ClientError {
+        error(ErrorCode::ForkNotFound, format!("Fork {} is not found", fork))
+    }
+
+    pub fn fork_block_not_found(seq_no: Option<u32>) -> ClientError {
+        let mut error = error(
+            ErrorCode::ForkBlockNotFound,
+            "Masterchain block to start the fork from is not found".to_owned(),
+        );
+        error.data = serde_json::json!({ "seq_no": seq_no });
+        error
+    }
+
+    pub fn libraries_not_found(hashes: &[String]) -> ClientError {
+        let mut error = error(
+            ErrorCode::LibraryNotFound,
+            format!(
+                "Libraries are not found in `libraries` and in the masterchain: {}",
+                hashes.join(", ")
+            ),
+        );
+        error.data = serde_json::json!({ "hashes": hashes });
+        error
+    }
+
+    pub fn invalid_fork_options(options: &[&str]) -> ClientError {
+        let mut error = error(
+            ErrorCode::InvalidForkOptions,
+            format!(
+                "Execution options are not supported by the fork: {}",
+                options.join(", ")
+            ),
+        );
+        error.data = serde_json::json!({ "options": options });
+        error
+    }
+
+    pub fn fork_account_modified(address: &MsgAddressInt, last_trans_lt: u64) -> ClientError {
+        let mut error = error(
+            ErrorCode::ForkAccountModified,
+            format!(
+                "Account {} was modified after the fork block (last transaction lt {})",
+                address, last_trans_lt
+            ),
+        );
+        error.data = serde_json::json!({
+            "account_address": address.to_string(),
+            "last_trans_lt": last_trans_lt.to_string(),
+        });
+        error
+    }
+
+    pub fn empty_coverage_source_map() -> ClientError {
+        error(
+            ErrorCode::InvalidCoverageSourceMap,
+            "Coverage source map can not be empty".to_owned(),
+        )
+    }
+
     pub fn low_balance(address: &MsgAddressInt, balance: u64) -> ClientError {
         let mut error = error(
             ErrorCode::LowBalance,
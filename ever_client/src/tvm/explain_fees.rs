@@ -0,0 +1,370 @@
+/*
+ * Copyright 2018-2021 EverX Labs Ltd.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ *
+ */
+
+use super::types::resolve_network_params;
+use crate::boc::blockchain_config::{
+    decode_fwd_prices, decode_gas_prices, decode_storage_prices, GasLimitsPricesConfig,
+    MsgForwardPricesConfig, StoragePricesConfig,
+};
+use crate::boc::internal::deserialize_object_from_boc;
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use crate::tvm::Error;
+use ever_block::{
+    Account, Cell, ConfigParamEnum, Grams, Message, MsgAddressInt, Serializable, StorageUsedShort,
+    TrComputePhase, Transaction, TransactionDescr,
+};
+use ever_executor::{BlockchainConfig, CalcMsgFwdFees};
+use ever_sdk::TransactionFees;
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct ParamsOfExplainFees {
+    /// Transaction BOC or BOC cache reference.
+    pub transaction: String,
+
+    /// Account BOC or BOC cache reference with the account state before the transaction.
+    ///
+    /// Required to explain the storage fee since the transaction does not contain
+    /// the storage period and the account size.
+    pub account: Option<String>,
+
+    /// Blockchain config BOC encoded as `base64` with the config the transaction
+    /// was executed with. Default is the current network config.
+    pub blockchain_config: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct StorageFeeExplanation {
+    /// Storage fee collected by the transaction in nanotokens.
+    pub fees_collected: String,
+
+    /// Storage due left unpaid by the transaction in nanotokens.
+    pub fees_due: Option<String>,
+
+    /// Unix time of the previous storage payment. Missing if `account` is not provided.
+    pub paid_since: Option<u32>,
+
+    /// Unix time of the transaction.
+    pub paid_until: u32,
+
+    /// Number of bits of the account state charged. Missing if `account` is not provided.
+    pub bits: Option<u64>,
+
+    /// Number of cells of the account state charged. Missing if `account` is not provided.
+    pub cells: Option<u64>,
+
+    /// Storage fee of the period calculated with the storage prices in nanotokens.
+    /// Missing if `account` is not provided.
+    ///
+    /// Differs from `fees_collected` if the account had a storage due or
+    /// the balance was not enough to pay the fee.
+    pub calculated_fee: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct GasFeeExplanation {
+    /// Amount of gas used by the compute phase.
+    pub gas_used: String,
+
+    /// Gas limit of the compute phase.
+    pub gas_limit: String,
+
+    /// Amount of gas charged with the flat price.
+    pub flat_gas_limit: u64,
+
+    /// Flat price of the first `flat_gas_limit` gas units in nanotokens.
+    pub flat_gas_price: u64,
+
+    /// Price of the gas unit above `flat_gas_limit` in 2^-16 nanotokens.
+    pub gas_price: u64,
+
+    /// Gas fee collected by the transaction in nanotokens.
+    pub gas_fees: String,
+
+    /// Gas fee calculated as `flat_gas_price + (gas_used - flat_gas_limit) * gas_price`
+    /// in nanotokens.
+    pub calculated_fee: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct MessageFeeExplanation {
+    /// Message hash.
+    pub hash: String,
+
+    /// Number of bits of the message charged. Bits of the root cell are not charged.
+    pub bits: u64,
+
+    /// Number of cells of the message charged. The root cell is not charged.
+    pub cells: u64,
+
+    /// Forward fee calculated as `lump_price + bits * bit_price + cells * cell_price`
+    /// in nanotokens. Masterchain prices are used if the message source or destination
+    /// is in the masterchain.
+    pub fwd_fee: String,
+
+    /// Part of `fwd_fee` collected by the validators of the transaction in nanotokens.
+    ///
+    /// Equals `fwd_fee` for the external messages and `fwd_fee * first_frac`
+    /// for the internal ones.
+    pub action_fee: String,
+
+    /// Part of `fwd_fee` carried by the internal message to the next validators
+    /// in nanotokens.
+    pub remaining_fwd_fee: String,
+
+    /// Instant hypercube routing fee of the internal message in nanotokens.
+    pub ihr_fee: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct ActionFeeExplanation {
+    /// Total forward fees of the messages sent by the action phase in nanotokens.
+    pub total_fwd_fees: String,
+
+    /// Total fees of the action phase collected by the validators of the transaction
+    /// in nanotokens.
+    pub total_action_fees: String,
+
+    /// Forward fees of the outbound messages.
+    pub out_messages: Vec<MessageFeeExplanation>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct FeeConfigParams {
+    /// Storage prices (param 18).
+    pub storage_prices: Vec<StoragePricesConfig>,
+
+    /// Gas prices of the transaction workchain (param 20 or 21).
+    pub gas_prices: GasLimitsPricesConfig,
+
+    /// Message forward prices of the transaction workchain (param 24 or 25).
+    pub msg_forward_prices: MsgForwardPricesConfig,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct ResultOfExplainFees {
+    /// Total transaction fees.
+    pub fees: TransactionFees,
+
+    /// Storage phase fees. Missing if the transaction has no storage phase.
+    pub storage: Option<StorageFeeExplanation>,
+
+    /// Import fee of the inbound external message.
+    pub in_message: Option<MessageFeeExplanation>,
+
+    /// Compute phase fees. Missing if the compute phase is skipped.
+    pub compute: Option<GasFeeExplanation>,
+
+    /// Action phase fees. Missing if the transaction has no action phase.
+    pub action: Option<ActionFeeExplanation>,
+
+    /// Blockchain config params the fees are calculated with.
+    pub config: FeeConfigParams,
+}
+
+// Returns bits and cells of the message tree without the root cell
+fn message_size(cell: &Cell) -> (u64, u64) {
+    let mut storage = StorageUsedShort::default();
+    storage.append(cell);
+    (
+        storage.bits() - cell.bit_length() as u64,
+        storage.cells() - 1,
+    )
+}
+
+// Masterchain forward prices are used if the message leaves or enters the masterchain
+fn is_masterchain_message(message: &Message) -> bool {
+    let is_masterchain =
+        |address: Option<&MsgAddressInt>| address.map_or(false, |address| address.is_masterchain());
+    is_masterchain(message.src_ref()) || is_masterchain(message.dst_ref())
+}
+
+fn explain_message_fee(
+    config: &BlockchainConfig,
+    message: &Message,
+) -> ClientResult<MessageFeeExplanation> {
+    let is_masterchain = is_masterchain_message(message);
+    let cell = message
+        .serialize()
+        .map_err(|err| Error::can_not_read_transaction(err))?;
+    let (bits, cells) = message_size(&cell);
+    let fwd_fee = config
+        .calc_fwd_fee(is_masterchain, &cell)
+        .map_err(|err| Error::can_not_read_blockchain_config(err))?;
+    let (action_fee, ihr_fee) = match message.int_header() {
+        Some(header) => (
+            config.get_fwd_prices(is_masterchain).mine_fee(&fwd_fee),
+            header.ihr_fee.clone(),
+        ),
+        None => (fwd_fee.clone(), Grams::default()),
+    };
+    Ok(MessageFeeExplanation {
+        hash: cell.repr_hash().as_hex_string(),
+        bits,
+        cells,
+        fwd_fee: fwd_fee.as_u128().to_string(),
+        action_fee: action_fee.as_u128().to_string(),
+        remaining_fwd_fee: (fwd_fee.as_u128() - action_fee.as_u128()).to_string(),
+        ihr_fee: ihr_fee.as_u128().to_string(),
+    })
+}
+
+/// Explains the fees of a transaction as a line-item breakdown.
+///
+/// Fees are recalculated with the blockchain config prices and reported along with
+/// the amounts actually charged by the transaction. Only ordinary transactions
+/// are supported.
+#[api_function]
+pub async fn explain_fees(
+    context: Arc<ClientContext>,
+    params: ParamsOfExplainFees,
+) -> ClientResult<ResultOfExplainFees> {
+    let transaction =
+        deserialize_object_from_boc::<Transaction>(&context, &params.transaction, "transaction")?
+            .object;
+    let account = match &params.account {
+        Some(account) => {
+            Some(deserialize_object_from_boc::<Account>(&context, account, "account")?.object)
+        }
+        None => None,
+    };
+    let descr = match transaction
+        .read_description()
+        .map_err(|err| Error::can_not_read_transaction(err))?
+    {
+        TransactionDescr::Ordinary(descr) => descr,
+        _ => {
+            return Err(Error::can_not_read_transaction(
+                "only ordinary transactions are supported",
+            ))
+        }
+    };
+    let in_message = transaction
+        .read_in_msg()
+        .map_err(|err| Error::can_not_read_transaction(err))?;
+    let is_masterchain = in_message
+        .as_ref()
+        .and_then(|message| message.dst_ref())
+        .or_else(|| account.as_ref().and_then(|account| account.get_addr()))
+        .map(|address| address.is_masterchain())
+        .unwrap_or_default();
+
+    let config = resolve_network_params(&context, params.blockchain_config, None)
+        .await?
+        .blockchain_config;
+    let raw_config = config.raw_config();
+    let read_param = |index: u32| {
+        raw_config.config(index).map_err(|err| {
+            Error::can_not_read_blockchain_config(format!("param {}: {}", index, err))
+        })
+    };
+    let storage_prices = match read_param(18)? {
+        Some(ConfigParamEnum::ConfigParam18(param)) => decode_storage_prices(&param)?,
+        _ => return Err(Error::can_not_read_blockchain_config("param 18 is missing")),
+    };
+    let gas_config = config.get_gas_config(is_masterchain);
+
+    let storage = match &descr.storage_ph {
+        Some(phase) => {
+            let mut storage = StorageFeeExplanation {
+                fees_collected: phase.storage_fees_collected.as_u128().to_string(),
+                fees_due: phase
+                    .storage_fees_due
+                    .as_ref()
+                    .map(|due| due.as_u128().to_string()),
+                paid_until: transaction.now(),
+                ..Default::default()
+            };
+            if let Some(info) = account.as_ref().and_then(|account| account.storage_info()) {
+                let fee = config
+                    .calc_storage_fee(info, is_masterchain, transaction.now())
+                    .map_err(|err| Error::can_not_read_blockchain_config(err))?;
+                storage.paid_since = Some(info.last_paid());
+                storage.bits = Some(info.used().bits() as u64);
+                storage.cells = Some(info.used().cells() as u64);
+                storage.calculated_fee = Some(fee.as_u128().to_string());
+            }
+            Some(storage)
+        }
+        None => None,
+    };
+
+    let in_message = match in_message {
+        Some(message) if message.is_inbound_external() => {
+            Some(explain_message_fee(&config, &message)?)
+        }
+        _ => None,
+    };
+
+    let compute = match &descr.compute_ph {
+        TrComputePhase::Vm(phase) => Some(GasFeeExplanation {
+            gas_used: phase.gas_used.as_u64().to_string(),
+            gas_limit: phase.gas_limit.as_u64().to_string(),
+            flat_gas_limit: gas_config.flat_gas_limit,
+            flat_gas_price: gas_config.flat_gas_price,
+            gas_price: gas_config.gas_price,
+            gas_fees: phase.gas_fees.as_u128().to_string(),
+            calculated_fee: gas_config.calc_gas_fee(phase.gas_used.as_u64()).to_string(),
+        }),
+        TrComputePhase::Skipped(_) => None,
+    };
+
+    let action = match &descr.action {
+        Some(phase) => {
+            let mut out_messages = Vec::new();
+            for i in 0..transaction.msg_count() {
+                let message = transaction
+                    .get_out_msg(i)
+                    .map_err(|err| Error::can_not_read_transaction(err))?
+                    .ok_or_else(|| Error::can_not_read_transaction("message missing"))?;
+                out_messages.push(explain_message_fee(&config, &message)?);
+            }
+            Some(ActionFeeExplanation {
+                total_fwd_fees: phase
+                    .total_fwd_fees
+                    .as_ref()
+                    .map(|fees| fees.as_u128())
+                    .unwrap_or_default()
+                    .to_string(),
+                total_action_fees: phase
+                    .total_action_fees
+                    .as_ref()
+                    .map(|fees| fees.as_u128())
+                    .unwrap_or_default()
+                    .to_string(),
+                out_messages,
+            })
+        }
+        None => None,
+    };
+
+    let fees = ever_sdk::Transaction::try_from(&transaction)
+        .map_err(|err| Error::can_not_read_transaction(err))?
+        .calc_fees();
+
+    Ok(ResultOfExplainFees {
+        fees,
+        storage,
+        in_message,
+        compute,
+        action,
+        config: FeeConfigParams {
+            storage_prices,
+            gas_prices: decode_gas_prices(gas_config),
+            msg_forward_prices: decode_fwd_prices(config.get_fwd_prices(is_masterchain)),
+        },
+    })
+}
@@ -0,0 +1,465 @@
+/*
+ * Copyright 2018-2021 EverX Labs Ltd.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ *
+ */
+
+use super::run_message::{run_executor_internal, AccountForExecutor, ParamsOfRunExecutor};
+use super::types::{ExecutionOptions, TransactionType};
+use crate::abi::Abi;
+use crate::boc::internal::deserialize_object_from_boc;
+use crate::client::ClientContext;
+use crate::encoding::account_decode;
+use crate::error::ClientResult;
+use crate::net::{OrderBy, ParamsOfQueryCollection, SortDirection, BLOCKS_COLLECTION};
+use crate::processing::fetching::fetch_account;
+use crate::processing::DecodedOutput;
+use crate::tvm::Error;
+use ever_block::{Message, MsgAddressInt, Transaction, MASTERCHAIN_ID};
+use ever_sdk::TransactionFees;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+const DEFAULT_MAX_TRANSACTIONS: u32 = 100;
+
+/// Local simulation session started at a masterchain block.
+///
+/// Accounts are taken with their current network states. The fork block bounds
+/// the accepted states (unless `allow_newer_states` is set) and sets the time and
+/// logical time of the fork transactions.
+pub(crate) struct Fork {
+    gen_utime: u32,
+    end_lt: u64,
+    allow_newer_states: bool,
+    execution_options: ExecutionOptions,
+    next_lt: u64,
+    // account BOCs by address, `None` for the non-existing accounts
+    accounts: HashMap<String, Option<String>>,
+}
+
+impl Fork {
+    async fn account(
+        &mut self,
+        context: &Arc<ClientContext>,
+        address: &MsgAddressInt,
+    ) -> ClientResult<Option<String>> {
+        let key = address.to_string();
+        if let Some(account) = self.accounts.get(&key) {
+            return Ok(account.clone());
+        }
+        let account =
+            fetch_account(context.clone(), address, "boc last_trans_lt(format:DEC)").await?;
+        let boc = account["boc"].as_str().map(|boc| boc.to_string());
+        if boc.is_some() && !self.allow_newer_states {
+            let last_trans_lt = account["last_trans_lt"]
+                .as_str()
+                .and_then(|lt| lt.parse::<u64>().ok())
+                .unwrap_or_default();
+            if last_trans_lt > self.end_lt {
+                return Err(Error::fork_account_modified(address, last_trans_lt));
+            }
+        }
+        self.accounts.insert(key, boc.clone());
+        Ok(boc)
+    }
+
+    fn execution_options(&self, lt: u64) -> ExecutionOptions {
+        ExecutionOptions {
+            block_time: self.execution_options.block_time.or(Some(self.gen_utime)),
+            block_lt: Some(lt),
+            transaction_lt: Some(lt),
+            ..self.execution_options.clone()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone)]
+pub struct ParamsOfCreateFork {
+    /// Sequence number of the masterchain block the fork starts from.
+    /// Default is the last masterchain block.
+    ///
+    /// Account states at the block are not reconstructed: the block only sets the time
+    /// and logical time of the fork transactions and the bound of the accepted account
+    /// states (see `allow_newer_states`).
+    pub seq_no: Option<u32>,
+
+    /// Execution options of the fork transactions.
+    ///
+    /// `block_time` is the fork block time by default. `block_lt` and `transaction_lt`
+    /// are ignored since logical time of the fork transactions is advanced automatically.
+    ///
+    /// `balance`, `gas_limit`, `gas_credit` and tick-tock `transaction_type` would apply
+    /// to every transaction of the fork, so they are rejected with `InvalidForkOptions` error.
+    pub execution_options: Option<ExecutionOptions>,
+
+    /// Allow the account states modified after the fork block.
+    ///
+    /// Accounts are fetched from the network with their current states, so the access
+    /// to an account which has transactions after the fork block fails with
+    /// `ForkAccountModified` error. Set this flag to use the current states
+    /// of such accounts. Default is `false`.
+    pub allow_newer_states: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct ResultOfCreateFork {
+    /// Fork handle.
+    pub fork: u32,
+
+    /// Sequence number of the block the fork starts from.
+    pub seq_no: u32,
+
+    /// Unix time of the block the fork starts from.
+    pub gen_utime: u32,
+}
+
+async fn query_fork_block(
+    context: &Arc<ClientContext>,
+    seq_no: Option<u32>,
+) -> ClientResult<(u32, u32, u64)> {
+    let server_link = context.get_server_link()?;
+    let query_block = |mut filter: Value| {
+        if let Some(seq_no) = seq_no {
+            filter["seq_no"] = json!({ "eq": seq_no });
+        }
+        ParamsOfQueryCollection {
+            collection: BLOCKS_COLLECTION.to_string(),
+            filter: Some(filter),
+            result: "seq_no gen_utime end_lt(format:DEC)".to_string(),
+            order: Some(vec![OrderBy {
+                path: "seq_no".to_owned(),
+                direction: SortDirection::DESC,
+            }]),
+            limit: Some(1),
+        }
+    };
+    let mut blocks = server_link
+        .query_collection(
+            query_block(json!({ "workchain_id": { "eq": MASTERCHAIN_ID } })),
+            None,
+        )
+        .await?;
+    if blocks[0].is_null() && seq_no.is_none() {
+        // Evernode SE case - there is no masterchain
+        blocks = server_link
+            .query_collection(query_block(json!({ "workchain_id": { "eq": 0 } })), None)
+            .await?;
+    }
+    let block = &blocks[0];
+    match (
+        block["seq_no"].as_u64(),
+        block["gen_utime"].as_u64(),
+        block["end_lt"]
+            .as_str()
+            .and_then(|lt| lt.parse::<u64>().ok()),
+    ) {
+        (Some(seq_no), Some(gen_utime), Some(end_lt)) => {
+            Ok((seq_no as u32, gen_utime as u32, end_lt))
+        }
+        _ => Err(Error::fork_block_not_found(seq_no)),
+    }
+}
+
+/// Creates a fork of the network state at a masterchain block.
+///
+/// Fork is a local simulation session: account states are fetched from the network
+/// on the first access and cached, messages are executed locally with `fork_run_message`
+/// and the resulting account states are kept in the fork. Nothing is sent to the network.
+///
+/// Fork uses the current account states of the network and checks that they are not
+/// newer than the fork block, historical states at the block are not reconstructed.
+///
+/// Use `remove_fork` to release the fork.
+#[api_function]
+pub async fn create_fork(
+    context: Arc<ClientContext>,
+    params: ParamsOfCreateFork,
+) -> ClientResult<ResultOfCreateFork> {
+    let execution_options = params.execution_options.unwrap_or_default();
+    let unsupported = [
+        ("balance", execution_options.balance.is_some()),
+        ("gas_limit", execution_options.gas_limit.is_some()),
+        ("gas_credit", execution_options.gas_credit.is_some()),
+        (
+            "transaction_type",
+            execution_options
+                .transaction_type
+                .map_or(false, |kind| kind != TransactionType::Ordinary),
+        ),
+    ]
+    .iter()
+    .filter(|(_, set)| *set)
+    .map(|(name, _)| *name)
+    .collect::<Vec<_>>();
+    if !unsupported.is_empty() {
+        return Err(Error::invalid_fork_options(&unsupported));
+    }
+    let (seq_no, gen_utime, end_lt) = query_fork_block(&context, params.seq_no).await?;
+    let fork = Fork {
+        gen_utime,
+        end_lt,
+        allow_newer_states: params.allow_newer_states.unwrap_or_default(),
+        execution_options,
+        next_lt: end_lt + 1,
+        accounts: HashMap::new(),
+    };
+    let handle = context.get_next_id();
+    context.forks.insert(handle, tokio::sync::Mutex::new(fork));
+    Ok(ResultOfCreateFork {
+        fork: handle,
+        seq_no,
+        gen_utime,
+    })
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Clone)]
+pub struct ParamsOfForkRunMessage {
+    /// Fork handle.
+    pub fork: u32,
+
+    /// Input message BOC. Must be encoded as base64.
+    pub message: String,
+
+    /// Contract ABI for decoding output messages of the first transaction.
+    pub abi: Option<Abi>,
+
+    /// Maximum number of the transactions executed in the fork. Default is 100.
+    ///
+    /// Internal messages which are not executed within the limit are returned
+    /// in `unprocessed_messages`.
+    pub max_transactions: Option<u32>,
+
+    /// Skip transaction check flag of the first transaction.
+    ///
+    /// Transactions of the internal messages are never checked since
+    /// aborted transactions are valid for them.
+    pub skip_transaction_check: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct ForkTransaction {
+    /// Parsed transaction.
+    ///
+    /// In addition to the regular transaction fields there is a
+    /// `boc` field encoded with `base64` which contains source
+    /// transaction BOC.
+    pub transaction: Value,
+
+    /// List of output messages' BOCs. Encoded as `base64`
+    pub out_messages: Vec<String>,
+
+    /// Optional decoded message bodies according to the optional
+    /// `abi` parameter. Provided for the first transaction only.
+    pub decoded: Option<DecodedOutput>,
+
+    /// Transaction fees
+    pub fees: TransactionFees,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct ResultOfForkRunMessage {
+    /// Transactions in the order of execution.
+    pub transactions: Vec<ForkTransaction>,
+
+    /// External outbound messages produced by the transactions. Encoded as `base64`.
+    pub ext_out_messages: Vec<String>,
+
+    /// Internal messages which are not executed due to `max_transactions` limit.
+    /// Encoded as `base64`.
+    pub unprocessed_messages: Vec<String>,
+}
+
+/// Executes a message in the fork and chases the produced internal messages
+///
+/// Internal messages are executed in the order they are produced until all of them
+/// are delivered or `max_transactions` limit is reached. Account states updated
+/// by the transactions are kept in the fork. If an execution fails, none of the
+/// account updates of the call are applied to the fork.
+#[api_function]
+pub async fn fork_run_message(
+    context: Arc<ClientContext>,
+    params: ParamsOfForkRunMessage,
+) -> ClientResult<ResultOfForkRunMessage> {
+    let fork = context
+        .forks
+        .get(&params.fork)
+        .ok_or(Error::fork_not_found(params.fork))?;
+    let mut fork = fork.1.lock().await;
+
+    let max_transactions = params.max_transactions.unwrap_or(DEFAULT_MAX_TRANSACTIONS) as usize;
+    let mut result = ResultOfForkRunMessage::default();
+    let mut queue = VecDeque::from(vec![params.message]);
+    let mut abi = params.abi;
+    let mut skip_transaction_check = params.skip_transaction_check.unwrap_or_default();
+    // account updates are applied to the fork when all the transactions are executed
+    let mut updated = HashMap::new();
+    let mut next_lt = fork.next_lt;
+    while let Some(message) = queue.pop_front() {
+        if result.transactions.len() >= max_transactions {
+            result.unprocessed_messages.push(message);
+            result.unprocessed_messages.extend(queue.drain(..));
+            break;
+        }
+        let address = deserialize_object_from_boc::<Message>(&context, &message, "message")?
+            .object
+            .dst_ref()
+            .cloned()
+            .ok_or_else(|| Error::invalid_message_type())?;
+        let account = match updated.get(&address.to_string()) {
+            Some(boc) => Some(boc.clone()),
+            None => fork.account(&context, &address).await?,
+        };
+        let account = match account {
+            Some(boc) => AccountForExecutor::Account {
+                boc,
+                unlimited_balance: None,
+            },
+            None => AccountForExecutor::None,
+        };
+        let executed = run_executor_internal(
+            context.clone(),
+            ParamsOfRunExecutor {
+                message,
+                account,
+                execution_options: Some(fork.execution_options(next_lt)),
+                abi: abi.take(),
+                skip_transaction_check: Some(skip_transaction_check),
+                boc_cache: None,
+                return_updated_account: Some(true),
+            },
+            true,
+        )
+        .await?;
+        skip_transaction_check = true;
+
+        let transaction = deserialize_object_from_boc::<Transaction>(
+            &context,
+            executed.transaction["boc"].as_str().unwrap_or_default(),
+            "transaction",
+        )?
+        .object;
+        next_lt = std::cmp::max(
+            next_lt,
+            transaction.logical_time() + transaction.msg_count() as u64 + 1,
+        );
+        updated.insert(address.to_string(), executed.account);
+
+        for out_message in &executed.out_messages {
+            let is_internal =
+                deserialize_object_from_boc::<Message>(&context, out_message, "message")?
+                    .object
+                    .is_internal();
+            if is_internal {
+                queue.push_back(out_message.clone());
+            } else {
+                result.ext_out_messages.push(out_message.clone());
+            }
+        }
+        result.transactions.push(ForkTransaction {
+            transaction: executed.transaction,
+            out_messages: executed.out_messages,
+            decoded: executed.decoded,
+            fees: executed.fees,
+        });
+    }
+    fork.next_lt = next_lt;
+    fork.accounts.extend(
+        updated
+            .into_iter()
+            .map(|(address, boc)| (address, Some(boc))),
+    );
+    Ok(result)
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct ParamsOfForkGetAccount {
+    /// Fork handle.
+    pub fork: u32,
+
+    /// Account address.
+    pub address: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct ResultOfForkGetAccount {
+    /// Account BOC encoded as `base64`. Missing if the account does not exist.
+    pub boc: Option<String>,
+}
+
+/// Returns the account state in the fork.
+///
+/// Account state is fetched from the network if it has not been accessed in the fork yet.
+#[api_function]
+pub async fn fork_get_account(
+    context: Arc<ClientContext>,
+    params: ParamsOfForkGetAccount,
+) -> ClientResult<ResultOfForkGetAccount> {
+    let address = account_decode(&params.address)?;
+    let fork = context
+        .forks
+        .get(&params.fork)
+        .ok_or(Error::fork_not_found(params.fork))?;
+    let mut fork = fork.1.lock().await;
+    Ok(ResultOfForkGetAccount {
+        boc: fork.account(&context, &address).await?,
+    })
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct ParamsOfForkSetAccount {
+    /// Fork handle.
+    pub fork: u32,
+
+    /// Account address.
+    pub address: String,
+
+    /// Account BOC encoded as `base64`. If missing then the account is removed from the fork.
+    pub boc: Option<String>,
+}
+
+/// Replaces the account state in the fork.
+///
+/// Can be used to emulate the contract upgrades or to provide the states
+/// of the accounts which are missing in the network.
+#[api_function]
+pub async fn fork_set_account(
+    context: Arc<ClientContext>,
+    params: ParamsOfForkSetAccount,
+) -> ClientResult<()> {
+    let address = account_decode(&params.address)?;
+    if let Some(boc) = &params.boc {
+        deserialize_object_from_boc::<ever_block::Account>(&context, boc, "account")?;
+    }
+    let fork = context
+        .forks
+        .get(&params.fork)
+        .ok_or(Error::fork_not_found(params.fork))?;
+    fork.1
+        .lock()
+        .await
+        .accounts
+        .insert(address.to_string(), params.boc);
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct ParamsOfRemoveFork {
+    /// Fork handle.
+    pub fork: u32,
+}
+
+/// Removes the fork and releases its state.
+#[api_function]
+pub fn remove_fork(context: Arc<ClientContext>, params: ParamsOfRemoveFork) -> ClientResult<()> {
+    context.forks.remove(&params.fork);
+    Ok(())
+}
@@ -0,0 +1,229 @@
+/*
+ * Copyright 2018-2021 EverX Labs Ltd.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ *
+ */
+
+use crate::boc::internal::deserialize_cell_from_boc;
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use crate::net::{OrderBy, ParamsOfQueryCollection, SortDirection, ACCOUNTS_COLLECTION};
+use crate::tvm::Error;
+use ever_block::{
+    BuilderData, Cell, CellType, HashmapE, HashmapType, SliceData, UInt256, MASTERCHAIN_ID,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+// library reference cell data consists of the cell type byte and the library hash
+const LIBRARY_CELL_DATA_LEN: usize = 33;
+
+const ACCOUNTS_PAGE_SIZE: u32 = 50;
+
+/// Collects hashes of the library reference cells in the trees of `roots`.
+pub(crate) fn collect_library_refs(roots: &[Cell]) -> HashSet<UInt256> {
+    let mut visited = HashSet::new();
+    let mut refs = HashSet::new();
+    let mut cells = roots.to_vec();
+    while let Some(cell) = cells.pop() {
+        if !visited.insert(cell.repr_hash()) {
+            continue;
+        }
+        if cell.cell_type() == CellType::LibraryReference {
+            let data = cell.data();
+            if data.len() >= LIBRARY_CELL_DATA_LEN {
+                refs.insert(UInt256::from_slice(&data[1..LIBRARY_CELL_DATA_LEN]));
+            }
+            continue;
+        }
+        for i in 0..cell.references_count() {
+            if let Ok(child) = cell.reference(i) {
+                cells.push(child);
+            }
+        }
+    }
+    refs
+}
+
+pub(crate) fn parse_libraries(
+    context: &ClientContext,
+    bocs: Vec<String>,
+) -> ClientResult<HashMap<UInt256, Cell>> {
+    let mut libraries = HashMap::new();
+    for boc in bocs {
+        let (_, cell) = deserialize_cell_from_boc(context, &boc, "library")?;
+        libraries.insert(cell.repr_hash(), cell);
+    }
+    Ok(libraries)
+}
+
+/// Public libraries of the masterchain accounts cached in the context.
+#[derive(Default)]
+pub(crate) struct MasterchainLibraries {
+    published: RwLock<Option<Arc<HashMap<UInt256, Cell>>>>,
+}
+
+impl MasterchainLibraries {
+    pub(crate) async fn cached(&self) -> Option<Arc<HashMap<UInt256, Cell>>> {
+        self.published.read().await.clone()
+    }
+
+    async fn refresh(
+        &self,
+        context: &Arc<ClientContext>,
+    ) -> ClientResult<Arc<HashMap<UInt256, Cell>>> {
+        let published = Arc::new(query_masterchain_libraries(context).await?);
+        *self.published.write().await = Some(published.clone());
+        Ok(published)
+    }
+}
+
+/// Reads the public libraries published by the masterchain accounts.
+///
+/// Libraries are taken from the `library` field of the `accounts` collection
+/// (`StateInit.library` dictionary of the account: `SimpleLib` values keyed
+/// by the library root hashes). Only the libraries marked as public are returned
+/// since the private ones are available to the owner account only.
+pub(crate) async fn query_masterchain_libraries(
+    context: &Arc<ClientContext>,
+) -> ClientResult<HashMap<UInt256, Cell>> {
+    let server_link = context.get_server_link()?;
+    let mut libraries = HashMap::new();
+    let mut last_id = None;
+    loop {
+        let mut filter = json!({
+            "workchain_id": { "eq": MASTERCHAIN_ID },
+            "library": { "ne": null },
+        });
+        if let Some(last_id) = &last_id {
+            filter["id"] = json!({ "gt": last_id });
+        }
+        let accounts = server_link
+            .query_collection(
+                ParamsOfQueryCollection {
+                    collection: ACCOUNTS_COLLECTION.to_string(),
+                    filter: Some(filter),
+                    result: "id library".to_string(),
+                    order: Some(vec![OrderBy {
+                        path: "id".to_string(),
+                        direction: SortDirection::ASC,
+                    }]),
+                    limit: Some(ACCOUNTS_PAGE_SIZE),
+                },
+                None,
+            )
+            .await?;
+        let accounts = accounts.as_array().cloned().unwrap_or_default();
+        for account in &accounts {
+            if let Some(boc) = account["library"].as_str() {
+                let (_, dict) = deserialize_cell_from_boc(context, boc, "library")?;
+                read_public_libraries(dict, &mut libraries)?;
+            }
+        }
+        last_id = accounts
+            .last()
+            .and_then(|account| account["id"].as_str())
+            .map(|id| id.to_string());
+        if accounts.len() < ACCOUNTS_PAGE_SIZE as usize || last_id.is_none() {
+            break;
+        }
+    }
+    Ok(libraries)
+}
+
+fn read_public_libraries(dict: Cell, libraries: &mut HashMap<UInt256, Cell>) -> ClientResult<()> {
+    HashmapE::with_hashmap(256, Some(dict))
+        .iterate_slices(|_, mut value| {
+            let public = value.get_next_bit()?;
+            let root = value.checked_drain_reference()?;
+            if public {
+                libraries.insert(root.repr_hash(), root);
+            }
+            Ok(true)
+        })
+        .map_err(|err| {
+            crate::boc::Error::invalid_boc(format!("can not read libraries: {}", err))
+        })?;
+    Ok(())
+}
+
+fn missing_library_refs(roots: &[Cell], libraries: &HashMap<UInt256, Cell>) -> HashSet<UInt256> {
+    collect_library_refs(roots)
+        .into_iter()
+        .filter(|hash| !libraries.contains_key(hash))
+        .collect()
+}
+
+/// Adds the libraries referenced in the trees of `roots` which are missing in `libraries`.
+///
+/// Missing libraries are taken from the public libraries of the masterchain accounts.
+/// Libraries referenced by the added libraries are resolved the same way.
+///
+/// The published libraries are queried once and cached in the context. The cache is
+/// refreshed if it does not contain some of the missing libraries.
+pub(crate) async fn fetch_missing_libraries(
+    context: &Arc<ClientContext>,
+    libraries: &mut HashMap<UInt256, Cell>,
+    roots: Vec<Cell>,
+) -> ClientResult<()> {
+    let mut missing = missing_library_refs(&roots, libraries);
+    if missing.is_empty() {
+        return Ok(());
+    }
+    let cache = &context.masterchain_libraries;
+    let (mut published, mut refreshed) = match cache.cached().await {
+        Some(published) => (published, false),
+        None => (cache.refresh(context).await?, true),
+    };
+    while !missing.is_empty() {
+        if missing.iter().any(|hash| !published.contains_key(hash)) {
+            if refreshed {
+                let mut hashes = missing
+                    .iter()
+                    .filter(|hash| !published.contains_key(hash))
+                    .map(|hash| hash.as_hex_string())
+                    .collect::<Vec<_>>();
+                hashes.sort();
+                return Err(Error::libraries_not_found(&hashes));
+            }
+            published = cache.refresh(context).await?;
+            refreshed = true;
+            continue;
+        }
+        let found = missing
+            .iter()
+            .filter_map(|hash| published.get(hash).cloned())
+            .collect::<Vec<_>>();
+        for cell in &found {
+            libraries.insert(cell.repr_hash(), cell.clone());
+        }
+        missing = missing_library_refs(&found, libraries);
+    }
+    Ok(())
+}
+
+/// Builds the libraries dictionary in the format used by TVM to resolve
+/// the library reference cells: a value of the dictionary keeps the library root
+/// in the first reference.
+pub(crate) fn libraries_dict(libraries: &HashMap<UInt256, Cell>) -> ClientResult<HashmapE> {
+    let mut dict = HashmapE::with_bit_len(256);
+    for (hash, cell) in libraries {
+        let mut value = BuilderData::new();
+        value
+            .append_bit_one()
+            .and_then(|value| value.checked_append_reference(cell.clone()))
+            .map_err(|err| Error::internal_error(format!("can not build library: {}", err)))?;
+        dict.set_builder(SliceData::from_raw(hash.as_slice().to_vec(), 256), &value)
+            .map_err(|err| Error::internal_error(format!("can not build libraries: {}", err)))?;
+    }
+    Ok(dict)
+}
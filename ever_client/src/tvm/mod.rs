@@ -14,21 +14,42 @@
 
 pub(crate) mod call_tvm;
 pub(crate) mod check_transaction;
+pub(crate) mod coverage;
+pub(crate) mod libraries;
 mod errors;
+pub(crate) mod explain_fees;
+pub(crate) mod fork;
 pub(crate) mod run_get;
 pub(crate) mod run_message;
 pub(crate) mod types;
 
 mod stack;
+mod typed_stack;
 #[cfg(test)]
 mod tests;
 
+pub use coverage::{
+    export_coverage_lcov, get_coverage, CodeCellCoverage, InstructionCoverage,
+    ParamsOfExportCoverageLcov, ParamsOfGetCoverage, ResultOfExportCoverageLcov,
+    ResultOfGetCoverage, SourceMapEntry,
+};
 pub use errors::{Error, ErrorCode, StdContractError};
+pub use explain_fees::{
+    explain_fees, ActionFeeExplanation, FeeConfigParams, GasFeeExplanation,
+    MessageFeeExplanation, ParamsOfExplainFees, ResultOfExplainFees, StorageFeeExplanation,
+};
+pub use fork::{
+    create_fork, fork_get_account, fork_run_message, fork_set_account, remove_fork,
+    ForkTransaction, ParamsOfCreateFork, ParamsOfForkGetAccount, ParamsOfForkRunMessage,
+    ParamsOfForkSetAccount, ParamsOfRemoveFork, ResultOfCreateFork, ResultOfForkGetAccount,
+    ResultOfForkRunMessage,
+};
 pub use run_get::{run_get, ParamsOfRunGet, ResultOfRunGet};
 pub use run_message::{
-    run_executor, run_tvm, run_solidity_getter, AccountForExecutor, ParamsOfRunExecutor, ParamsOfRunTvm,
-    ResultOfRunExecutor, ResultOfRunTvm,
+    run_executor, run_executor_batch, run_tvm, run_solidity_getter, AccountForExecutor,
+    ParamsOfRunExecutor, ParamsOfRunExecutorBatch, ParamsOfRunTvm, ResultOfRunExecutor,
+    ResultOfRunExecutorBatch, ResultOfRunTvm, RunExecutorOutcome,
 };
 pub(crate) use run_message::run_executor_internal;
 pub use ever_sdk::TransactionFees;
-pub use types::ExecutionOptions;
+pub use types::{ExecutionOptions, TransactionType};
@@ -14,7 +14,9 @@
 use serde_json::Value;
 
 use super::stack;
+use super::typed_stack;
 use super::types::{ExecutionOptions, ResolvedExecutionOptions};
+use crate::abi::AbiParam;
 use crate::boc::internal::deserialize_object_from_boc;
 use crate::client::ClientContext;
 use crate::crypto::internal::ton_crc16;
@@ -40,12 +42,27 @@ pub struct ParamsOfRunGet {
     /// set this flag to true.
     /// This may happen, for example, when elector contract contains too many participants
     pub tuple_list_as_array: Option<bool>,
+    /// Types of the get-method parameters.
+    ///
+    /// If specified then `input` must be an object with the named parameter values
+    /// which are encoded according to the ABI types, e.g. addresses are passed as slices
+    /// and cells as BOCs encoded with `base64`.
+    pub input_params: Option<Vec<AbiParam>>,
+    /// Types of the get-method return values.
+    ///
+    /// If specified then `output` is an object with the named return values
+    /// decoded according to the ABI types, e.g. address is read from a slice,
+    /// cell is returned as a BOC encoded with `base64` and tuple as an object.
+    /// Arrays are read from tuples, or from the lists based on nested tuples
+    /// if `tuple_list_as_array` is `true`.
+    pub output_params: Option<Vec<AbiParam>>,
 }
 
 #[allow(non_snake_case)]
 #[derive(Serialize, Deserialize, ApiType, Default, Clone)]
 pub struct ResultOfRunGet {
-    /// Values returned by get-method on stack
+    /// Values returned by get-method on stack.
+    /// Object with the named values if `output_params` are specified.
     pub output: Value,
 }
 
@@ -59,18 +76,25 @@ pub async fn run_get(
     context: Arc<ClientContext>,
     params: ParamsOfRunGet,
 ) -> ClientResult<ResultOfRunGet> {
-    let mut account: ever_block::Account =
-        deserialize_object_from_boc(&context, &params.account, "account")?.object;
-    let options = ResolvedExecutionOptions::from_options(&context, params.execution_options).await?;
+    let account =
+        deserialize_object_from_boc::<ever_block::Account>(&context, &params.account, "account")?;
+    let (mut account, account_cell) = (account.object, account.cell);
+    let mut options = ResolvedExecutionOptions::from_options(&context, params.execution_options).await?;
 
     if account.is_none() {
         return Err(Error::invalid_account_boc("Account is None"))
     }
+    options.resolve_libraries(&context, vec![account_cell]).await?;
 
     let crc = ton_crc16(params.function_name.as_bytes());
     let function_id = ((crc as u32) & 0xffff) | 0x10000;
     let mut stack_in = Stack::new();
-    if let Some(input) = params.input {
+    if let Some(input_params) = params.input_params {
+        let input_params = typed_stack::resolve_params(input_params)?;
+        for item in typed_stack::encode_input(&input_params, params.input.as_ref())? {
+            stack_in.push(item);
+        }
+    } else if let Some(input) = params.input {
         if let Value::Array(array) = input {
             for value in array {
                 stack_in.push(stack::deserialize_item(&value)?);
@@ -84,7 +108,22 @@ pub async fn run_get(
         function_id,
     ))));
 
+    let output_params = params
+        .output_params
+        .map(typed_stack::resolve_params)
+        .transpose()?;
+
     let engine = super::call_tvm::call_tvm(&mut account, options, stack_in)?;
+    if let Some(output_params) = output_params {
+        let items = engine.stack().iter().collect::<Vec<_>>();
+        return Ok(ResultOfRunGet {
+            output: typed_stack::decode_output(
+                &output_params,
+                &items,
+                params.tuple_list_as_array.unwrap_or_default(),
+            )?,
+        });
+    }
     Ok(ResultOfRunGet {
         output: stack::serialize_items(
             Box::new(engine.stack().iter()),
@@ -13,23 +13,28 @@
  */
 
 use super::stack::serialize_item;
-use super::types::{ExecutionOptions, ResolvedExecutionOptions};
+use super::types::{ExecutionOptions, ResolvedExecutionOptions, TransactionType};
 use crate::boc::internal::{
     deserialize_cell_from_boc, deserialize_object_from_boc, deserialize_object_from_cell,
     serialize_cell_to_boc, serialize_object_to_base64, serialize_object_to_boc,
     serialize_object_to_cell,
 };
 use crate::client::ClientContext;
-use crate::error::ClientResult;
+use crate::error::{ClientError, ClientResult};
 use crate::processing::{parsing::decode_output, DecodedOutput};
 use crate::tvm::{check_transaction::calc_transaction_fees, Error};
 use crate::{abi::Abi, boc::BocCacheType};
 use serde_json::Value;
 use std::convert::TryFrom;
 use std::sync::{atomic::AtomicU64, Arc};
-use ever_block::{Account, CurrencyCollection, Message, MsgAddressInt, Serializable, Transaction};
+use ever_block::{
+    Account, CurrencyCollection, Message, MsgAddressInt, Serializable, Transaction,
+    TransactionTickTock,
+};
+use futures::StreamExt;
 use ever_executor::{
-    ExecuteParams, ExecutorError, OrdinaryTransactionExecutor, TransactionExecutor,
+    ExecuteParams, ExecutorError, OrdinaryTransactionExecutor, TickTockTransactionExecutor,
+    TransactionExecutor,
 };
 use ever_sdk::TransactionFees;
 use ever_block::{Cell, UInt256};
@@ -61,6 +66,7 @@ impl Default for AccountForExecutor {
 }
 
 const UNLIMITED_BALANCE: u64 = u64::MAX;
+const DEFAULT_BATCH_CONCURRENCY: u32 = 10;
 
 impl AccountForExecutor {
     pub fn get_account(
@@ -107,6 +113,19 @@ impl AccountForExecutor {
         }
     }
 
+    pub(crate) fn set_balance(account: Cell, balance: u64) -> ClientResult<Cell> {
+        let mut account: Account = deserialize_object_from_cell(account, "account")?;
+        let mut new_balance = account
+            .balance()
+            .ok_or_else(|| {
+                Error::invalid_account_boc("can not set balance for non existed account")
+            })?
+            .clone();
+        new_balance.grams = balance.into();
+        account.set_balance(new_balance);
+        serialize_object_to_cell(&account, "account")
+    }
+
     pub fn restore_balance_if_needed(
         account: Cell,
         balance: Option<CurrencyCollection>,
@@ -124,6 +143,7 @@ impl AccountForExecutor {
 #[derive(Serialize, Deserialize, ApiType, Clone, Default)]
 pub struct ParamsOfRunExecutor {
     /// Input message BOC. Must be encoded as base64.
+    /// Not used for the tick and tock transactions.
     pub message: String,
     /// Account to run on executor
     pub account: AccountForExecutor,
@@ -252,15 +272,31 @@ pub async fn run_executor_internal(
     params: ParamsOfRunExecutor,
     show_tips_on_error: bool,
 ) -> ClientResult<ResultOfRunExecutor> {
-    let message =
-        deserialize_object_from_boc::<Message>(&context, &params.message, "message")?.object;
-    let msg_address = message
-        .dst_ref()
-        .ok_or_else(|| Error::invalid_message_type())?
-        .clone();
-    let (account, _) = params.account.get_account(&context, msg_address.clone())?;
-    let options =
+    let mut options =
         ResolvedExecutionOptions::from_options(&context, params.execution_options).await?;
+    // tick and tock transactions have no inbound message
+    let message = if options.transaction_type == TransactionType::Ordinary {
+        Some(deserialize_object_from_boc::<Message>(&context, &params.message, "message")?.object)
+    } else {
+        None
+    };
+    let msg_address = match &message {
+        Some(message) => message
+            .dst_ref()
+            .ok_or_else(|| Error::invalid_message_type())?
+            .clone(),
+        None => MsgAddressInt::default(),
+    };
+    let (mut account, _) = params.account.get_account(&context, msg_address.clone())?;
+    if let Some(balance) = options.balance {
+        account = AccountForExecutor::set_balance(account, balance)?;
+    }
+    let mut roots = vec![account.clone()];
+    if let Some(message) = &message {
+        // deploy message can bring the code with the library references
+        roots.push(serialize_object_to_cell(message, "message")?);
+    }
+    options.resolve_libraries(&context, roots).await?;
 
     let account_copy = account.clone();
     let contract_info = move || async move {
@@ -324,6 +360,88 @@ pub async fn run_executor_internal(
     })
 }
 
+#[derive(Serialize, Deserialize, ApiType, Clone, Default)]
+pub struct ParamsOfRunExecutorBatch {
+    /// Messages with the accounts to run on executor.
+    pub items: Vec<ParamsOfRunExecutor>,
+    /// Maximum number of the messages executed concurrently. Default is 10.
+    ///
+    /// The default does not depend on the platform: the executions are spawned
+    /// into the client runtime which limits the actual parallelism.
+    pub max_concurrency: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct RunExecutorOutcome {
+    /// Result of the message execution. Missing if the execution has failed.
+    pub result: Option<ResultOfRunExecutor>,
+
+    /// Execution error. Missing if the message has been executed successfully.
+    pub error: Option<ClientError>,
+}
+
+impl From<ClientResult<ResultOfRunExecutor>> for RunExecutorOutcome {
+    fn from(result: ClientResult<ResultOfRunExecutor>) -> Self {
+        match result {
+            Ok(result) => Self {
+                result: Some(result),
+                error: None,
+            },
+            Err(error) => Self {
+                result: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct ResultOfRunExecutorBatch {
+    /// Outcomes of the message execution in the order of `ParamsOfRunExecutorBatch.items`.
+    pub results: Vec<RunExecutorOutcome>,
+}
+
+/// Emulates the execution of many messages on the local accounts concurrently
+///
+/// Each item is executed as a separate `run_executor` call. Items are independent:
+/// the updated account of an item is not passed to the other items, and a failure
+/// of an item does not affect the rest of the batch.
+///
+/// Can be used to estimate fees of the messages to many accounts at once
+/// without the overhead of the separate requests.
+#[api_function]
+pub async fn run_executor_batch(
+    context: Arc<ClientContext>,
+    params: ParamsOfRunExecutorBatch,
+) -> ClientResult<ResultOfRunExecutorBatch> {
+    let max_concurrency = params
+        .max_concurrency
+        .unwrap_or(DEFAULT_BATCH_CONCURRENCY)
+        .max(1) as usize;
+
+    let results = futures::stream::iter(params.items)
+        .map(|item| {
+            let context = context.clone();
+            async move {
+                // every item runs in its own task so the executions use all the runtime threads
+                let (sender, receiver) = futures::channel::oneshot::channel();
+                let context_copy = context.clone();
+                context.env.spawn(async move {
+                    let _ = sender.send(run_executor_internal(context_copy, item, true).await);
+                });
+                receiver.await.unwrap_or_else(|_| {
+                    Err(Error::internal_error("executor task has been cancelled"))
+                })
+            }
+        })
+        .buffered(max_concurrency)
+        .map(RunExecutorOutcome::from)
+        .collect::<Vec<_>>()
+        .await;
+
+    Ok(ResultOfRunExecutorBatch { results })
+}
+
 /// Executes get-methods of ABI-compatible contracts
 ///
 /// Performs only a part of compute phase of transaction execution
@@ -346,11 +464,14 @@ pub async fn run_tvm(
     let mut account = deserialize_object_from_boc::<Account>(&context, &params.account, "account")?;
     let message =
         deserialize_object_from_boc::<Message>(&context, &params.message, "message")?.object;
-    let options =
+    let mut options =
         ResolvedExecutionOptions::from_options(&context, params.execution_options).await?;
     if account.object.is_none() {
         return Err(Error::invalid_account_boc("Account is None"));
     }
+    options
+        .resolve_libraries(&context, vec![account.cell.clone()])
+        .await?;
 
     let messages = super::call_tvm::call_tvm_msg(&mut account.object, options, &message)?;
 
@@ -405,18 +526,21 @@ pub async fn run_solidity_getter(
     stack_items: Vec<StackItem>
 ) -> ClientResult<Vec<StackItem>> {
     let mut account = deserialize_object_from_boc::<Account>(&context, &params.account, "account")?;
-    let options =
+    let mut options =
         ResolvedExecutionOptions::from_options(&context, params.execution_options).await?;
     if account.object.is_none() {
         return Err(Error::invalid_account_boc("Account is None"));
     }
+    options
+        .resolve_libraries(&context, vec![account.cell.clone()])
+        .await?;
 
     Ok(super::call_tvm::call_tvm_msg_getter(&mut account.object, options, stack_items)?)
 }
 
 async fn call_executor<F>(
     mut account_root: Cell,
-    msg: Message,
+    msg: Option<Message>,
     options: ResolvedExecutionOptions,
     contract_info: impl FnOnce() -> F,
     show_tips_on_error: bool,
@@ -424,9 +548,8 @@ async fn call_executor<F>(
 where
     F: futures::Future<Output = ClientResult<(MsgAddressInt, u64)>>,
 {
-    let executor = OrdinaryTransactionExecutor::new(
-        Arc::try_unwrap(options.blockchain_config).unwrap_or_else(|arc| arc.as_ref().clone()),
-    );
+    let config =
+        Arc::try_unwrap(options.blockchain_config).unwrap_or_else(|arc| arc.as_ref().clone());
     let params = ExecuteParams {
         block_unixtime: options.block_time,
         block_lt: options.block_lt,
@@ -434,43 +557,59 @@ where
         behavior_modifiers: Some(options.behavior_modifiers),
         seed_block: UInt256::rand(),
         signature_id: options.signature_id,
+        state_libs: options.libraries_dict()?,
+        trace_callback: options
+            .coverage
+            .as_ref()
+            .map(|coverage| coverage.start_run()),
         ..ExecuteParams::default()
     };
-    let msg = ever_block::CommonMessage::Std(msg);
-    let transaction =
-        match executor.execute_with_libs_and_params(Some(&msg), &mut account_root, params) {
-            Ok(transaction) => transaction,
-            Err(err) => {
-                let err_message = err.to_string();
-                let err = match contract_info().await {
-                    Ok((address, balance)) => match &err.downcast_ref::<ExecutorError>() {
-                        Some(ExecutorError::NoAcceptError(code, exit_arg)) => {
-                            let exit_arg = exit_arg
-                                .as_ref()
-                                .map(|item| serialize_item(item))
-                                .transpose()?;
-                            Error::tvm_execution_failed(
-                                err_message,
-                                *code,
-                                exit_arg,
-                                &address,
-                                None,
-                                show_tips_on_error,
-                            )
-                        }
-                        Some(ExecutorError::NoFundsToImportMsg) => {
-                            Error::low_balance(&address, balance)
-                        }
-                        Some(ExecutorError::ExtMsgComputeSkipped(reason)) => {
-                            Error::tvm_execution_skipped(reason, &address, balance)
-                        }
-                        _ => Error::unknown_execution_error(err),
-                    },
-                    Err(err) => err,
-                };
-                return Err(err);
-            }
-        };
+    let msg = msg.map(ever_block::CommonMessage::Std);
+    let result = match options.transaction_type {
+        TransactionType::Ordinary => OrdinaryTransactionExecutor::new(config)
+            .execute_with_libs_and_params(msg.as_ref(), &mut account_root, params),
+        TransactionType::Tick => {
+            TickTockTransactionExecutor::new(config, TransactionTickTock::Tick)
+                .execute_with_libs_and_params(None, &mut account_root, params)
+        }
+        TransactionType::Tock => {
+            TickTockTransactionExecutor::new(config, TransactionTickTock::Tock)
+                .execute_with_libs_and_params(None, &mut account_root, params)
+        }
+    };
+    let transaction = match result {
+        Ok(transaction) => transaction,
+        Err(err) => {
+            let err_message = err.to_string();
+            let err = match contract_info().await {
+                Ok((address, balance)) => match &err.downcast_ref::<ExecutorError>() {
+                    Some(ExecutorError::NoAcceptError(code, exit_arg)) => {
+                        let exit_arg = exit_arg
+                            .as_ref()
+                            .map(|item| serialize_item(item))
+                            .transpose()?;
+                        Error::tvm_execution_failed(
+                            err_message,
+                            *code,
+                            exit_arg,
+                            &address,
+                            None,
+                            show_tips_on_error,
+                        )
+                    }
+                    Some(ExecutorError::NoFundsToImportMsg) => {
+                        Error::low_balance(&address, balance)
+                    }
+                    Some(ExecutorError::ExtMsgComputeSkipped(reason)) => {
+                        Error::tvm_execution_skipped(reason, &address, balance)
+                    }
+                    _ => Error::unknown_execution_error(err),
+                },
+                Err(err) => err,
+            };
+            return Err(err);
+        }
+    };
 
     Ok((transaction, account_root))
 }
@@ -15,13 +15,16 @@
 use super::*;
 use super::types::resolve_network_params;
 use crate::abi::{
-    encode_account::ParamsOfEncodeAccount,
-    Abi, CallSet, DeploySet, FunctionHeader, ParamsOfEncodeMessage, ResultOfEncodeMessage, Signer,
+    encode_account::{ParamsOfEncodeAccount, ResultOfEncodeAccount},
+    Abi, AbiParam, CallSet, DeploySet, FunctionHeader, ParamsOfEncodeMessage, ResultOfEncodeMessage, Signer,
 };
-use crate::boc::ParamsOfEncodeStateInit;
+use crate::boc::{ParamsOfEncodeStateInit, ResultOfEncodeStateInit};
 use crate::tests::GIVER_V2;
 use crate::boc::{
-    internal::{deserialize_object_from_base64, serialize_cell_to_base64},
+    internal::{
+        deserialize_cell_from_base64, deserialize_object_from_base64, serialize_cell_to_base64,
+        serialize_object_to_base64,
+    },
     BocCacheType,
 };
 use crate::error::ClientResult;
@@ -34,7 +37,7 @@ use api_info::ApiModule;
 use serde_json::Value;
 use ever_block::{GlobalCapabilities, ConfigParamEnum, Serializable, ConfigParam8};
 use std::sync::Arc;
-use ever_block::{BuilderData, Cell, SliceData};
+use ever_block::{BuilderData, Cell, CellType, SliceData, UInt256};
 use ever_vm::stack::{continuation::ContinuationData, StackItem};
 
 const ELECTOR_ADDRESS: &str = "-1:3333333333333333333333333333333333333333333333333333333333333333";
@@ -88,6 +91,8 @@ async fn test_execute_get() {
             input: None,
             execution_options: None,
             tuple_list_as_array: Some(true),
+            input_params: None,
+            output_params: None,
         })
         .await
         .unwrap()
@@ -142,6 +147,234 @@ async fn test_execute_get() {
     assert_eq!(result[0][0][0], "1588268660");
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_run_get_with_libraries() {
+    TestClient::init_log();
+    let client = TestClient::new();
+
+    // account code jumps to the elector code stored in the library
+    let elector_code = deserialize_cell_from_base64(ELECTOR_CODE, "code").unwrap().1;
+    let mut library_ref = BuilderData::new();
+    library_ref.set_type(CellType::LibraryReference);
+    library_ref.append_u8(2).unwrap(); // library reference cell type
+    library_ref
+        .append_raw(elector_code.repr_hash().as_slice(), 256)
+        .unwrap();
+    let mut code = BuilderData::new();
+    code.append_raw(&[0xDB, 0x3D], 16).unwrap(); // JMPREF
+    code.checked_append_reference(library_ref.into_cell().unwrap())
+        .unwrap();
+    let code = serialize_cell_to_base64(&code.into_cell().unwrap(), "code").unwrap();
+
+    let encode_account = |code: String| {
+        let state_init: ResultOfEncodeStateInit = client
+            .request(
+                "boc.encode_state_init",
+                ParamsOfEncodeStateInit {
+                    code: Some(code),
+                    data: Some(ELECTOR_DATA.into()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let account: ResultOfEncodeAccount = client
+            .request(
+                "abi.encode_account",
+                ParamsOfEncodeAccount {
+                    state_init: state_init.state_init,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        account.account
+    };
+    let run_get = |account: String, libraries: Option<Vec<String>>| {
+        client.request_async::<_, ResultOfRunGet>(
+            "tvm.run_get",
+            ParamsOfRunGet {
+                account,
+                function_name: "participant_list".into(),
+                execution_options: Some(ExecutionOptions {
+                    libraries,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+    };
+
+    let expected = run_get(encode_account(ELECTOR_CODE.into()), None)
+        .await
+        .unwrap();
+    let account = encode_account(code);
+    let result = run_get(account.clone(), Some(vec![ELECTOR_CODE.into()]))
+        .await
+        .unwrap();
+    assert_eq!(result.output, expected.output);
+
+    assert!(run_get(account, None).await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_fetch_masterchain_libraries() {
+    TestClient::init_log();
+    let client = TestClient::new();
+    let context = client.context();
+
+    let library_ref = |hash: &UInt256| {
+        let mut cell = BuilderData::new();
+        cell.set_type(CellType::LibraryReference);
+        cell.append_u8(2).unwrap(); // library reference cell type
+        cell.append_raw(hash.as_slice(), 256).unwrap();
+        cell.into_cell().unwrap()
+    };
+
+    let published = libraries::query_masterchain_libraries(&context)
+        .await
+        .unwrap();
+    for (hash, root) in &published {
+        assert_eq!(*hash, root.repr_hash());
+    }
+
+    // reference to the library which is not published is reported with its hash
+    let mut resolved = std::collections::HashMap::new();
+    let error = libraries::fetch_missing_libraries(
+        &context,
+        &mut resolved,
+        vec![library_ref(&UInt256::default())],
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(error.code, ErrorCode::LibraryNotFound as u32);
+    assert_eq!(
+        error.data["hashes"],
+        json!([UInt256::default().as_hex_string()])
+    );
+    assert!(resolved.is_empty());
+
+    if let Some((hash, root)) = published.iter().next() {
+        libraries::fetch_missing_libraries(&context, &mut resolved, vec![library_ref(hash)])
+            .await
+            .unwrap();
+        assert_eq!(resolved.get(hash), Some(root));
+    }
+    // published libraries are queried once per context
+    assert!(context.masterchain_libraries.cached().await.is_some());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_execute_get_typed() {
+    TestClient::init_log();
+    let client = TestClient::new();
+
+    let state_init: ResultOfEncodeStateInit = client
+        .request(
+            "boc.encode_state_init",
+            ParamsOfEncodeStateInit {
+                code: Some(ELECTOR_CODE.into()),
+                data: Some(ELECTOR_DATA.into()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let elector: ResultOfEncodeAccount = client
+        .request_async(
+            "abi.encode_account",
+            ParamsOfEncodeAccount {
+                state_init: state_init.state_init,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    let param = |name: &str, param_type: &str, components: Vec<AbiParam>| AbiParam {
+        name: name.into(),
+        param_type: param_type.into(),
+        components,
+        ..Default::default()
+    };
+    let participants_params = vec![param(
+        "participants",
+        "tuple[]",
+        vec![
+            param("id", "uint256", vec![]),
+            param("stake", "uint64", vec![]),
+        ],
+    )];
+
+    let result: ResultOfRunGet = client
+        .request_async(
+            "tvm.run_get",
+            ParamsOfRunGet {
+                account: elector.account.clone(),
+                function_name: "participant_list".into(),
+                output_params: Some(participants_params.clone()),
+                tuple_list_as_array: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    let participants = result.output["participants"].as_array().unwrap();
+    assert_eq!(participants.len(), 109);
+    assert_eq!(
+        participants[0],
+        json!({
+            "id": "0x0101b6d65a384b9c70deb49fd6c43ffc0f60ed22fcc3a4966f7043794a749228",
+            "stake": "60138000000000",
+        })
+    );
+
+    let result: ResultOfRunGet = client
+        .request_async(
+            "tvm.run_get",
+            ParamsOfRunGet {
+                account: elector.account.clone(),
+                function_name: "compute_returned_stake".into(),
+                input: Some(json!({
+                    "wallet_addr": format!("0x{}", ELECTOR_ADDRESS.split(':').last().unwrap()),
+                })),
+                input_params: Some(vec![param("wallet_addr", "uint256", vec![])]),
+                output_params: Some(vec![param("stake", "uint128", vec![])]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.output, json!({ "stake": "0" }));
+
+    // the list is not read as a tuple unless `tuple_list_as_array` is set
+    let error = client
+        .request_async::<_, ResultOfRunGet>(
+            "tvm.run_get",
+            ParamsOfRunGet {
+                account: elector.account.clone(),
+                function_name: "participant_list".into(),
+                output_params: Some(participants_params),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidOutputStack as u32);
+
+    let result: ClientResult<ResultOfRunGet> = client
+        .request_async(
+            "tvm.run_get",
+            ParamsOfRunGet {
+                account: elector.account,
+                function_name: "participant_list".into(),
+                output_params: Some(vec![param("participants", "address", vec![])]),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert_eq!(
+        result.err().unwrap().code,
+        ErrorCode::InvalidOutputStack as u32
+    );
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_run_executor() {
     let run = |client: Arc<TestClient>,
@@ -341,6 +574,62 @@ async fn test_run_account_none() {
     assert_eq!(parsed.parsed["acc_type_name"], "Uninit");
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_run_executor_batch() {
+    TestClient::init_log();
+    let client = TestClient::new();
+
+    let message = "te6ccgEBAQEAXAAAs0gAV2lB0HI8/VEO/pBKDJJJeoOcIh+dL9JzpmRzM8PfdicAPGNEGwRWGaJsR6UYmnsFVC2llSo1ZZN5mgUnCiHf7ZaUBKgXyAAGFFhgAAAB69+UmQS/LjmiQA==";
+    let item = |message: &str| ParamsOfRunExecutor {
+        message: message.to_owned(),
+        account: AccountForExecutor::None,
+        skip_transaction_check: Some(true),
+        ..Default::default()
+    };
+
+    let result: ResultOfRunExecutorBatch = client
+        .request_async(
+            "tvm.run_executor_batch",
+            ParamsOfRunExecutorBatch {
+                items: vec![item(message), item("invalid"), item(message)],
+                max_concurrency: Some(2),
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result.results.len(), 3);
+    let single: ResultOfRunExecutor = client
+        .request_async("tvm.run_executor", item(message))
+        .await
+        .unwrap();
+    for index in [0, 2] {
+        let outcome = &result.results[index];
+        assert!(outcome.error.is_none());
+        let executed = outcome.result.as_ref().unwrap();
+        assert_eq!(executed.out_messages.len(), single.out_messages.len());
+        assert_eq!(executed.fees, single.fees);
+    }
+    assert!(result.results[1].result.is_none());
+    assert_eq!(
+        result.results[1].error.as_ref().unwrap().code,
+        crate::boc::ErrorCode::InvalidBoc as u32
+    );
+
+    // zero concurrency executes the items one by one
+    let result: ResultOfRunExecutorBatch = client
+        .request_async(
+            "tvm.run_executor_batch",
+            ParamsOfRunExecutorBatch {
+                items: vec![item(message)],
+                max_concurrency: Some(0),
+            },
+        )
+        .await
+        .unwrap();
+    assert!(result.results[0].result.is_some());
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_run_account_uninit() {
     TestClient::init_log();
@@ -1275,3 +1564,768 @@ async fn test_signature_id() {
         .unwrap_err();
     assert_eq!(result.data["exit_code"].as_i64().unwrap(), StdContractError::InvalidSignature as i64);
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_coverage() {
+    let client = TestClient::new();
+    let (abi, tvc) = TestClient::package(HELLO, None);
+    let keys = client.generate_sign_keys();
+    let coverage_options = Some(ExecutionOptions {
+        collect_coverage: Some(true),
+        ..Default::default()
+    });
+
+    let deploy_message = client
+        .encode_message(ParamsOfEncodeMessage {
+            abi: abi.clone(),
+            call_set: CallSet::some_with_function("constructor"),
+            deploy_set: DeploySet::some_with_tvc(tvc),
+            signer: Signer::Keys { keys: keys.clone() },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let deployed: ResultOfRunExecutor = client
+        .request_async(
+            "tvm.run_executor",
+            ParamsOfRunExecutor {
+                message: deploy_message.message,
+                account: AccountForExecutor::Uninit,
+                execution_options: coverage_options.clone(),
+                return_updated_account: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let say_hello_message = client
+        .encode_message(ParamsOfEncodeMessage {
+            abi: abi.clone(),
+            address: Some(deploy_message.address),
+            call_set: CallSet::some_with_function("sayHello"),
+            signer: Signer::None,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let _: ResultOfRunTvm = client
+        .request_async(
+            "tvm.run_tvm",
+            ParamsOfRunTvm {
+                message: say_hello_message.message,
+                account: deployed.account,
+                abi: Some(abi),
+                execution_options: coverage_options,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let coverage: ResultOfGetCoverage = client
+        .request("tvm.get_coverage", ParamsOfGetCoverage::default())
+        .unwrap();
+    assert_eq!(coverage.runs, 2);
+    assert!(!coverage.cells.is_empty());
+    assert!(coverage
+        .cells
+        .iter()
+        .all(|cell| cell.instructions.iter().all(|instr| instr.hits > 0)));
+
+    let cell = &coverage.cells[0];
+    let lcov: ResultOfExportCoverageLcov = client
+        .request(
+            "tvm.export_coverage_lcov",
+            ParamsOfExportCoverageLcov {
+                source_map: vec![
+                    SourceMapEntry {
+                        cell_hash: cell.cell_hash.clone(),
+                        offset: cell.instructions[0].offset,
+                        file: "Hello.sol".to_owned(),
+                        line: 1,
+                    },
+                    SourceMapEntry {
+                        cell_hash: cell.cell_hash.clone(),
+                        offset: cell.bit_length + 1,
+                        file: "Hello.sol".to_owned(),
+                        line: 2,
+                    },
+                ],
+                reset: Some(true),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        lcov.lcov,
+        format!(
+            "TN:\nSF:Hello.sol\nDA:1,{}\nDA:2,0\nLF:2\nLH:1\nend_of_record\n",
+            cell.instructions[0].hits
+        )
+    );
+
+    let coverage: ResultOfGetCoverage = client
+        .request("tvm.get_coverage", ParamsOfGetCoverage::default())
+        .unwrap();
+    assert_eq!(coverage, ResultOfGetCoverage::default());
+
+    let error = client
+        .request::<_, ResultOfExportCoverageLcov>(
+            "tvm.export_coverage_lcov",
+            ParamsOfExportCoverageLcov::default(),
+        )
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidCoverageSourceMap as u32);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_fork() {
+    let client = TestClient::new();
+    let (abi, tvc) = TestClient::package(HELLO, None);
+    let keys = client.generate_sign_keys();
+
+    let fork: ResultOfCreateFork = client
+        .request_async("tvm.create_fork", ParamsOfCreateFork::default())
+        .await
+        .unwrap();
+
+    let deploy_message = client
+        .encode_message(ParamsOfEncodeMessage {
+            abi: abi.clone(),
+            call_set: CallSet::some_with_function("constructor"),
+            deploy_set: DeploySet::some_with_tvc(tvc),
+            signer: Signer::Keys { keys: keys.clone() },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let address = crate::encoding::account_decode(&deploy_message.address).unwrap();
+    let account = ever_block::Account::uninit(address, 0, fork.gen_utime, 1_000_000_000u64.into());
+    let _: () = client
+        .request_async(
+            "tvm.fork_set_account",
+            ParamsOfForkSetAccount {
+                fork: fork.fork,
+                address: deploy_message.address.clone(),
+                boc: Some(serialize_object_to_base64(&account, "account").unwrap()),
+            },
+        )
+        .await
+        .unwrap();
+
+    let deployed: ResultOfForkRunMessage = client
+        .request_async(
+            "tvm.fork_run_message",
+            ParamsOfForkRunMessage {
+                fork: fork.fork,
+                message: deploy_message.message,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(deployed.transactions.len(), 1);
+    assert!(deployed.unprocessed_messages.is_empty());
+
+    let touch_message = client
+        .encode_message(ParamsOfEncodeMessage {
+            abi: abi.clone(),
+            address: Some(deploy_message.address.clone()),
+            call_set: CallSet::some_with_function("touch"),
+            signer: Signer::Keys { keys },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let touched: ResultOfForkRunMessage = client
+        .request_async(
+            "tvm.fork_run_message",
+            ParamsOfForkRunMessage {
+                fork: fork.fork,
+                message: touch_message.message,
+                abi: Some(abi),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(touched.transactions.len(), 1);
+    assert!(touched.transactions[0].decoded.is_some());
+
+    let account: ResultOfForkGetAccount = client
+        .request_async(
+            "tvm.fork_get_account",
+            ParamsOfForkGetAccount {
+                fork: fork.fork,
+                address: deploy_message.address.clone(),
+            },
+        )
+        .await
+        .unwrap();
+    let account =
+        deserialize_object_from_base64::<ever_block::Account>(&account.boc.unwrap(), "account")
+            .unwrap()
+            .object;
+    assert_eq!(account.status(), ever_block::AccountStatus::AccStateActive);
+
+    let _: () = client
+        .request("tvm.remove_fork", ParamsOfRemoveFork { fork: fork.fork })
+        .unwrap();
+    let removed: ClientResult<ResultOfForkGetAccount> = client
+        .request_async(
+            "tvm.fork_get_account",
+            ParamsOfForkGetAccount {
+                fork: fork.fork,
+                address: deploy_message.address,
+            },
+        )
+        .await;
+    assert_eq!(removed.err().unwrap().code, ErrorCode::ForkNotFound as u32);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_fork_chase_messages() {
+    let client = TestClient::new();
+    let (abi, tvc) = TestClient::package(GIVER_V2, Some(2));
+    let keys = client.generate_sign_keys();
+
+    let get_account = |fork: u32, address: String| {
+        client.request_async::<_, ResultOfForkGetAccount>(
+            "tvm.fork_get_account",
+            ParamsOfForkGetAccount { fork, address },
+        )
+    };
+    let balance = |boc: &str| {
+        deserialize_object_from_base64::<ever_block::Account>(boc, "account")
+            .unwrap()
+            .object
+            .balance()
+            .unwrap()
+            .grams
+            .as_u128()
+    };
+
+    // account created in the network before the fork block
+    let recipient = format!("0:{}", client.generate_sign_keys().public);
+    client
+        .get_tokens_from_giver_async(&recipient, Some(500_000_000))
+        .await;
+
+    let fork: ResultOfCreateFork = client
+        .request_async("tvm.create_fork", ParamsOfCreateFork::default())
+        .await
+        .unwrap();
+
+    // account modified in the network after the fork block
+    let modified = format!("0:{}", client.generate_sign_keys().public);
+    client
+        .get_tokens_from_giver_async(&modified, Some(500_000_000))
+        .await;
+
+    // accounts are fetched from the network on the first access
+    let missing = format!("0:{}", client.generate_sign_keys().public);
+    assert_eq!(get_account(fork.fork, missing).await.unwrap().boc, None);
+    let recipient_balance = balance(
+        &get_account(fork.fork, recipient.clone())
+            .await
+            .unwrap()
+            .boc
+            .unwrap(),
+    );
+
+    let deploy_message = client
+        .encode_message(ParamsOfEncodeMessage {
+            abi: abi.clone(),
+            call_set: CallSet::some_with_function("constructor"),
+            deploy_set: DeploySet::some_with_tvc(tvc),
+            signer: Signer::Keys { keys: keys.clone() },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let giver = deploy_message.address.clone();
+    let account = ever_block::Account::uninit(
+        crate::encoding::account_decode(&giver).unwrap(),
+        0,
+        fork.gen_utime,
+        10_000_000_000u64.into(),
+    );
+    let _: () = client
+        .request_async(
+            "tvm.fork_set_account",
+            ParamsOfForkSetAccount {
+                fork: fork.fork,
+                address: giver.clone(),
+                boc: Some(serialize_object_to_base64(&account, "account").unwrap()),
+            },
+        )
+        .await
+        .unwrap();
+    let _: ResultOfForkRunMessage = client
+        .request_async(
+            "tvm.fork_run_message",
+            ParamsOfForkRunMessage {
+                fork: fork.fork,
+                message: deploy_message.message,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let send_message = |dest: &str| {
+        client.encode_message(ParamsOfEncodeMessage {
+            abi: abi.clone(),
+            address: Some(giver.clone()),
+            call_set: CallSet::some_with_function_and_input(
+                "sendTransaction",
+                json!({
+                    "dest": dest,
+                    "value": 1_000_000_000u64,
+                    "bounce": false
+                }),
+            ),
+            signer: Signer::Keys { keys: keys.clone() },
+            ..Default::default()
+        })
+    };
+
+    // internal message is delivered to the recipient within the fork
+    let sent: ResultOfForkRunMessage = client
+        .request_async(
+            "tvm.fork_run_message",
+            ParamsOfForkRunMessage {
+                fork: fork.fork,
+                message: send_message(&recipient).await.unwrap().message,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(sent.transactions.len(), 2);
+    assert_eq!(sent.transactions[1].transaction["account_addr"], recipient);
+    assert!(sent.unprocessed_messages.is_empty());
+    let boc = get_account(fork.fork, recipient.clone())
+        .await
+        .unwrap()
+        .boc
+        .unwrap();
+    assert!(balance(&boc) > recipient_balance);
+
+    // internal message is not executed beyond the limit
+    let limited: ResultOfForkRunMessage = client
+        .request_async(
+            "tvm.fork_run_message",
+            ParamsOfForkRunMessage {
+                fork: fork.fork,
+                message: send_message(&recipient).await.unwrap().message,
+                max_transactions: Some(1),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(limited.transactions.len(), 1);
+    assert_eq!(limited.unprocessed_messages.len(), 1);
+
+    // failed chase does not change the fork
+    let giver_before = get_account(fork.fork, giver.clone()).await.unwrap().boc;
+    let error = client
+        .request_async::<_, ResultOfForkRunMessage>(
+            "tvm.fork_run_message",
+            ParamsOfForkRunMessage {
+                fork: fork.fork,
+                message: send_message(&modified).await.unwrap().message,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::ForkAccountModified as u32);
+    assert_eq!(
+        get_account(fork.fork, giver.clone()).await.unwrap().boc,
+        giver_before
+    );
+
+    let _: () = client
+        .request("tvm.remove_fork", ParamsOfRemoveFork { fork: fork.fork })
+        .unwrap();
+
+    let error = client
+        .request_async::<_, ResultOfCreateFork>(
+            "tvm.create_fork",
+            ParamsOfCreateFork {
+                seq_no: Some(u32::MAX),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::ForkBlockNotFound as u32);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_create_fork_invalid_options() {
+    let client = TestClient::new();
+
+    let error = client
+        .request_async::<_, ResultOfCreateFork>(
+            "tvm.create_fork",
+            ParamsOfCreateFork {
+                execution_options: Some(ExecutionOptions {
+                    balance: Some(1_000_000_000),
+                    transaction_type: Some(TransactionType::Tick),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidForkOptions as u32);
+    assert_eq!(error.data["options"], json!(["balance", "transaction_type"]));
+
+    let fork: ResultOfCreateFork = client
+        .request_async(
+            "tvm.create_fork",
+            ParamsOfCreateFork {
+                execution_options: Some(ExecutionOptions {
+                    transaction_type: Some(TransactionType::Ordinary),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    let _: () = client
+        .request("tvm.remove_fork", ParamsOfRemoveFork { fork: fork.fork })
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_explain_fees() {
+    let client = TestClient::new();
+    let (abi, tvc) = TestClient::package(HELLO, None);
+    let keys = client.generate_sign_keys();
+
+    let deploy_message = client
+        .encode_message(ParamsOfEncodeMessage {
+            abi: abi.clone(),
+            call_set: CallSet::some_with_function("constructor"),
+            deploy_set: DeploySet::some_with_tvc(tvc),
+            signer: Signer::Keys { keys },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let deployed: ResultOfRunExecutor = client
+        .request_async(
+            "tvm.run_executor",
+            ParamsOfRunExecutor {
+                message: deploy_message.message,
+                account: AccountForExecutor::Uninit,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let explained: ResultOfExplainFees = client
+        .request_async(
+            "tvm.explain_fees",
+            ParamsOfExplainFees {
+                transaction: deployed.transaction["boc"].as_str().unwrap().to_owned(),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(explained.fees.gas_fee, deployed.fees.gas_fee);
+    assert_eq!(explained.fees.ext_in_msg_fee, deployed.fees.ext_in_msg_fee);
+
+    let compute = explained.compute.unwrap();
+    assert_eq!(compute.gas_fees, deployed.fees.gas_fee.to_string());
+    assert_eq!(compute.calculated_fee, compute.gas_fees);
+
+    let in_message = explained.in_message.unwrap();
+    assert!(in_message.cells > 0);
+    assert_eq!(in_message.fwd_fee, deployed.fees.ext_in_msg_fee.to_string());
+
+    let action = explained.action.unwrap();
+    assert_eq!(action.out_messages.len(), deployed.out_messages.len());
+    assert_eq!(
+        action.total_fwd_fees,
+        deployed.fees.total_fwd_fees.to_string()
+    );
+
+    let storage = explained.storage.unwrap();
+    assert_eq!(storage.calculated_fee, None);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_explain_fees_out_messages() {
+    let client = TestClient::new();
+    let (abi, tvc) = TestClient::package(GIVER_V2, Some(2));
+    let keys = client.generate_sign_keys();
+
+    let deploy_message = client
+        .encode_message(ParamsOfEncodeMessage {
+            abi: abi.clone(),
+            call_set: CallSet::some_with_function("constructor"),
+            deploy_set: DeploySet::some_with_tvc(tvc),
+            signer: Signer::Keys { keys: keys.clone() },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let giver = deploy_message.address.clone();
+    let account = ever_block::Account::uninit(
+        crate::encoding::account_decode(&giver).unwrap(),
+        0,
+        (client.context().env.now_ms() / 1000) as u32,
+        100_000_000_000u64.into(),
+    );
+    let deployed: ResultOfRunExecutor = client
+        .request_async(
+            "tvm.run_executor",
+            ParamsOfRunExecutor {
+                message: deploy_message.message,
+                account: AccountForExecutor::Account {
+                    boc: serialize_object_to_base64(&account, "account").unwrap(),
+                    unlimited_balance: None,
+                },
+                return_updated_account: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    // storage of the giver is not paid for a day
+    let mut account =
+        deserialize_object_from_base64::<ever_block::Account>(&deployed.account, "account")
+            .unwrap()
+            .object;
+    let paid_since = account.storage_info().unwrap().last_paid() - 86400;
+    account.set_last_paid(paid_since);
+    let account = serialize_object_to_base64(&account, "account").unwrap();
+
+    // the second destination is in the masterchain and is charged with its forward prices
+    for dest in [
+        format!("0:{}", client.generate_sign_keys().public),
+        format!("-1:{}", client.generate_sign_keys().public),
+    ] {
+        let send_message = client
+            .encode_message(ParamsOfEncodeMessage {
+                abi: abi.clone(),
+                address: Some(giver.clone()),
+                call_set: CallSet::some_with_function_and_input(
+                    "sendTransaction",
+                    json!({
+                        "dest": dest,
+                        "value": 1_000_000_000u64,
+                        "bounce": false
+                    }),
+                ),
+                signer: Signer::Keys { keys: keys.clone() },
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let sent: ResultOfRunExecutor = client
+            .request_async(
+                "tvm.run_executor",
+                ParamsOfRunExecutor {
+                    message: send_message.message,
+                    account: AccountForExecutor::Account {
+                        boc: account.clone(),
+                        unlimited_balance: None,
+                    },
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(sent.out_messages.len(), 1);
+
+        let explained: ResultOfExplainFees = client
+            .request_async(
+                "tvm.explain_fees",
+                ParamsOfExplainFees {
+                    transaction: sent.transaction["boc"].as_str().unwrap().to_owned(),
+                    account: Some(account.clone()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let action = explained.action.unwrap();
+        assert_eq!(action.total_fwd_fees, sent.fees.total_fwd_fees.to_string());
+        assert_eq!(action.out_messages.len(), 1);
+        let out_message = &action.out_messages[0];
+        assert_eq!(out_message.fwd_fee, action.total_fwd_fees);
+        assert_eq!(out_message.action_fee, action.total_action_fees);
+
+        let storage = explained.storage.unwrap();
+        assert_eq!(storage.paid_since, Some(paid_since));
+        assert_eq!(storage.fees_collected, sent.fees.storage_fee.to_string());
+        assert_eq!(storage.calculated_fee, Some(storage.fees_collected.clone()));
+        assert_ne!(storage.fees_collected, "0");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_execution_overrides() {
+    let client = TestClient::new();
+    let (abi, tvc) = TestClient::package(HELLO, None);
+    let keys = client.generate_sign_keys();
+
+    let deploy_message = client
+        .encode_message(ParamsOfEncodeMessage {
+            abi: abi.clone(),
+            call_set: CallSet::some_with_function("constructor"),
+            deploy_set: DeploySet::some_with_tvc(tvc),
+            signer: Signer::Keys { keys: keys.clone() },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let out_of_gas = client
+        .request_async::<_, ResultOfRunExecutor>(
+            "tvm.run_executor",
+            ParamsOfRunExecutor {
+                message: deploy_message.message.clone(),
+                account: AccountForExecutor::Uninit,
+                execution_options: Some(ExecutionOptions {
+                    gas_limit: Some(100),
+                    gas_credit: Some(100),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(out_of_gas.code, ErrorCode::ContractExecutionError as u32);
+    assert_eq!(out_of_gas.data["exit_code"], -14);
+
+    let deployed: ResultOfRunExecutor = client
+        .request_async(
+            "tvm.run_executor",
+            ParamsOfRunExecutor {
+                message: deploy_message.message,
+                account: AccountForExecutor::Uninit,
+                return_updated_account: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let touch_message = client
+        .encode_message(ParamsOfEncodeMessage {
+            abi,
+            address: Some(deploy_message.address),
+            call_set: CallSet::some_with_function("touch"),
+            signer: Signer::Keys { keys },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let balance = 2_000_000_000u64;
+    let touched: ResultOfRunExecutor = client
+        .request_async(
+            "tvm.run_executor",
+            ParamsOfRunExecutor {
+                message: touch_message.message,
+                account: AccountForExecutor::Account {
+                    boc: deployed.account,
+                    unlimited_balance: None,
+                },
+                execution_options: Some(ExecutionOptions {
+                    balance: Some(balance),
+                    ..Default::default()
+                }),
+                return_updated_account: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    let account =
+        deserialize_object_from_base64::<ever_block::Account>(&touched.account, "account")
+            .unwrap()
+            .object;
+    assert_eq!(
+        account.balance().unwrap().grams.as_u128(),
+        (balance - touched.fees.account_fees) as u128
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_tick_tock_transactions() {
+    let client = TestClient::new();
+
+    let state_init: ResultOfEncodeStateInit = client
+        .request(
+            "boc.encode_state_init",
+            ParamsOfEncodeStateInit {
+                code: Some(ELECTOR_CODE.into()),
+                data: Some(ELECTOR_DATA.into()),
+                tick: Some(true),
+                tock: Some(true),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let state_init = deserialize_object_from_base64::<ever_block::StateInit>(
+        &state_init.state_init,
+        "state_init",
+    )
+    .unwrap()
+    .object;
+    let account = ever_block::Account::active_by_init_code_hash(
+        crate::encoding::account_decode(ELECTOR_ADDRESS).unwrap(),
+        100_000_000_000u64.into(),
+        0,
+        state_init,
+        false,
+    )
+    .unwrap();
+    let account = serialize_object_to_base64(&account, "account").unwrap();
+
+    for (transaction_type, tr_type) in [(TransactionType::Tick, 2), (TransactionType::Tock, 3)] {
+        let result: ResultOfRunExecutor = client
+            .request_async(
+                "tvm.run_executor",
+                ParamsOfRunExecutor {
+                    message: String::new(),
+                    account: AccountForExecutor::Account {
+                        boc: account.clone(),
+                        unlimited_balance: None,
+                    },
+                    execution_options: Some(ExecutionOptions {
+                        transaction_type: Some(transaction_type),
+                        ..Default::default()
+                    }),
+                    skip_transaction_check: Some(true),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.transaction["tr_type"], tr_type);
+        assert_eq!(result.transaction["account_addr"], ELECTOR_ADDRESS);
+        assert!(result.transaction["in_msg"].is_null());
+    }
+}
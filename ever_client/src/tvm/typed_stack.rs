@@ -0,0 +1,350 @@
+/*
+ * Copyright 2018-2021 EverX Labs Ltd.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ *
+ */
+
+//! Conversion of the get-method stack items according to the ABI-like types.
+//!
+//! Supported types are `int<N>`, `uint<N>`, `varint<N>`, `varuint<N>`, `gram`, `bool`,
+//! `address` (stored in a slice), `cell`, `bytes` and `string` (stored in a cell chain),
+//! `tuple` (decoded into an object with the component names), `T[]`, `T[N]`
+//! and `optional(T)`.
+//!
+//! Arrays are stored in tuples. Returned arrays are read from the lists based on
+//! the nested tuples `[a, [b, [c, null]]]` instead if the caller requests it.
+
+use super::stack;
+use crate::abi::AbiParam;
+use crate::boc::internal::{deserialize_cell_from_base64, serialize_cell_to_base64};
+use crate::encoding::{account_decode, slice_from_cell};
+use crate::error::ClientResult;
+use crate::tvm::Error;
+use ever_abi::{Param, ParamType};
+use ever_block::{BuilderData, Cell, Deserializable, MsgAddressInt, Serializable};
+use ever_vm::stack::integer::IntegerData;
+use ever_vm::stack::StackItem;
+use num_bigint::{BigInt, Sign};
+use serde_json::{Map, Value};
+use std::convert::TryInto;
+
+// maximum number of data bytes stored in one cell of the `bytes` and `string` chains
+const CELL_DATA_BYTES: usize = 127;
+
+pub(crate) fn resolve_params(params: Vec<AbiParam>) -> ClientResult<Vec<Param>> {
+    params.into_iter().map(|param| param.try_into()).collect()
+}
+
+fn integer_bits(kind: &ParamType) -> Option<(usize, bool)> {
+    match kind {
+        ParamType::Uint(size) => Some((*size, false)),
+        ParamType::Int(size) => Some((*size, true)),
+        ParamType::VarUint(size) => Some(((size - 1) * 8, false)),
+        ParamType::VarInt(size) => Some(((size - 1) * 8, true)),
+        ParamType::Token => Some((120, false)),
+        _ => None,
+    }
+}
+
+fn to_bigint(value: &IntegerData) -> Option<BigInt> {
+    if value.is_nan() {
+        return None;
+    }
+    BigInt::parse_bytes(value.to_str_radix(10).as_bytes(), 10)
+}
+
+fn fits_into(value: &BigInt, size: usize, signed: bool) -> bool {
+    if value.sign() == Sign::Minus {
+        signed && (-value - 1).bits() < size as u64
+    } else if signed {
+        value.bits() < size as u64
+    } else {
+        value.bits() <= size as u64
+    }
+}
+
+fn cell_of_item(item: &StackItem) -> ClientResult<Cell> {
+    match item {
+        StackItem::Cell(cell) => Ok(cell.clone()),
+        StackItem::Slice(slice) => Ok(slice.clone().into_cell()),
+        StackItem::Builder(builder) => builder
+            .as_ref()
+            .clone()
+            .into_cell()
+            .map_err(|err| Error::invalid_output_stack(err)),
+        _ => Err(Error::invalid_output_stack(
+            "cell, slice or builder expected",
+        )),
+    }
+}
+
+fn read_bytes(item: &StackItem) -> ClientResult<Vec<u8>> {
+    let mut slice = slice_from_cell(cell_of_item(item)?)?;
+    let mut data = Vec::new();
+    loop {
+        data.extend_from_slice(&slice.get_bytestring(0));
+        if slice.remaining_references() == 0 {
+            return Ok(data);
+        }
+        let next = slice
+            .reference(0)
+            .map_err(|err| Error::invalid_output_stack(err))?;
+        slice = slice_from_cell(next)?;
+    }
+}
+
+fn write_bytes(data: &[u8], value: &Value) -> ClientResult<StackItem> {
+    let mut next: Option<Cell> = None;
+    for chunk in data.chunks(CELL_DATA_BYTES).rev() {
+        let mut builder = BuilderData::new();
+        builder
+            .append_raw(chunk, chunk.len() * 8)
+            .map_err(|err| Error::invalid_input_stack(err, value))?;
+        if let Some(next) = next {
+            builder
+                .checked_append_reference(next)
+                .map_err(|err| Error::invalid_input_stack(err, value))?;
+        }
+        next = Some(
+            builder
+                .into_cell()
+                .map_err(|err| Error::invalid_input_stack(err, value))?,
+        );
+    }
+    let cell = match next {
+        Some(cell) => cell,
+        None => BuilderData::new()
+            .into_cell()
+            .map_err(|err| Error::invalid_input_stack(err, value))?,
+    };
+    Ok(StackItem::cell(cell))
+}
+
+// Returns the items of the list represented with the nested tuples `[a, [b, [c, null]]]`
+fn list_items(mut item: &StackItem) -> ClientResult<Vec<&StackItem>> {
+    let mut items = Vec::new();
+    loop {
+        match item {
+            StackItem::None => return Ok(items),
+            StackItem::Tuple(tuple) if tuple.len() == 2 => {
+                items.push(&tuple[0]);
+                item = &tuple[1];
+            }
+            _ => return Err(Error::invalid_output_stack("list expected")),
+        }
+    }
+}
+
+fn decode_array(kind: &ParamType, item: &StackItem, lists: bool) -> ClientResult<Vec<Value>> {
+    let items = match item {
+        _ if lists => list_items(item)?,
+        StackItem::Tuple(tuple) => tuple.iter().collect(),
+        _ => return Err(Error::invalid_output_stack("tuple expected")),
+    };
+    items
+        .into_iter()
+        .map(|item| decode_item(kind, item, lists))
+        .collect()
+}
+
+fn decode_item(kind: &ParamType, item: &StackItem, lists: bool) -> ClientResult<Value> {
+    if let Some((size, signed)) = integer_bits(kind) {
+        let value = match item {
+            StackItem::Integer(value) => to_bigint(value),
+            _ => return Err(Error::invalid_output_stack("integer expected")),
+        }
+        .filter(|value| fits_into(value, size, signed))
+        .ok_or_else(|| Error::invalid_output_stack(format!("integer is out of {} range", kind)))?;
+        return Ok(if !signed && size > 128 {
+            Value::String(format!("0x{:0>1$}", value.to_str_radix(16), size / 4))
+        } else {
+            Value::String(value.to_str_radix(10))
+        });
+    }
+    Ok(match kind {
+        ParamType::Bool => match item {
+            StackItem::Integer(value) => Value::Bool(!value.is_zero()),
+            _ => return Err(Error::invalid_output_stack("integer expected")),
+        },
+        ParamType::Address => {
+            let mut slice = slice_from_cell(cell_of_item(item)?)?;
+            let address = MsgAddressInt::construct_from(&mut slice)
+                .map_err(|err| Error::invalid_output_stack(format!("invalid address: {}", err)))?;
+            Value::String(address.to_string())
+        }
+        ParamType::Cell => Value::String(serialize_cell_to_base64(
+            &cell_of_item(item)?,
+            "stack item",
+        )?),
+        ParamType::Bytes => Value::String(hex::encode(read_bytes(item)?)),
+        ParamType::String => Value::String(
+            String::from_utf8(read_bytes(item)?)
+                .map_err(|err| Error::invalid_output_stack(format!("invalid string: {}", err)))?,
+        ),
+        ParamType::Tuple(components) => match item {
+            StackItem::Tuple(items) if items.len() == components.len() => {
+                let mut object = Map::new();
+                for (param, item) in components.iter().zip(items.iter()) {
+                    object.insert(param.name.clone(), decode_item(&param.kind, item, lists)?);
+                }
+                Value::Object(object)
+            }
+            _ => {
+                return Err(Error::invalid_output_stack(format!(
+                    "tuple of {} items expected",
+                    components.len()
+                )))
+            }
+        },
+        ParamType::Array(kind) => Value::Array(decode_array(kind, item, lists)?),
+        ParamType::FixedArray(kind, size) => {
+            let items = decode_array(kind, item, lists)?;
+            if items.len() != *size {
+                return Err(Error::invalid_output_stack(format!(
+                    "array of {} items expected",
+                    size
+                )));
+            }
+            Value::Array(items)
+        }
+        ParamType::Optional(kind) => match item {
+            StackItem::None => Value::Null,
+            _ => decode_item(kind, item, lists)?,
+        },
+        _ => {
+            return Err(Error::invalid_output_stack(format!(
+                "unsupported type {}",
+                kind
+            )))
+        }
+    })
+}
+
+fn encode_item(kind: &ParamType, value: &Value) -> ClientResult<StackItem> {
+    if let Some((size, signed)) = integer_bits(kind) {
+        let item = match value {
+            Value::Number(_) | Value::String(_) => stack::deserialize_item(value)?,
+            _ => return Err(Error::invalid_input_stack("integer expected", value)),
+        };
+        let fits = match &item {
+            StackItem::Integer(integer) => {
+                to_bigint(integer).map_or(false, |integer| fits_into(&integer, size, signed))
+            }
+            _ => false,
+        };
+        if !fits {
+            return Err(Error::invalid_input_stack(
+                format!("integer is out of {} range", kind),
+                value,
+            ));
+        }
+        return Ok(item);
+    }
+    Ok(match (kind, value) {
+        (ParamType::Bool, Value::Bool(flag)) => StackItem::boolean(*flag),
+        (ParamType::Address, Value::String(address)) => {
+            let address = account_decode(address)?;
+            let cell = address
+                .serialize()
+                .map_err(|err| Error::invalid_input_stack(err, value))?;
+            StackItem::slice(slice_from_cell(cell)?)
+        }
+        (ParamType::Cell, Value::String(boc)) => {
+            StackItem::cell(deserialize_cell_from_base64(boc, "stack item")?.1)
+        }
+        (ParamType::Bytes, Value::String(data)) => write_bytes(
+            &hex::decode(data).map_err(|err| Error::invalid_input_stack(err, value))?,
+            value,
+        )?,
+        (ParamType::String, Value::String(data)) => write_bytes(data.as_bytes(), value)?,
+        (ParamType::Tuple(components), Value::Object(object)) => StackItem::tuple(
+            components
+                .iter()
+                .map(|param| {
+                    encode_item(&param.kind, object.get(&param.name).unwrap_or(&Value::Null))
+                })
+                .collect::<ClientResult<_>>()?,
+        ),
+        (ParamType::Array(kind), Value::Array(items)) => StackItem::tuple(
+            items
+                .iter()
+                .map(|item| encode_item(kind, item))
+                .collect::<ClientResult<_>>()?,
+        ),
+        (ParamType::FixedArray(kind, size), Value::Array(items)) if items.len() == *size => {
+            StackItem::tuple(
+                items
+                    .iter()
+                    .map(|item| encode_item(kind, item))
+                    .collect::<ClientResult<_>>()?,
+            )
+        }
+        (ParamType::Optional(_), Value::Null) => StackItem::None,
+        (ParamType::Optional(kind), _) => encode_item(kind, value)?,
+        _ => {
+            return Err(Error::invalid_input_stack(
+                format!("value does not match type {}", kind),
+                value,
+            ))
+        }
+    })
+}
+
+/// Encodes the named input values into the stack items pushed in the order of `params`.
+pub(crate) fn encode_input(
+    params: &[Param],
+    input: Option<&Value>,
+) -> ClientResult<Vec<StackItem>> {
+    let empty = Map::new();
+    let object = match input {
+        None | Some(Value::Null) => &empty,
+        Some(Value::Object(object)) => object,
+        Some(value) => {
+            return Err(Error::invalid_input_stack(
+                "object with the named parameters expected",
+                value,
+            ))
+        }
+    };
+    params
+        .iter()
+        .map(|param| encode_item(&param.kind, object.get(&param.name).unwrap_or(&Value::Null)))
+        .collect()
+}
+
+/// Decodes the top `params.len()` stack items into the object with the named values.
+/// The deepest of the items corresponds to the first param.
+///
+/// Arrays are read from the lists based on the nested tuples if `lists` is `true`
+/// and from the tuples otherwise.
+pub(crate) fn decode_output(
+    params: &[Param],
+    items: &[&StackItem],
+    lists: bool,
+) -> ClientResult<Value> {
+    if items.len() < params.len() {
+        return Err(Error::invalid_output_stack(format!(
+            "get-method returned {} values, {} expected",
+            items.len(),
+            params.len()
+        )));
+    }
+    let items = &items[items.len() - params.len()..];
+    let mut output = Map::new();
+    for (param, item) in params.iter().zip(items.iter()) {
+        let value = decode_item(&param.kind, item, lists).map_err(|mut err| {
+            err.message = format!("can not decode `{}`: {}", param.name, err.message);
+            err
+        })?;
+        output.insert(param.name.clone(), value);
+    }
+    Ok(Value::Object(output))
+}
@@ -12,12 +12,16 @@
  *
  */
 
+use super::coverage::CoverageSession;
+use super::libraries;
 use super::Error;
 use crate::client::{ClientContext, NetworkParams};
 use crate::error::ClientResult;
 use crate::net::network_params::get_default_params;
 use crate::boc::internal::deserialize_object_from_boc;
+use std::collections::HashMap;
 use std::sync::Arc;
+use ever_block::{Cell, ConfigParamEnum, HashmapE, UInt256};
 use ever_executor::BlockchainConfig;
 use ever_vm::executor::BehaviorModifiers;
 
@@ -25,11 +29,17 @@ use ever_vm::executor::BehaviorModifiers;
 pub struct ExecutionOptions {
     /// boc with config
     pub blockchain_config: Option<String>,
-    /// time that is used as transaction time
+    /// Unix time in seconds that is used as the block and transaction time (`now` of the
+    /// executed message). Default is the current time.
     pub block_time: Option<u32>,
-    /// block logical time
+    /// Logical time of the block the transaction is emulated in.
+    /// Default is `transaction_lt - 1` if `transaction_lt` is set, otherwise `1000000`.
     pub block_lt: Option<u64>,
-    /// transaction logical time
+    /// Logical time of the emulated transaction (`lt` of the executed message).
+    /// Default is `block_lt + 1`.
+    ///
+    /// Set `block_time` and `transaction_lt` explicitly for each message to emulate
+    /// a sequence of transactions deterministically.
     pub transaction_lt: Option<u64>,
     /// Overrides standard TVM behaviour.
     /// If set to `true` then CHKSIG always will return `true`.
@@ -37,6 +47,51 @@ pub struct ExecutionOptions {
     /// Signature ID to be used in signature verifying instructions when CapSignatureWithId
     /// capability is enabled
     pub signature_id: Option<i32>,
+    /// Collect code coverage of the execution into the context coverage session.
+    /// Use `tvm.get_coverage` to read the collected coverage. Default is `false`.
+    pub collect_coverage: Option<bool>,
+    /// Library cells used to resolve the library references in the account code and data.
+    /// BOCs encoded as `base64`. Libraries are identified by the root cell hashes.
+    pub libraries: Option<Vec<String>>,
+    /// Fetch the referenced libraries missing in `libraries` from the public libraries
+    /// of the masterchain accounts (`library` field of the GraphQL `accounts` collection).
+    /// The published libraries are cached in the context.
+    /// Execution fails with `LibraryNotFound` error if some libraries are not published.
+    /// Default is `false`.
+    pub fetch_libraries: Option<bool>,
+    /// Overrides the gas limit of the transaction from the blockchain config
+    /// (`gas_limit` and `special_gas_limit` of params 20 and 21).
+    ///
+    /// The gas limit of an ordinary transaction is also bounded by the gas
+    /// the account balance can pay for, use `balance` to set it precisely.
+    pub gas_limit: Option<u64>,
+    /// Overrides the gas credit of the external inbound messages from the blockchain config
+    /// (`gas_credit` of params 20 and 21).
+    pub gas_credit: Option<u64>,
+    /// Overrides the account balance in nanotokens before the execution.
+    /// Can not be used with the non-existing account.
+    pub balance: Option<u64>,
+    /// Type of the emulated transaction. Default is `Ordinary`.
+    ///
+    /// Tick and tock transactions are executed without the inbound message,
+    /// so the `message` parameter is not used and can be empty.
+    pub transaction_type: Option<TransactionType>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Debug, Clone, Copy, PartialEq)]
+pub enum TransactionType {
+    /// Ordinary transaction processing the inbound message.
+    Ordinary,
+    /// Tick transaction of the special account executed at the beginning of the block.
+    Tick,
+    /// Tock transaction of the special account executed at the end of the block.
+    Tock,
+}
+
+impl Default for TransactionType {
+    fn default() -> Self {
+        TransactionType::Ordinary
+    }
 }
 
 pub(crate) struct ResolvedExecutionOptions {
@@ -46,6 +101,11 @@ pub(crate) struct ResolvedExecutionOptions {
     pub block_lt: u64,
     pub transaction_lt: u64,
     pub behavior_modifiers: BehaviorModifiers,
+    pub coverage: Option<Arc<CoverageSession>>,
+    pub libraries: HashMap<UInt256, Cell>,
+    pub fetch_libraries: bool,
+    pub balance: Option<u64>,
+    pub transaction_type: TransactionType,
 }
 
 pub(crate) fn blockchain_config_from_boc(context: &ClientContext, b64: &str) -> ClientResult<BlockchainConfig> {
@@ -54,6 +114,43 @@ pub(crate) fn blockchain_config_from_boc(context: &ClientContext, b64: &str) ->
         .map_err(|err| Error::can_not_read_blockchain_config(err))
 }
 
+fn override_gas_limits(
+    config: &BlockchainConfig,
+    gas_limit: Option<u64>,
+    gas_credit: Option<u64>,
+) -> ClientResult<BlockchainConfig> {
+    let mut raw_config = config.raw_config().clone();
+    for index in &[20, 21] {
+        let param = raw_config
+            .config(*index)
+            .map_err(|err| Error::can_not_read_blockchain_config(err))?;
+        let param = match param {
+            Some(ConfigParamEnum::ConfigParam20(mut prices)) => {
+                if let Some(gas_limit) = gas_limit {
+                    prices.gas_limit = gas_limit;
+                    prices.special_gas_limit = gas_limit;
+                }
+                prices.gas_credit = gas_credit.unwrap_or(prices.gas_credit);
+                ConfigParamEnum::ConfigParam20(prices)
+            }
+            Some(ConfigParamEnum::ConfigParam21(mut prices)) => {
+                if let Some(gas_limit) = gas_limit {
+                    prices.gas_limit = gas_limit;
+                    prices.special_gas_limit = gas_limit;
+                }
+                prices.gas_credit = gas_credit.unwrap_or(prices.gas_credit);
+                ConfigParamEnum::ConfigParam21(prices)
+            }
+            _ => continue,
+        };
+        raw_config
+            .set_config(param)
+            .map_err(|err| Error::can_not_read_blockchain_config(err))?;
+    }
+    BlockchainConfig::with_config(raw_config)
+        .map_err(|err| Error::can_not_read_blockchain_config(err))
+}
+
 impl ResolvedExecutionOptions {
     pub async fn from_options(
         context: &Arc<ClientContext>,
@@ -76,15 +173,50 @@ impl ResolvedExecutionOptions {
             chksig_always_succeed: options.chksig_always_succeed.unwrap_or(false),
             ..Default::default()
         };
+        let blockchain_config = if options.gas_limit.is_some() || options.gas_credit.is_some() {
+            Arc::new(override_gas_limits(
+                &params.blockchain_config,
+                options.gas_limit,
+                options.gas_credit,
+            )?)
+        } else {
+            params.blockchain_config
+        };
         Ok(Self {
             block_lt,
             block_time,
-            blockchain_config: params.blockchain_config,
+            blockchain_config,
             signature_id: params.global_id,
             transaction_lt,
             behavior_modifiers,
+            coverage: if options.collect_coverage.unwrap_or_default() {
+                Some(context.coverage.clone())
+            } else {
+                None
+            },
+            libraries: libraries::parse_libraries(context, options.libraries.unwrap_or_default())?,
+            fetch_libraries: options.fetch_libraries.unwrap_or_default(),
+            balance: options.balance,
+            transaction_type: options.transaction_type.unwrap_or_default(),
         })
     }
+
+    /// Fetches the libraries referenced in the trees of `roots` if `fetch_libraries` is set.
+    pub async fn resolve_libraries(
+        &mut self,
+        context: &Arc<ClientContext>,
+        mut roots: Vec<Cell>,
+    ) -> ClientResult<()> {
+        if self.fetch_libraries {
+            roots.extend(self.libraries.values().cloned());
+            libraries::fetch_missing_libraries(context, &mut self.libraries, roots).await?;
+        }
+        Ok(())
+    }
+
+    pub fn libraries_dict(&self) -> ClientResult<HashmapE> {
+        libraries::libraries_dict(&self.libraries)
+    }
 }
 
 pub(crate) async fn resolve_network_params(
@@ -20,11 +20,15 @@ pub(crate) mod conversion;
 #[cfg(feature = "include-zstd")]
 pub(crate) mod compression;
 pub(crate) mod json;
+pub(crate) mod project_storage;
 mod errors;
 
 pub use calc_storage_fee::{
     calc_storage_fee, ParamsOfCalcStorageFee, ResultOfCalcStorageFee
 };
+pub use project_storage::{
+    project_storage, ParamsOfProjectStorage, ResultOfProjectStorage, StorageFeeEpoch,
+};
 pub use conversion::{
     convert_address, AddressStringFormat, ParamsOfConvertAddress, ResultOfConvertAddress,
     get_address_type, ParamsOfGetAddressType, ResultOfGetAddressType,
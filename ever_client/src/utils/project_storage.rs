@@ -0,0 +1,254 @@
+/*
+ * Copyright 2018-2021 EverX Labs Ltd.
+ *
+ * Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+ * this file except in compliance with the License.
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific EVERX DEV software governing permissions and
+ * limitations under the License.
+ *
+ */
+
+use crate::boc::blockchain_config::{
+    accrued_fee, decode_gas_prices, decode_storage_prices, storage_epochs, to_nanotokens, Epoch,
+};
+use crate::boc::internal::deserialize_object_from_boc;
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use crate::tvm::Error;
+use ever_block::{AccountStatus, ConfigParamEnum};
+use std::sync::Arc;
+
+const SECONDS_PER_DAY: u64 = 86400;
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug)]
+pub struct ParamsOfProjectStorage {
+    /// Account BOC or BOC cache reference.
+    pub account: String,
+
+    /// Number of days the account must stay alive since `now`.
+    ///
+    /// If specified then the required top-up is calculated.
+    pub keep_alive_days: Option<u32>,
+
+    /// Unix time in seconds the projection starts at. Default is the current time.
+    pub now: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct StorageFeeEpoch {
+    /// Unix time the storage prices of the epoch are effective since.
+    pub utime_since: u32,
+
+    /// Unix time the storage prices of the epoch are replaced with the next ones.
+    ///
+    /// Missing for the last epoch.
+    pub utime_until: Option<u32>,
+
+    /// Storage fee of the account per day under the epoch prices in nanotokens.
+    pub fee_per_day: String,
+
+    /// Storage fee of the account within the projection period charged
+    /// under the epoch prices in nanotokens.
+    pub fee: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct ResultOfProjectStorage {
+    /// Account balance in nanotokens.
+    pub balance: String,
+
+    /// Storage due of the account in nanotokens.
+    pub due_payment: String,
+
+    /// Storage fee accrued since the last storage payment till `now` in nanotokens.
+    pub storage_fee: String,
+
+    /// Storage fee per day under the prices effective at `now` in nanotokens.
+    pub fee_per_day: String,
+
+    /// Unix time when the balance is exhausted by the storage fee.
+    ///
+    /// Missing if the account does not pay for the storage.
+    pub balance_exhausted_at: Option<u32>,
+
+    /// Unix time since which a transaction on the account freezes it.
+    ///
+    /// Missing if the account is not active or does not pay for the storage.
+    pub freeze_at: Option<u32>,
+
+    /// Unix time since which a transaction on the account deletes it.
+    ///
+    /// Missing if the account does not pay for the storage.
+    pub delete_at: Option<u32>,
+
+    /// Amount in nanotokens which keeps the account balance positive for
+    /// `keep_alive_days` days since `now`.
+    ///
+    /// Missing if `keep_alive_days` is not specified.
+    pub keep_alive_top_up: Option<String>,
+
+    /// Storage fee of the projection period broken down per storage prices epoch.
+    ///
+    /// Projection period starts at the last storage payment and ends at `now`
+    /// or at the end of the `keep_alive_days` period if it is specified.
+    pub epochs: Vec<StorageFeeEpoch>,
+}
+
+// Returns the earliest time since `from` when the accrued storage fee exceeds `limit` nanotokens
+fn time_of_exceeding(epochs: &[Epoch], from: u32, limit: i128) -> Option<u32> {
+    if limit < 0 {
+        return Some(from);
+    }
+    let limit = (limit as u128) << 16;
+    let mut accrued = 0u128;
+    for epoch in epochs {
+        let start = std::cmp::max(from, epoch.since);
+        let end = epoch.until.unwrap_or(u32::MAX);
+        if end <= start || epoch.rate == 0 {
+            continue;
+        }
+        let seconds = (limit - accrued) / epoch.rate + 1;
+        if seconds <= (end - start) as u128 {
+            return Some(start + seconds as u32);
+        }
+        accrued += epoch.rate * (end - start) as u128;
+    }
+    None
+}
+
+/// Projects the storage fee payments of an account.
+///
+/// Calculates when the account balance is exhausted by the storage fee and
+/// since which moment a transaction on the account freezes or deletes it
+/// (the storage due exceeds `freeze_due_limit` or `delete_due_limit` of the
+/// blockchain config). Uninitialized accounts are deleted instead of being frozen.
+/// The calculated moments may be in the past if the account was not touched
+/// by transactions for a long time.
+///
+/// Projection takes into account all the storage prices epochs of the current
+/// blockchain config including the ones which are not effective yet.
+#[api_function]
+pub async fn project_storage(
+    context: Arc<ClientContext>,
+    params: ParamsOfProjectStorage,
+) -> ClientResult<ResultOfProjectStorage> {
+    let account =
+        deserialize_object_from_boc::<ever_block::Account>(&context, &params.account, "account")?
+            .object;
+
+    let storage = account
+        .storage_info()
+        .ok_or(Error::invalid_account_boc("Account is None"))?;
+    let addr = account
+        .get_addr()
+        .ok_or(Error::invalid_account_boc("Account is None"))?;
+    if storage.last_paid() == 0 {
+        return Err(Error::invalid_account_boc(
+            "Account `last_paid` field is not initialized",
+        ));
+    }
+    let is_masterchain = addr.is_masterchain();
+    let config = crate::net::network_params::get_default_params(&context)
+        .await?
+        .blockchain_config;
+    let raw_config = config.raw_config();
+
+    let read_param = |index: u32| {
+        raw_config.config(index).map_err(|err| {
+            Error::internal_error(format!("can not read config param {}: {}", index, err))
+        })
+    };
+    let prices = match read_param(18)? {
+        Some(ConfigParamEnum::ConfigParam18(param)) => decode_storage_prices(&param)?,
+        _ => {
+            return Err(Error::internal_error(
+                "storage prices are missing in config",
+            ))
+        }
+    };
+    let gas_prices = match read_param(if is_masterchain { 20 } else { 21 })? {
+        Some(ConfigParamEnum::ConfigParam20(param)) => decode_gas_prices(&param),
+        Some(ConfigParamEnum::ConfigParam21(param)) => decode_gas_prices(&param),
+        _ => return Err(Error::internal_error("gas prices are missing in config")),
+    };
+
+    let bits = storage.used().bits() as u128;
+    let cells = storage.used().cells() as u128;
+    let epochs = storage_epochs(&prices, bits, cells, is_masterchain);
+
+    let last_paid = storage.last_paid();
+    let now = std::cmp::max(
+        params
+            .now
+            .unwrap_or_else(|| (context.env.now_ms() / 1000) as u32),
+        last_paid,
+    );
+    let balance = account
+        .balance()
+        .map(|balance| balance.grams.as_u128())
+        .unwrap_or_default();
+    let due_payment = storage
+        .due_payment()
+        .map(|due| due.as_u128())
+        .unwrap_or_default();
+    let storage_fee = to_nanotokens(accrued_fee(&epochs, last_paid, now));
+    let fee_per_day = epochs
+        .iter()
+        .find(|epoch| epoch.overlap(now, now + 1) > 0)
+        .map(|epoch| to_nanotokens(epoch.rate * SECONDS_PER_DAY as u128))
+        .unwrap_or_default();
+
+    // storage due of the account is `accrued fee + due payment - balance`
+    let exceeding = |limit: u64| {
+        time_of_exceeding(
+            &epochs,
+            last_paid,
+            balance as i128 + limit as i128 - due_payment as i128,
+        )
+    };
+    let balance_exhausted_at = exceeding(0);
+    let (freeze_at, delete_at) = match account.status() {
+        AccountStatus::AccStateActive => (
+            exceeding(gas_prices.freeze_due_limit),
+            exceeding(gas_prices.delete_due_limit),
+        ),
+        AccountStatus::AccStateUninit => (None, exceeding(gas_prices.freeze_due_limit)),
+        _ => (None, exceeding(gas_prices.delete_due_limit)),
+    };
+
+    let keep_alive_until = params.keep_alive_days.map(|days| {
+        std::cmp::min(now as u64 + days as u64 * SECONDS_PER_DAY, u32::MAX as u64) as u32
+    });
+    let keep_alive_top_up = keep_alive_until.map(|until| {
+        let fee = to_nanotokens(accrued_fee(&epochs, last_paid, until)) + due_payment;
+        fee.saturating_sub(balance).to_string()
+    });
+
+    let horizon = keep_alive_until.unwrap_or(now);
+    let epochs = epochs
+        .iter()
+        .filter(|epoch| epoch.overlap(last_paid, horizon) > 0)
+        .map(|epoch| StorageFeeEpoch {
+            utime_since: epoch.since,
+            utime_until: epoch.until,
+            fee_per_day: to_nanotokens(epoch.rate * SECONDS_PER_DAY as u128).to_string(),
+            fee: to_nanotokens(epoch.rate * epoch.overlap(last_paid, horizon) as u128).to_string(),
+        })
+        .collect();
+
+    Ok(ResultOfProjectStorage {
+        balance: balance.to_string(),
+        due_payment: due_payment.to_string(),
+        storage_fee: storage_fee.to_string(),
+        fee_per_day: fee_per_day.to_string(),
+        balance_exhausted_at,
+        freeze_at,
+        delete_at,
+        keep_alive_top_up,
+        epochs,
+    })
+}
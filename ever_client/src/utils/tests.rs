@@ -89,6 +89,52 @@ async fn test_calc_storage_fee() {
     assert_eq!(result.fee, "330");
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_project_storage() {
+    let client = TestClient::new();
+    let account = base64::encode(&include_bytes!("../boc/test_data/account.boc"));
+
+    let parsed: crate::boc::ResultOfParse = client.request_async(
+        "boc.parse_account",
+        crate::boc::ParamsOfParse { boc: account.clone() }
+    ).await.unwrap();
+    let last_paid = parsed.parsed["last_paid"].as_u64().unwrap() as u32;
+
+    let result: ResultOfProjectStorage = client.request_async(
+        "utils.project_storage",
+        ParamsOfProjectStorage {
+            account: account.clone(),
+            keep_alive_days: None,
+            now: Some(last_paid + 1000),
+        }
+    ).await.unwrap();
+
+    assert_eq!(result.storage_fee, "330");
+    assert_eq!(result.keep_alive_top_up, None);
+    let exhausted_at = result.balance_exhausted_at.unwrap();
+    let delete_at = result.delete_at.unwrap();
+    assert!(exhausted_at <= delete_at);
+    if let Some(freeze_at) = result.freeze_at {
+        assert!(exhausted_at <= freeze_at && freeze_at <= delete_at);
+    }
+
+    // top-up keeps the balance positive till the moment the balance would be exhausted
+    let days = exhausted_at.saturating_sub(last_paid + 1000) / 86400 + 1;
+    let result: ResultOfProjectStorage = client.request_async(
+        "utils.project_storage",
+        ParamsOfProjectStorage {
+            account,
+            keep_alive_days: Some(days),
+            now: Some(last_paid + 1000),
+        }
+    ).await.unwrap();
+
+    assert_ne!(result.keep_alive_top_up.as_deref(), Some("0"));
+    assert!(!result.epochs.is_empty());
+    let epochs_fee: u128 = result.epochs.iter().map(|epoch| epoch.fee.parse::<u128>().unwrap()).sum();
+    assert!(epochs_fee > 0);
+}
+
 #[test]
 fn test_compression() {
     let client = TestClient::new();
@@ -28,8 +28,9 @@ mod tests;
 
 pub use error::{Error, Result};
 pub use message_monitor::{
-    MessageMonitor, MessageMonitoringParams, MessageMonitoringResult, MessageMonitoringStatus,
-    MessageMonitoringTransaction, MessageMonitoringTransactionCompute, MonitorFetchWaitMode,
-    MonitoredMessage, MonitoringQueueInfo,
+    MessageMonitor, MessageMonitoringAbortedTransaction, MessageMonitoringParams,
+    MessageMonitoringResult, MessageMonitoringStatus, MessageMonitoringTransaction,
+    MessageMonitoringTransactionCompute, MessageMonitoringTransactionTree, MonitorFetchWaitMode,
+    MonitoredMessage, MonitoringQueueInfo, RempTimelineEntry,
 };
 pub use sdk_services::{MessageMonitorSdkServices, NetSubscription};
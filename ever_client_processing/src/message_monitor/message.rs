@@ -56,6 +56,17 @@ pub struct MessageMonitoringParams {
     /// User defined data associated with this message.
     /// Helps to identify this message when user received `MessageMonitoringResult`.
     pub user_data: Option<Value>,
+
+    /// Wait for the whole tree of transactions caused by the message.
+    ///
+    /// If `true` then `Finalized` result is reported only when all internal messages
+    /// produced by the message transaction and its descendants are processed.
+    /// Default is `false`.
+    pub wait_transaction_tree: Option<bool>,
+
+    /// Collect the REMP statuses of the message into `MessageMonitoringResult.remp_timeline`.
+    /// Default is `false`.
+    pub collect_remp_timeline: Option<bool>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, ApiType)]
@@ -77,6 +88,16 @@ pub struct MessageMonitoringResult {
     /// User defined data related to this message.
     /// This is the same value as passed before with `MessageMonitoringParams` or `SendMessageParams`.
     pub user_data: Option<Value>,
+
+    /// Summary of the transaction tree caused by the message.
+    ///
+    /// Present for the `Finalized` messages monitored with `wait_transaction_tree`.
+    pub transaction_tree: Option<MessageMonitoringTransactionTree>,
+
+    /// REMP statuses received for the message before the result has been resolved.
+    ///
+    /// Present for the messages monitored with `collect_remp_timeline`.
+    pub remp_timeline: Option<Vec<RempTimelineEntry>>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, ApiType)]
@@ -109,3 +130,37 @@ pub struct MessageMonitoringTransactionCompute {
     /// Compute phase exit code.
     pub exit_code: i32,
 }
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, ApiType)]
+pub struct MessageMonitoringTransactionTree {
+    /// Count of the transactions in the tree including the message transaction.
+    pub transaction_count: u32,
+    /// `false` if some internal messages of the tree were not processed
+    /// during the tree waiting timeout.
+    pub complete: bool,
+    /// Aborted descendant transactions.
+    pub aborted: Vec<MessageMonitoringAbortedTransaction>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, ApiType)]
+pub struct MessageMonitoringAbortedTransaction {
+    /// Hash of the transaction.
+    pub hash: String,
+    /// Address of the transaction account.
+    pub address: String,
+    /// Compute phase exit code.
+    pub exit_code: Option<i32>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, ApiType)]
+pub struct RempTimelineEntry {
+    /// REMP status kind, e.g. `SentToValidators`, `IncludedIntoBlock`,
+    /// `IncludedIntoAcceptedBlock`, `Finalized`, `RejectedByFullnode`.
+    pub kind: String,
+    /// Time of the status in milliseconds.
+    pub timestamp: u64,
+    /// Identifier of the validator or the fullnode that reported the status.
+    pub source: Option<String>,
+    /// Reason of the message rejection.
+    pub rejection_reason: Option<String>,
+}
@@ -6,7 +6,8 @@ mod monitor_queues;
 #[cfg(test)]
 pub(crate) use message::CellFromBoc;
 pub use message::{
-    MessageMonitoringParams, MessageMonitoringResult, MessageMonitoringStatus,
-    MessageMonitoringTransaction, MessageMonitoringTransactionCompute, MonitoredMessage,
+    MessageMonitoringAbortedTransaction, MessageMonitoringParams, MessageMonitoringResult,
+    MessageMonitoringStatus, MessageMonitoringTransaction, MessageMonitoringTransactionCompute,
+    MessageMonitoringTransactionTree, MonitoredMessage, RempTimelineEntry,
 };
 pub use monitor::{MessageMonitor, MonitorFetchWaitMode, MonitoringQueueInfo};
@@ -1,4 +1,6 @@
-use crate::message_monitor::message::{MessageMonitoringParams, MessageMonitoringResult};
+use crate::message_monitor::message::{
+    MessageMonitoringParams, MessageMonitoringResult, MessageMonitoringStatus, RempTimelineEntry,
+};
 use crate::message_monitor::monitor_queues::{BufferedMessages, MonitorQueues, ADDING_TIMEOUT_MS};
 use crate::message_monitor::queue::BufferedMessage;
 use crate::sdk_services::MessageMonitorSdkServices;
@@ -23,6 +25,15 @@ struct MonitorState<Sdk: MessageMonitorSdkServices + Send + Sync + 'static> {
     notify_resolved: Arc<tokio::sync::watch::Sender<crate::error::Result<()>>>,
     listen_resolved: tokio::sync::watch::Receiver<crate::error::Result<()>>,
     active_subscriptions: Mutex<HashMap<usize, HashSet<String>>>,
+
+    /// Hashes of the messages which results must wait for the whole transaction tree
+    tree_messages: Mutex<HashSet<String>>,
+
+    /// Hashes of the finalized messages which results are waiting for the transaction tree
+    deferred_messages: Mutex<HashSet<String>>,
+
+    /// REMP statuses collected for the messages monitored with `collect_remp_timeline`
+    remp_timelines: Mutex<HashMap<String, Vec<RempTimelineEntry>>>,
 }
 
 #[derive(Deserialize, Serialize, ApiType, Default)]
@@ -87,6 +98,9 @@ impl<Sdk: MessageMonitorSdkServices + Send + Sync> MonitorState<Sdk> {
             sdk,
             queues: RwLock::new(MonitorQueues::new()),
             active_subscriptions: Mutex::new(HashMap::new()),
+            tree_messages: Mutex::new(HashSet::new()),
+            deferred_messages: Mutex::new(HashSet::new()),
+            remp_timelines: Mutex::new(HashMap::new()),
             notify_resolved: Arc::new(sender),
             listen_resolved: receiver,
         }
@@ -101,12 +115,20 @@ impl<Sdk: MessageMonitorSdkServices + Send + Sync> MonitorState<Sdk> {
             return Ok(());
         }
         let mut buffered = Vec::new();
+        let mut tree_messages = Vec::new();
+        let mut remp_messages = Vec::new();
         for message in messages {
-            buffered.push(BufferedMessage {
-                hash: message.message.hash(&self.sdk)?,
-                message,
-            });
+            let hash = message.message.hash(&self.sdk)?;
+            if message.wait_transaction_tree.unwrap_or(false) {
+                tree_messages.push(hash.clone());
+            }
+            if message.collect_remp_timeline.unwrap_or(false) {
+                remp_messages.push((hash.clone(), Vec::new()));
+            }
+            buffered.push(BufferedMessage { hash, message });
         }
+        self.tree_messages.lock().unwrap().extend(tree_messages);
+        self.remp_timelines.lock().unwrap().extend(remp_messages);
 
         let mut queues = self.queues.write().unwrap();
         let should_start_buffering_timer = !queues.has_buffered();
@@ -141,7 +163,22 @@ impl<Sdk: MessageMonitorSdkServices + Send + Sync> MonitorState<Sdk> {
     }
 
     fn cancel_monitor(&self, queue: &str) -> crate::error::Result<()> {
-        self.queues.write().unwrap().remove(queue);
+        let mut queues = self.queues.write().unwrap();
+        if let Some(removed) = queues.remove(queue) {
+            // message can be monitored by several queues
+            let hashes = removed
+                .hashes()
+                .filter(|hash| !queues.queues.values().any(|queue| queue.contains(hash)))
+                .collect::<Vec<_>>();
+            let mut tree_messages = self.tree_messages.lock().unwrap();
+            let mut deferred_messages = self.deferred_messages.lock().unwrap();
+            let mut remp_timelines = self.remp_timelines.lock().unwrap();
+            for hash in hashes {
+                tree_messages.remove(hash);
+                deferred_messages.remove(hash);
+                remp_timelines.remove(hash);
+            }
+        }
         Ok(())
     }
 
@@ -174,7 +211,9 @@ impl<Sdk: MessageMonitorSdkServices + Send + Sync> MonitorState<Sdk> {
             let self1 = self1.clone();
             async move {
                 match results {
-                    Ok(results) => {
+                    Ok(mut results) => {
+                        self1.clone().defer_transaction_tree_results(&results);
+                        self1.attach_remp_timelines(&mut results);
                         let empty_subscriptions =
                             self1.resolve_results_and_return_empty_subscriptions(&results);
                         for subscription in empty_subscriptions {
@@ -188,6 +227,18 @@ impl<Sdk: MessageMonitorSdkServices + Send + Sync> MonitorState<Sdk> {
                 }
             }
         };
+        let remp_hashes = {
+            let remp_timelines = self.remp_timelines.lock().unwrap();
+            buffered
+                .hashes
+                .iter()
+                .filter(|hash| remp_timelines.contains_key(*hash))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        for hash in remp_hashes {
+            self.clone().subscribe_remp_receipts(hash).await;
+        }
         let subscription = self
             .sdk
             .subscribe_for_recent_ext_in_message_statuses(buffered.messages, callback)
@@ -199,13 +250,95 @@ impl<Sdk: MessageMonitorSdkServices + Send + Sync> MonitorState<Sdk> {
         Ok(())
     }
 
+    /// Collects the REMP statuses of the message until its result is resolved.
+    ///
+    /// Timeline is optional, so the subscription errors are ignored.
+    async fn subscribe_remp_receipts(self: Arc<Self>, hash: String) {
+        let self1 = self.clone();
+        let message_hash = hash.clone();
+        let callback = move |entry: crate::error::Result<RempTimelineEntry>| {
+            if let Ok(entry) = entry {
+                if let Some(timeline) = self1.remp_timelines.lock().unwrap().get_mut(&message_hash)
+                {
+                    timeline.push(entry);
+                }
+            }
+            futures::future::ready(())
+        };
+        if let Ok(subscription) = self.sdk.subscribe_for_remp_receipts(&hash, callback).await {
+            self.active_subscriptions
+                .lock()
+                .unwrap()
+                .insert(subscription.0, vec![hash].into_iter().collect());
+        }
+    }
+
+    fn attach_remp_timelines(&self, results: &mut Vec<MessageMonitoringResult>) {
+        let deferred_messages = self.deferred_messages.lock().unwrap();
+        let mut remp_timelines = self.remp_timelines.lock().unwrap();
+        for result in results {
+            if !deferred_messages.contains(&result.hash) {
+                result.remp_timeline = remp_timelines.remove(&result.hash);
+            }
+        }
+    }
+
+    /// Starts waiting for the transaction trees of the finalized messages
+    /// monitored with `wait_transaction_tree`.
+    ///
+    /// Such messages are marked as deferred, so their results are not resolved in queues
+    /// until the transaction tree is processed.
+    fn defer_transaction_tree_results(self: Arc<Self>, results: &[MessageMonitoringResult]) {
+        let mut tree_messages = self.tree_messages.lock().unwrap();
+        for result in results {
+            if result.status == MessageMonitoringStatus::Finalized
+                && tree_messages.remove(&result.hash)
+            {
+                self.deferred_messages
+                    .lock()
+                    .unwrap()
+                    .insert(result.hash.clone());
+                self.clone().wait_for_transaction_tree(result.clone());
+            }
+        }
+    }
+
+    fn wait_for_transaction_tree(self: Arc<Self>, mut result: MessageMonitoringResult) {
+        self.clone().sdk.spawn(async move {
+            match self.sdk.wait_for_transaction_tree(&result.hash).await {
+                Ok(tree) => result.transaction_tree = Some(tree),
+                Err(err) => {
+                    result.error = Some(format!("Transaction tree waiting failed: {}", err.message))
+                }
+            }
+            self.deferred_messages.lock().unwrap().remove(&result.hash);
+            let mut results = vec![result];
+            self.attach_remp_timelines(&mut results);
+            {
+                let mut queues = self.queues.write().unwrap();
+                for queue in queues.queues.values_mut() {
+                    queue.resolve(&results);
+                }
+            }
+            self.notify_resolved.send(Ok(())).ok();
+        });
+    }
+
     fn resolve_results_and_return_empty_subscriptions(
         &self,
         results: &Vec<MessageMonitoringResult>,
     ) -> Vec<NetSubscription> {
+        let resolved = {
+            let deferred_messages = self.deferred_messages.lock().unwrap();
+            results
+                .iter()
+                .filter(|result| !deferred_messages.contains(&result.hash))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
         let mut queues = self.queues.write().unwrap();
         for queue in queues.queues.values_mut() {
-            queue.resolve(&results);
+            queue.resolve(&resolved);
         }
 
         let mut active_subscriptions = self.active_subscriptions.lock().unwrap();
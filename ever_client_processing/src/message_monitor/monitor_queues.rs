@@ -38,8 +38,8 @@ impl MonitorQueues {
             .unwrap_or_default()
     }
 
-    pub fn remove(&mut self, queue: &str) {
-        self.queues.remove(queue);
+    pub fn remove(&mut self, queue: &str) -> Option<MonitoringQueue> {
+        self.queues.remove(queue)
     }
 
     pub fn add_buffered(&mut self, now_ms: u64, queue: &str, messages: Vec<BufferedMessage>) {
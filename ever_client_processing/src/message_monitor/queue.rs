@@ -27,6 +27,17 @@ impl MonitoringQueue {
         }
     }
 
+    pub fn contains(&self, hash: &str) -> bool {
+        self.resolving.contains_key(hash) || self.buffered.iter().any(|x| x.hash == hash)
+    }
+
+    pub fn hashes(&self) -> impl Iterator<Item = &String> {
+        self.buffered
+            .iter()
+            .map(|x| &x.hash)
+            .chain(self.resolving.keys())
+    }
+
     pub fn get_info(&self) -> MonitoringQueueInfo {
         MonitoringQueueInfo {
             unresolved: (self.buffered.len() + self.resolving.len()) as u32,
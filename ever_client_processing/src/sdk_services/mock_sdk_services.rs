@@ -1,4 +1,7 @@
-use crate::message_monitor::{CellFromBoc, MessageMonitoringParams, MessageMonitoringResult};
+use crate::message_monitor::{
+    CellFromBoc, MessageMonitoringParams, MessageMonitoringResult,
+    MessageMonitoringTransactionTree, RempTimelineEntry,
+};
 use crate::{error, Error, MessageMonitorSdkServices, NetSubscription};
 use base64::Engine;
 use std::collections::{HashMap, HashSet};
@@ -14,6 +17,9 @@ pub struct MockSdkServices {
 
 struct State {
     results: RwLock<HashMap<String, MessageMonitoringResult>>,
+    trees: RwLock<HashMap<String, MessageMonitoringTransactionTree>>,
+    remp_receipts: RwLock<HashMap<String, Vec<RempTimelineEntry>>>,
+    delivered_remp_receipts: RwLock<HashMap<String, usize>>,
     next_subscription: Mutex<usize>,
     subscriptions: RwLock<HashSet<usize>>,
 }
@@ -49,6 +55,38 @@ impl State {
         subscription
     }
 
+    fn subscribe_remp<F: Future<Output = ()> + Send>(
+        self: Arc<Self>,
+        hash: String,
+        callback: impl Fn(error::Result<RempTimelineEntry>) -> F + Send + Sync + 'static,
+    ) -> usize {
+        let subscription = self.create_subscription();
+        tokio::spawn(async move {
+            let mut delivered = 0;
+            while self.contains_subscription(subscription) {
+                let receipts = self
+                    .remp_receipts
+                    .read()
+                    .unwrap()
+                    .get(&hash)
+                    .map(|x| x[delivered.min(x.len())..].to_vec())
+                    .unwrap_or_default();
+                delivered += receipts.len();
+                for receipt in receipts {
+                    callback(Ok(receipt)).await;
+                    *self
+                        .delivered_remp_receipts
+                        .write()
+                        .unwrap()
+                        .entry(hash.clone())
+                        .or_default() += 1;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        });
+        subscription
+    }
+
     fn find_results(
         &self,
         messages: HashMap<String, MessageMonitoringParams>,
@@ -101,6 +139,9 @@ impl MockSdkServices {
         Self {
             state: Arc::new(State {
                 results: RwLock::new(HashMap::new()),
+                trees: RwLock::new(HashMap::new()),
+                remp_receipts: RwLock::new(HashMap::new()),
+                delivered_remp_receipts: RwLock::new(HashMap::new()),
                 next_subscription: Mutex::new(1),
                 subscriptions: RwLock::new(HashSet::new()),
             }),
@@ -112,6 +153,31 @@ impl MockSdkServices {
         recent.extend(messages.into_iter().map(|x| (x.hash.clone(), x)))
     }
 
+    pub fn add_transaction_trees(&self, trees: Vec<(String, MessageMonitoringTransactionTree)>) {
+        self.state.trees.write().unwrap().extend(trees)
+    }
+
+    pub fn add_remp_receipts(&self, hash: &str, receipts: Vec<RempTimelineEntry>) {
+        self.state
+            .remp_receipts
+            .write()
+            .unwrap()
+            .entry(hash.to_string())
+            .or_default()
+            .extend(receipts)
+    }
+
+    /// Number of the REMP receipts of the message passed to the subscription callbacks.
+    pub fn delivered_remp_receipt_count(&self, hash: &str) -> usize {
+        self.state
+            .delivered_remp_receipts
+            .read()
+            .unwrap()
+            .get(hash)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     pub fn active_subscription_count(&self) -> usize {
         self.state.subscriptions.read().unwrap().len()
     }
@@ -129,11 +195,35 @@ impl MessageMonitorSdkServices for MockSdkServices {
         ))
     }
 
+    async fn subscribe_for_remp_receipts<F: Future<Output = ()> + Send>(
+        &self,
+        message_hash: &str,
+        callback: impl Fn(error::Result<RempTimelineEntry>) -> F + Send + Sync + 'static,
+    ) -> error::Result<NetSubscription> {
+        Ok(NetSubscription(
+            self.state
+                .clone()
+                .subscribe_remp(message_hash.to_string(), callback),
+        ))
+    }
+
     async fn unsubscribe(&self, subscription: NetSubscription) -> error::Result<()> {
         self.state.remove_subscription(subscription.0);
         Ok(())
     }
 
+    async fn wait_for_transaction_tree(
+        &self,
+        message_hash: &str,
+    ) -> error::Result<MessageMonitoringTransactionTree> {
+        loop {
+            if let Some(tree) = self.state.trees.read().unwrap().get(message_hash) {
+                return Ok(tree.clone());
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
     async fn sleep(&self, ms: u64) -> crate::Result<()> {
         tokio::time::sleep(Duration::from_millis(ms)).await;
         Ok(())
@@ -2,7 +2,10 @@
 mod mock_sdk_services;
 
 use crate::error;
-use crate::message_monitor::{MessageMonitoringParams, MessageMonitoringResult};
+use crate::message_monitor::{
+    MessageMonitoringParams, MessageMonitoringResult, MessageMonitoringTransactionTree,
+    RempTimelineEntry,
+};
 #[cfg(test)]
 pub use mock_sdk_services::MockSdkServices;
 use std::future::Future;
@@ -18,8 +21,22 @@ pub trait MessageMonitorSdkServices {
         callback: impl Fn(error::Result<Vec<MessageMonitoringResult>>) -> F + Send + Sync + 'static,
     ) -> error::Result<NetSubscription>;
 
+    /// Subscribes for the REMP statuses of the specified message.
+    async fn subscribe_for_remp_receipts<F: Future<Output = ()> + Send>(
+        &self,
+        message_hash: &str,
+        callback: impl Fn(error::Result<RempTimelineEntry>) -> F + Send + Sync + 'static,
+    ) -> error::Result<NetSubscription>;
+
     async fn unsubscribe(&self, subscription: NetSubscription) -> error::Result<()>;
 
+    /// Waits until all the internal messages of the transaction tree
+    /// caused by the specified message are processed.
+    async fn wait_for_transaction_tree(
+        &self,
+        message_hash: &str,
+    ) -> error::Result<MessageMonitoringTransactionTree>;
+
     fn spawn(&self, future: impl Future<Output = ()> + Send + 'static);
 
     async fn sleep(&self, ms: u64) -> error::Result<()>;
@@ -1,6 +1,7 @@
 use crate::message_monitor::{
-    MessageMonitor, MessageMonitoringParams, MessageMonitoringResult, MessageMonitoringStatus,
-    MessageMonitoringTransaction, MonitorFetchWaitMode,
+    MessageMonitor, MessageMonitoringAbortedTransaction, MessageMonitoringParams,
+    MessageMonitoringResult, MessageMonitoringStatus, MessageMonitoringTransaction,
+    MessageMonitoringTransactionTree, MonitorFetchWaitMode, RempTimelineEntry,
 };
 use crate::sdk_services::MockSdkServices;
 use crate::MonitoredMessage;
@@ -230,6 +231,129 @@ async fn test_buffering() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_wait_transaction_tree() {
+    let api = sdk_services();
+    let mon = MessageMonitor::new(api.clone());
+    let mut tree_msg = msg(1, 1);
+    tree_msg.wait_transaction_tree = Some(true);
+    mon.monitor_messages("1", vec![tree_msg, msg(2, 2)]).unwrap();
+    api.add_recent_ext_in_messages(vec![
+        msg_res(1, MessageMonitoringStatus::Finalized),
+        msg_res(2, MessageMonitoringStatus::Finalized),
+    ]);
+    let results = mon
+        .fetch_next_monitor_results("1", MonitorFetchWaitMode::AtLeastOne)
+        .await
+        .unwrap();
+    assert_eq!(results, vec![msg_res(2, MessageMonitoringStatus::Finalized)]);
+    let info = mon.get_queue_info("1").unwrap();
+    assert_eq!(info.unresolved, 1);
+
+    let tree = MessageMonitoringTransactionTree {
+        transaction_count: 3,
+        complete: true,
+        aborted: vec![MessageMonitoringAbortedTransaction {
+            hash: hash(3),
+            address: addr(3),
+            exit_code: Some(101),
+        }],
+    };
+    api.add_transaction_trees(vec![(hash(1), tree.clone())]);
+    let results = mon
+        .fetch_next_monitor_results("1", MonitorFetchWaitMode::All)
+        .await
+        .unwrap();
+    let mut expected = msg_res(1, MessageMonitoringStatus::Finalized);
+    expected.transaction_tree = Some(tree);
+    assert_eq!(results, vec![expected]);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_cancel_monitor_forgets_message_options() {
+    let api = sdk_services();
+    let mon = MessageMonitor::new(api.clone());
+    let mut tree_msg = msg(1, 1);
+    tree_msg.wait_transaction_tree = Some(true);
+    tree_msg.collect_remp_timeline = Some(true);
+    mon.monitor_messages("1", vec![tree_msg]).unwrap();
+    mon.cancel_monitor("1").unwrap();
+
+    mon.monitor_messages("2", vec![msg(1, 1)]).unwrap();
+    api.add_recent_ext_in_messages(vec![msg_res(1, MessageMonitoringStatus::Finalized)]);
+    let results = tokio::time::timeout(
+        Duration::from_secs(10),
+        mon.fetch_next_monitor_results("2", MonitorFetchWaitMode::All),
+    )
+    .await
+    .expect("result must not wait for the transaction tree")
+    .unwrap();
+    assert_eq!(results, vec![msg_res(1, MessageMonitoringStatus::Finalized)]);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_reserved_status_resolved() {
+    let api = sdk_services();
+    let mon = MessageMonitor::new(api.clone());
+    mon.monitor_messages("1", vec![msg(1, 1)]).unwrap();
+    api.add_recent_ext_in_messages(vec![msg_res(1, MessageMonitoringStatus::Reserved)]);
+    let results = mon
+        .fetch_next_monitor_results("1", MonitorFetchWaitMode::All)
+        .await
+        .unwrap();
+    assert_eq!(results, vec![msg_res(1, MessageMonitoringStatus::Reserved)]);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_collect_remp_timeline() {
+    let api = sdk_services();
+    let mon = MessageMonitor::new(api.clone());
+    let receipts = vec![
+        remp_entry("IncludedIntoBlock", 1),
+        remp_entry("IncludedIntoAcceptedBlock", 2),
+        remp_entry("Finalized", 3),
+    ];
+    api.add_remp_receipts(&hash(1), receipts.clone());
+    let mut remp_msg = msg(1, 1);
+    remp_msg.collect_remp_timeline = Some(true);
+    mon.monitor_messages("1", vec![remp_msg, msg(2, 2)]).unwrap();
+    wait_until(|| api.delivered_remp_receipt_count(&hash(1)) == 3).await;
+    api.add_recent_ext_in_messages(vec![
+        msg_res(1, MessageMonitoringStatus::Finalized),
+        msg_res(2, MessageMonitoringStatus::Finalized),
+    ]);
+    let results = mon
+        .fetch_next_monitor_results("1", MonitorFetchWaitMode::All)
+        .await
+        .unwrap();
+    let mut expected = msg_res(1, MessageMonitoringStatus::Finalized);
+    expected.remp_timeline = Some(receipts);
+    assert_eq!(
+        sorted(results, |x| &x.hash),
+        vec![expected, msg_res(2, MessageMonitoringStatus::Finalized)]
+    );
+    wait_until(|| api.active_subscription_count() == 0).await;
+}
+
+async fn wait_until(condition: impl Fn() -> bool) {
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while !condition() {
+            sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("condition is not met in time");
+}
+
+fn remp_entry(kind: &str, timestamp: u64) -> RempTimelineEntry {
+    RempTimelineEntry {
+        kind: kind.to_string(),
+        timestamp,
+        source: None,
+        rejection_reason: None,
+    }
+}
+
 fn hash(n: usize) -> String {
     UInt256::from_be_bytes(&n.to_be_bytes()).as_hex_string()
 }
@@ -251,6 +375,8 @@ fn msg(h: usize, w: u32) -> MessageMonitoringParams {
         },
         wait_until: w,
         user_data: None,
+        wait_transaction_tree: None,
+        collect_remp_timeline: None,
     }
 }
 
@@ -265,6 +391,8 @@ fn msg_res(h: usize, s: MessageMonitoringStatus) -> MessageMonitoringResult {
         }),
         error: None,
         user_data: None,
+        transaction_tree: None,
+        remp_timeline: None,
     }
 }
 
@@ -84,16 +84,28 @@ pub struct Transaction {
 impl TryFrom<&ever_block::Transaction> for Transaction {
     type Error = anyhow::Error;
     fn try_from(transaction: &ever_block::Transaction) -> Result<Self> {
-        let descr = if let TransactionDescr::Ordinary(descr) = transaction.read_description()? {
-            descr
-        } else {
-            return Err(SdkError::InvalidData {
-                msg: "Invalid transaction type".to_owned(),
+        let (storage_ph, compute_ph, action, aborted) = match transaction.read_description()? {
+            TransactionDescr::Ordinary(descr) => (
+                descr.storage_ph,
+                descr.compute_ph,
+                descr.action,
+                descr.aborted,
+            ),
+            TransactionDescr::TickTock(descr) => (
+                Some(descr.storage),
+                descr.compute_ph,
+                descr.action,
+                descr.aborted,
+            ),
+            _ => {
+                return Err(SdkError::InvalidData {
+                    msg: "Invalid transaction type".to_owned(),
+                }
+                .into())
             }
-            .into());
         };
 
-        let storage_phase = if let Some(phase) = descr.storage_ph {
+        let storage_phase = if let Some(phase) = storage_ph {
             Some(StoragePhase {
                 status_change: phase.status_change,
                 storage_fees_collected: grams_to_u64(&phase.storage_fees_collected)?,
@@ -102,7 +114,7 @@ impl TryFrom<&ever_block::Transaction> for Transaction {
             None
         };
 
-        let compute_phase = match descr.compute_ph {
+        let compute_phase = match compute_ph {
             TrComputePhase::Skipped(ph) => ComputePhase {
                 skipped_reason: Some(ph.reason),
                 exit_code: None,
@@ -123,7 +135,7 @@ impl TryFrom<&ever_block::Transaction> for Transaction {
             }
         };
 
-        let action_phase = if let Some(phase) = descr.action {
+        let action_phase = if let Some(phase) = action {
             Some(ActionPhase {
                 success: phase.success,
                 valid: phase.valid,
@@ -161,7 +173,7 @@ impl TryFrom<&ever_block::Transaction> for Transaction {
             in_msg,
             out_msgs,
             out_messages: out_messages,
-            aborted: descr.aborted,
+            aborted,
             total_fees: grams_to_u64(&transaction.total_fees().grams)?,
             storage: storage_phase,
             compute: compute_phase,